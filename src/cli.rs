@@ -1,7 +1,8 @@
 //! CLI
 
-use clap::{Parser, Subcommand};
-use std::io::{self, Read};
+use clap::{Parser, Subcommand, ValueEnum};
+use futures_util::{Stream, StreamExt};
+use std::io::{self, Read, Write};
 use std::sync::Arc;
 
 use crate::{runtime, templates, tools, types};
@@ -32,6 +33,10 @@ enum Commands {
     Children {
         /// URL
         url: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
     },
 
     /// Map Page URLs
@@ -83,6 +88,15 @@ enum Commands {
     },
 }
 
+/// Output format for streaming-capable commands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// One pretty-printed JSON document once everything is done
+    Json,
+    /// Newline-delimited JSON, one value per line, flushed as each completes
+    Ndjson,
+}
+
 pub fn read_input(input: &str, ctx: Arc<types::Context>) -> String {
     if input == "-" {
         // Read from stdin
@@ -122,6 +136,28 @@ pub fn print_json<T: serde::Serialize>(value: &T) {
     }
 }
 
+/// Write `value` as one compact JSON line and flush, so consumers see each
+/// result as soon as it completes.
+pub fn write_ndjson_line<W: Write, T: serde::Serialize>(out: &mut W, value: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, value)?;
+    out.write_all(b"\n")?;
+    out.flush()
+}
+
+/// Write each child URL of a `qrawl_children_stream` as an NDJSON line,
+/// returning at the first write error — a closed pipe (e.g. `| head`) ends the
+/// crawl, since dropping the stream stops dispatching new fetches.
+pub async fn write_children_ndjson<W: Write, T>(
+    out: &mut W,
+    children: impl Stream<Item = (String, T)>,
+) -> io::Result<()> {
+    let mut children = Box::pin(children);
+    while let Some((child_url, _html)) = children.next().await {
+        write_ndjson_line(out, &child_url)?;
+    }
+    Ok(())
+}
+
 pub fn run() {
     let cli = Cli::parse();
     let ctx_arc = Arc::new(if cli.fast {
@@ -153,7 +189,17 @@ pub fn run() {
             }
         }
 
-        Commands::Children { url } => {
+        Commands::Children {
+            url,
+            format: OutputFormat::Ndjson,
+        } => runtime::block_on(async {
+            let children =
+                templates::qrawl_children_stream(vec![url.to_string()], (*ctx_arc).clone());
+            let stdout = io::stdout();
+            let _ = write_children_ndjson(&mut stdout.lock(), children).await;
+        }),
+
+        Commands::Children { url, .. } => {
             let result = runtime::block_on(templates::qrawl_children(
                 vec![url.to_string()],
                 (*ctx_arc).clone(),
@@ -212,3 +258,79 @@ pub fn run() {
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{write_children_ndjson, write_ndjson_line};
+    use std::io::{self, Write};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn ndjson_lines_parse_independently() {
+        let mut out = Vec::new();
+        write_ndjson_line(&mut out, &"https://example.com/a").unwrap();
+        write_ndjson_line(
+            &mut out,
+            &serde_json::json!({"url": "https://example.com/b\nc"}),
+        )
+        .unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            serde_json::from_str::<serde_json::Value>(line).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn children_ndjson_writes_one_line_per_child() {
+        let children = futures_util::stream::iter(vec![
+            (
+                "https://example.com/a".to_string(),
+                "<html>a</html>".to_string(),
+            ),
+            (
+                "https://example.com/b".to_string(),
+                "<html>b</html>".to_string(),
+            ),
+        ]);
+        let mut out = Vec::new();
+        write_children_ndjson(&mut out, children).await.unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let urls: Vec<String> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b"]);
+    }
+
+    /// A writer whose reader went away, like stdout into `| head -1`.
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn children_ndjson_stops_pulling_on_closed_pipe() {
+        let pulled = AtomicUsize::new(0);
+        let children = futures_util::stream::iter(0..10).map(|i| {
+            pulled.fetch_add(1, Ordering::SeqCst);
+            (format!("https://example.com/{i}"), ())
+        });
+
+        let err = write_children_ndjson(&mut ClosedPipe, children)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(pulled.load(Ordering::SeqCst), 1);
+    }
+}
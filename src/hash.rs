@@ -0,0 +1,9 @@
+//! Stable Hashing
+
+/// 64-bit FNV-1a: stable across platforms and Rust versions, unlike
+/// `DefaultHasher`, so hashes can be stored and compared between crawls.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
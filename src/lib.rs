@@ -3,8 +3,10 @@ pub mod macros;
 
 pub mod cli;
 pub mod errors;
+mod hash;
 pub mod runtime;
 pub mod selectors;
 pub mod templates;
+mod test_support;
 pub mod tools;
 pub mod types;
@@ -39,7 +39,7 @@ macro_rules! chain {
 
     // Helper: Extract functions (Vec<(url, String)> -> Vec<(url, Vec<String>)>)
     (@process_extract $items:expr, $ctx:expr, $fn:expr $(, $rest:ident)*) => {{
-        let concurrency = $ctx.concurrency;
+        let concurrency = $ctx.effective_concurrency();
         let items: Vec<(String, Vec<String>)> = $crate::tools::batch::batch(
             $items,
             concurrency,
@@ -79,13 +79,15 @@ macro_rules! chain {
         $crate::chain!(@process_extract $items, $ctx, $crate::tools::extract::extract_phones $(, $rest)*)
     }};
 
-    // map_children: batched per-item, needs URL from tuple, flattens Vec<String> results
+    // map_children: batched per-item, needs URL from tuple, flattens Vec<String> results,
+    // drops children outside the context's domain filters
     (@process $items:expr, $ctx:expr, map_children $(, $rest:ident)*) => {{
-        let concurrency = $ctx.concurrency;
+        let concurrency = $ctx.effective_concurrency();
         let items: Vec<(String, String)> = $crate::tools::batch::batch(
             $items,
             concurrency,
             |(url, html): (String, String)| async move {
+                let html = $crate::types::Html::new(html);
                 let children = $crate::tools::map::map_children(&html, &url).await;
                 children.into_iter()
                     .map(|child| (child.clone(), child))
@@ -94,17 +96,20 @@ macro_rules! chain {
         ).await
         .into_iter()
         .flatten()
+        .filter(|(child, _)| $ctx.allows_url(child))
         .collect();
         $crate::chain!(@process items, $ctx $(, $rest)*)
     }};
 
-    // map_page: batched per-item, needs URL from tuple, flattens Vec<String> results
+    // map_page: batched per-item, needs URL from tuple, flattens Vec<String> results,
+    // drops links outside the context's domain filters
     (@process $items:expr, $ctx:expr, map_page $(, $rest:ident)*) => {{
-        let concurrency = $ctx.concurrency;
+        let concurrency = $ctx.effective_concurrency();
         let items: Vec<(String, String)> = $crate::tools::batch::batch(
             $items,
             concurrency,
             |(url, html): (String, String)| async move {
+                let html = $crate::types::Html::new(html);
                 let links = $crate::tools::map::map_page(&html, &url).await;
                 links.into_iter()
                     .map(|link| (link.clone(), link))
@@ -113,13 +118,14 @@ macro_rules! chain {
         ).await
         .into_iter()
         .flatten()
+        .filter(|(link, _)| $ctx.allows_url(link))
         .collect();
         $crate::chain!(@process items, $ctx $(, $rest)*)
     }};
 
     // clean_html: per-item batched, returns String (infallible)
     (@process $items:expr, $ctx:expr, clean_html $(, $rest:ident)*) => {{
-        let concurrency = $ctx.concurrency;
+        let concurrency = $ctx.effective_concurrency();
         let items: Vec<(String, String)> = $crate::tools::batch::batch(
             $items,
             concurrency,
@@ -136,7 +142,7 @@ macro_rules! chain {
 
     // Default: per-item batched function returning Result (fetch_*, etc.)
     (@process $items:expr, $ctx:expr, $fn:ident $(, $rest:ident)*) => {{
-        let concurrency = $ctx.concurrency;
+        let concurrency = $ctx.effective_concurrency();
         let items: Vec<(String, String)> = $crate::tools::batch::batch(
             $items,
             concurrency,
@@ -177,6 +183,26 @@ macro_rules! merge {
     }};
 }
 
+/// Merge URL Vectors (Deduplicated)
+///
+/// Concatenates like [`merge!`], then drops URLs whose canonical form
+/// ([`normalize_url`](crate::tools::normalize::normalize_url)) was already
+/// seen. The first occurrence is kept as-is, so the result is order-stable and
+/// merging it again is a no-op.
+#[macro_export]
+macro_rules! merge_dedup {
+    ($($vec:expr),+ $(,)?) => {{
+        let mut seen = std::collections::HashSet::new();
+        let mut result: Vec<String> = Vec::new();
+        for url in $crate::merge!($($vec),+) {
+            if seen.insert($crate::tools::normalize::normalize_url(&url)) {
+                result.push(url);
+            }
+        }
+        result
+    }};
+}
+
 /// Run Processor
 #[macro_export]
 macro_rules! run {
@@ -12,6 +12,10 @@ pub static JSONLD_SELECTOR: Lazy<Selector> = Lazy::new(|| {
     Selector::parse("script[type='application/ld+json']").expect("valid jsonld selector")
 });
 
+/// Selector for inline (no `src`) script tags.
+pub static INLINE_SCRIPT_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("script:not([src])").expect("valid inline script selector"));
+
 /// Selector for Microdata items (`itemscope` elements). Top-level items are
 /// filtered in code (an `itemscope` that also has `itemprop` is a nested item).
 pub static MICRODATA_SELECTOR: Lazy<Selector> =
@@ -32,10 +36,41 @@ pub static CLASS_SELECTOR: Lazy<Selector> =
 pub static BODY_SELECTOR: Lazy<Selector> =
     Lazy::new(|| Selector::parse("body").expect("valid body selector"));
 
+/// Selector for main-content containers (`<main>` / `<article>`), first in
+/// document order wins.
+pub static MAIN_CONTENT_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("main, article").expect("valid main content selector"));
+
+/// Selector for `<link rel="canonical">` with an href.
+pub static CANONICAL_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse("link[rel~='canonical' i][href]").expect("valid canonical selector")
+});
+
+/// Selector for `<link rel="alternate" hreflang="…">` language alternates.
+pub static HREFLANG_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse("link[rel~='alternate'][hreflang][href]").expect("valid hreflang selector")
+});
+
+/// Selector for legacy `<link rel="image_src" href="…">` page images.
+pub static IMAGE_SRC_LINK_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse("link[rel~='image_src' i][href]").expect("valid image_src selector")
+});
+
 /// Selector for `<title>` tags.
 pub static TITLE_SELECTOR: Lazy<Selector> =
     Lazy::new(|| Selector::parse("title").expect("valid title selector"));
 
+/// Selector for `<h1>` elements.
+pub static H1_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("h1").expect("valid h1 selector"));
+
+/// Selector for site-search widgets: `role="search"` forms and search inputs
+/// (`type="search"`, or the conventional `q` / `s` query names).
+pub static SEARCH_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse("[role='search'], input[type='search'], input[name='q'], input[name='s']")
+        .expect("valid search selector")
+});
+
 /// Selector for metadata tags with name/property attributes.
 pub static META_SELECTOR: Lazy<Selector> =
     Lazy::new(|| Selector::parse("meta[name], meta[property]").expect("valid metadata selector"));
@@ -51,3 +86,13 @@ pub static LI_SELECTOR: Lazy<Selector> =
 /// Selector for `<p>` elements (mf2 `e-*` step splitting).
 pub static P_SELECTOR: Lazy<Selector> =
     Lazy::new(|| Selector::parse("p").expect("valid p selector"));
+
+/// Selector for `<img>` elements.
+pub static IMG_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("img").expect("valid img selector"));
+
+/// Selector for subresource references whose host a page connects to:
+/// scripts, images and `<link>`s (stylesheets, `preconnect`, `dns-prefetch`, …).
+pub static SUBRESOURCE_SELECTOR: Lazy<Selector> = Lazy::new(|| {
+    Selector::parse("script[src], img[src], link[href]").expect("valid subresource selector")
+});
@@ -1,15 +1,19 @@
 //! Example Templates
 
+mod tests;
+
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 use futures_util::stream::{Stream, StreamExt};
 use tokio::sync::mpsc;
 
-use crate::tools::clean::{canonicalize_url, clean_urls};
-use crate::tools::fetch::fetch_strategy;
-use crate::tools::map::map_children;
-use crate::types::{fetch_cache_new, Context, CTX, FETCH_CACHE};
+use crate::tools::batch::batch;
+use crate::tools::clean::{canonicalize_url, clean_emails, clean_urls};
+use crate::tools::extract::extract_emails;
+use crate::tools::fetch::{fetch_auto, fetch_strategy};
+use crate::tools::map::{map_children, map_page};
+use crate::types::{ctx_allows_url, fetch_cache_new, Context, Html, CTX, FETCH_CACHE};
 
 /// Streaming child-URL discovery.
 ///
@@ -48,7 +52,7 @@ pub fn qrawl_discover_children(
     urls: Vec<String>,
     ctx: Context,
 ) -> impl Stream<Item = String> + Send + 'static {
-    let concurrency = ctx.concurrency;
+    let concurrency = ctx.effective_concurrency();
     let (tx, rx) = mpsc::channel::<String>(concurrency);
     let ctx_arc = Arc::new(ctx);
     let cache = fetch_cache_new();
@@ -57,7 +61,7 @@ pub fn qrawl_discover_children(
         CTX.scope(ctx_arc, async move {
             FETCH_CACHE
                 .scope(cache, async move {
-                    let stream = build_discover_stream(urls, concurrency);
+                    let stream = build_discover_stream(urls, concurrency, fetch_html);
                     pump(stream, tx).await;
                 })
                 .await;
@@ -88,7 +92,7 @@ pub fn qrawl_fetch_stream<S>(
 where
     S: Stream<Item = String> + Send + 'static,
 {
-    let concurrency = ctx.concurrency;
+    let concurrency = ctx.effective_concurrency();
     let (tx, rx) = mpsc::channel::<(String, String)>(concurrency);
     let ctx_arc = Arc::new(ctx);
     let cache = fetch_cache_new();
@@ -97,7 +101,7 @@ where
         CTX.scope(ctx_arc, async move {
             FETCH_CACHE
                 .scope(cache, async move {
-                    let stream = build_fetch_stream(urls, concurrency);
+                    let stream = build_fetch_stream(urls, concurrency, fetch_html);
                     pump(stream, tx).await;
                 })
                 .await;
@@ -144,7 +148,7 @@ pub fn qrawl_children_stream(
     urls: Vec<String>,
     ctx: Context,
 ) -> impl Stream<Item = (String, String)> + Send + 'static {
-    let concurrency = ctx.concurrency;
+    let concurrency = ctx.effective_concurrency();
     let (tx, rx) = mpsc::channel::<(String, String)>(concurrency);
     let ctx_arc = Arc::new(ctx);
     let cache = fetch_cache_new();
@@ -153,8 +157,7 @@ pub fn qrawl_children_stream(
         CTX.scope(ctx_arc, async move {
             FETCH_CACHE
                 .scope(cache, async move {
-                    let discover = build_discover_stream(urls, concurrency);
-                    let stream = build_fetch_stream(discover, concurrency);
+                    let stream = build_children_stream(urls, concurrency, fetch_html);
                     pump(stream, tx).await;
                 })
                 .await;
@@ -183,14 +186,57 @@ pub async fn qrawl_children(
     Ok(qrawl_children_stream(urls, ctx).collect().await)
 }
 
+/// Get the child URLs of a single page (one-shot, non-recursive).
+///
+/// Fetches `url` with [`fetch_auto`], runs `map_children` on it and returns the
+/// children canonicalized and deduped — the discover stage of
+/// [`qrawl_children`] for one URL, without fetching the children. A page with
+/// no detectable children yields `[url]` (the leaf fallback). Unlike the batch
+/// templates, a fetch failure is returned instead of dropped.
+pub async fn children_from_url(url: &str) -> Result<Vec<String>, String> {
+    let html = fetch_auto(url).await.map_err(|e| e.to_string())?;
+    let children = map_children(&html, url).await;
+    let parent = canonicalize_url(url);
+    Ok(clean_urls(&children)
+        .into_iter()
+        .filter(|child| *child == parent || ctx_allows_url(child))
+        .collect())
+}
+
+/// The pipeline's fetcher: `fetch_strategy`, with errors as `None`.
+async fn fetch_html(url: String) -> Option<String> {
+    fetch_strategy(&url).await.ok().map(|html| html.to_string())
+}
+
+/// Both halves of [`qrawl_children_stream`] over an injected fetcher (`None` =
+/// failed fetch). Must be run inside a `CTX` + `FETCH_CACHE` scope.
+fn build_children_stream<F, Fut>(
+    urls: Vec<String>,
+    concurrency: usize,
+    fetch: F,
+) -> impl Stream<Item = (String, String)> + Send + 'static
+where
+    F: Fn(String) -> Fut + Clone + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<String>> + Send + 'static,
+{
+    let discover = build_discover_stream(urls, concurrency, fetch.clone());
+    build_fetch_stream(discover, concurrency, fetch)
+}
+
 /// Discover half of the pipeline: clean+dedupe parents, fetch them, run
 /// `map_children` (parallel, parse-bounded), canonicalize+dedupe children
-/// across the stream. Pure stream construction — no scoping, no spawning;
-/// must be run inside a `CTX` + `FETCH_CACHE` scope.
-fn build_discover_stream(
+/// across the stream, dropping children outside the `CTX` link filters. Pure
+/// stream construction — no scoping, no spawning; must be run inside a `CTX` +
+/// `FETCH_CACHE` scope.
+fn build_discover_stream<F, Fut>(
     urls: Vec<String>,
     concurrency: usize,
-) -> impl Stream<Item = String> + Send + 'static {
+    fetch: F,
+) -> impl Stream<Item = String> + Send + 'static
+where
+    F: Fn(String) -> Fut + Clone + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<String>> + Send + 'static,
+{
     // Stage 1: clean + dedupe input URLs (synchronous; small list).
     let parents = clean_urls(&urls);
 
@@ -210,8 +256,9 @@ fn build_discover_stream(
 
     futures_util::stream::iter(parents)
         // Stage 2: fetch parents, drop fetch errors.
-        .map(|url| async move {
-            fetch_strategy(&url).await.ok().map(|html| (url, html))
+        .map(move |url| {
+            let fetch = fetch.clone();
+            async move { fetch(url.clone()).await.map(|html| (url, Html::new(html))) }
         })
         .buffer_unordered(concurrency)
         .filter_map(|opt| async move { opt })
@@ -219,14 +266,20 @@ fn build_discover_stream(
         // `spawn_blocking`; bounded concurrency lets parents parse in parallel
         // across cores without unbounded DOMs in memory.
         .map(|(parent_url, parent_html)| async move {
-            map_children(&parent_html, &parent_url).await
+            let children = map_children(&parent_html, &parent_url).await;
+            (canonicalize_url(&parent_url), children)
         })
         .buffer_unordered(parse_concurrency)
-        // Stage 4: flatten + canonicalize + dedupe across the stream.
-        .flat_map(move |children| {
+        // Stage 4: flatten + canonicalize + filter + dedupe across the stream.
+        // The leaf fallback (a parent yielded as its own child) bypasses the
+        // filters: seeds are always processed.
+        .flat_map(move |(parent, children)| {
             let mut unique = Vec::with_capacity(children.len());
             for c in children {
                 let canonical = canonicalize_url(&c);
+                if canonical != parent && !ctx_allows_url(&canonical) {
+                    continue;
+                }
                 if seen.lock().unwrap().insert(canonical.clone()) {
                     unique.push(canonical);
                 }
@@ -235,18 +288,22 @@ fn build_discover_stream(
         })
 }
 
-/// Fetch half of the pipeline: per-URL `fetch_strategy`, drop errors. Pure
+/// Fetch half of the pipeline: per-URL `fetch`, drop errors. Pure
 /// stream construction — no scoping, no spawning; must be run inside a `CTX`
 /// + `FETCH_CACHE` scope.
-fn build_fetch_stream<S>(
+fn build_fetch_stream<S, F, Fut>(
     urls: S,
     concurrency: usize,
+    fetch: F,
 ) -> impl Stream<Item = (String, String)> + Send + 'static
 where
     S: Stream<Item = String> + Send + 'static,
+    F: Fn(String) -> Fut + Clone + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<String>> + Send + 'static,
 {
-    urls.map(|child_url| async move {
-        fetch_strategy(&child_url).await.ok().map(|html| (child_url, html))
+    urls.map(move |child_url| {
+        let fetch = fetch.clone();
+        async move { fetch(child_url.clone()).await.map(|html| (child_url, html)) }
     })
     .buffer_unordered(concurrency)
     .filter_map(|opt| async move { opt })
@@ -268,21 +325,93 @@ where
 }
 
 /// Get emails from URLs.
+///
+/// [`qrawl_emails_bounded`] at the context's concurrency.
 pub async fn qrawl_emails(urls: Vec<String>, ctx: Context) -> Result<Vec<String>, String> {
-    let result = chain! {
-        urls, ctx =>
-        clean_urls ->
-        fetch_strategy ->
-        map_children ->
-        clean_urls ->
-        fetch_strategy ->
-        map_page ->
-        clean_urls ->
-        fetch_strategy ->
-        extract_emails ->
-        clean_emails
-    }
-    .await;
+    let concurrency = ctx.effective_concurrency();
+    qrawl_emails_bounded(urls, ctx, concurrency).await
+}
 
-    Ok(result.into_iter().map(|(_, email)| email).collect())
+/// Get emails from URLs with at most `concurrency` fetches in flight.
+///
+/// Parents → `map_children` → children → `map_page` → linked pages →
+/// `extract_emails`, cleaning URLs between stages and dropping URLs outside
+/// the `CTX` domain filters. Stages run one after another and each fans out
+/// through [`batch`] at `concurrency` (0 = the context's), so the cap holds
+/// across the whole pipeline, not just per stage. Fetch errors are dropped.
+pub async fn qrawl_emails_bounded(
+    urls: Vec<String>,
+    ctx: Context,
+    concurrency: usize,
+) -> Result<Vec<String>, String> {
+    let concurrency = match concurrency {
+        0 => ctx.effective_concurrency(),
+        n => n,
+    };
+    let emails = CTX
+        .scope(
+            Arc::new(ctx),
+            FETCH_CACHE.scope(
+                fetch_cache_new(),
+                emails_pipeline(urls, concurrency, fetch_html),
+            ),
+        )
+        .await;
+    Ok(emails)
+}
+
+/// The [`qrawl_emails_bounded`] stages over an injected fetcher (`None` =
+/// failed fetch). Must be run inside a `CTX` + `FETCH_CACHE` scope.
+async fn emails_pipeline<F, Fut>(urls: Vec<String>, concurrency: usize, fetch: F) -> Vec<String>
+where
+    F: Fn(String) -> Fut + Clone + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<String>> + Send + 'static,
+{
+    let fetch_all = |urls: Vec<String>| {
+        let fetch = fetch.clone();
+        batch(clean_urls(&urls), concurrency, move |url: String| {
+            let fetch = fetch.clone();
+            async move { fetch(url.clone()).await.map(|html| (url, Html::new(html))) }
+        })
+    };
+    let allowed = |urls: Vec<Vec<String>>| -> Vec<String> {
+        urls.into_iter()
+            .flatten()
+            .filter(|url| ctx_allows_url(url))
+            .collect()
+    };
+
+    let parents = fetch_all(urls).await.into_iter().flatten().collect();
+    let children = batch(
+        parents,
+        concurrency,
+        |(url, html): (String, Html)| async move { map_children(&html, &url).await },
+    )
+    .await;
+    let pages = fetch_all(allowed(children))
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    let links = batch(
+        pages,
+        concurrency,
+        |(url, html): (String, Html)| async move { map_page(&html, &url).await },
+    )
+    .await;
+    let leaves = fetch_all(allowed(links))
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    let emails: Vec<String> = batch(
+        leaves,
+        concurrency,
+        |(_, html): (String, Html)| async move { extract_emails(&html).await },
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect();
+    clean_emails(&emails)
 }
@@ -0,0 +1,219 @@
+#![cfg(test)]
+use crate::templates::{build_children_stream, children_from_url, emails_pipeline};
+use crate::test_support::{html_response, mock_server, roundup_page};
+use crate::types::{fetch_cache_new, Context, CTX, FETCH_CACHE};
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_children_from_url_returns_roundup_children() {
+    let body = roundup_page(&[
+        "https://www.example.com/recipes/spicy-margarita/",
+        "https://www.example.com/recipes/paloma/",
+        "https://www.example.com/recipes/ranch-water/",
+        "https://www.example.com/recipes/paloma/",
+    ]);
+    let (base, _) = mock_server(html_response(&body));
+
+    let children = children_from_url(&format!("{base}/tequila-cocktails"))
+        .await
+        .unwrap();
+    assert_eq!(
+        children,
+        vec![
+            "https://example.com/recipes/spicy-margarita",
+            "https://example.com/recipes/paloma",
+            "https://example.com/recipes/ranch-water",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_children_from_url_follows_only_url_filter_matches() {
+    let body = roundup_page(&[
+        "recipes/paloma",
+        "shop/tequila-glasses",
+        "recipes/ranch-water",
+        "newsletter/signup",
+    ]);
+    let (base, _) = mock_server(html_response(&body));
+    let ctx = Context::fast().with_url_filter(regex::Regex::new("/recipes/").unwrap());
+
+    // The seed is off-pattern but still fetched; off-pattern children are dropped.
+    let children = CTX
+        .scope(
+            Arc::new(ctx),
+            children_from_url(&format!("{base}/tequila-cocktails")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        children,
+        vec![
+            "https://example.com/recipes/paloma",
+            "https://example.com/recipes/ranch-water",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_children_crawl_never_fetches_url_filter_misses() {
+    let paths = [
+        "recipes/paloma",
+        "shop/tequila-glasses",
+        "recipes/ranch-water",
+        "newsletter/signup",
+    ];
+    let mut pages = HashMap::new();
+    pages.insert(
+        "https://example.com/tequila-cocktails".to_string(),
+        roundup_page(&paths),
+    );
+    for path in paths {
+        pages.insert(
+            format!("https://example.com/{path}"),
+            format!("<html><body><h1>{path}</h1></body></html>"),
+        );
+    }
+
+    // Mock fetcher logging every URL the crawl asks for. (A loopback server
+    // can't stand in here: the pipeline canonicalizes every URL to https.)
+    let pages = Arc::new(pages);
+    let fetched = Arc::new(Mutex::new(Vec::new()));
+    let fetch = {
+        let fetched = fetched.clone();
+        move |url: String| {
+            let (pages, fetched) = (pages.clone(), fetched.clone());
+            async move {
+                fetched.lock().unwrap().push(url.clone());
+                pages.get(&url).cloned()
+            }
+        }
+    };
+
+    let ctx = Context::fast().with_url_filter(regex::Regex::new("/recipes/").unwrap());
+    let mut children: Vec<String> = CTX
+        .scope(
+            Arc::new(ctx),
+            FETCH_CACHE.scope(
+                fetch_cache_new(),
+                build_children_stream(
+                    vec!["https://example.com/tequila-cocktails".to_string()],
+                    4,
+                    fetch,
+                )
+                .map(|(url, _html)| url)
+                .collect::<Vec<_>>(),
+            ),
+        )
+        .await;
+    children.sort();
+    assert_eq!(
+        children,
+        vec![
+            "https://example.com/recipes/paloma",
+            "https://example.com/recipes/ranch-water",
+        ]
+    );
+
+    let fetched = fetched.lock().unwrap();
+    let hits = |prefix: &str| fetched.iter().filter(|url| url.contains(prefix)).count();
+    assert_eq!(hits("/shop/"), 0, "{fetched:?}");
+    assert_eq!(hits("/newsletter/"), 0, "{fetched:?}");
+    assert_eq!(hits("/recipes/"), 2, "{fetched:?}");
+}
+
+#[tokio::test]
+async fn test_children_from_url_skips_links_past_max_path_depth() {
+    let body = roundup_page(&[
+        "recipes/paloma",
+        "archive/2019/05/12/old-paloma",
+        "recipes/ranch-water",
+    ]);
+    let (base, _) = mock_server(html_response(&body));
+
+    let children = CTX
+        .scope(
+            Arc::new(Context::fast().with_max_path_depth(2)),
+            children_from_url(&format!("{base}/a/b/c/tequila-cocktails")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        children,
+        vec![
+            "https://example.com/recipes/paloma",
+            "https://example.com/recipes/ranch-water",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_children_from_url_surfaces_fetch_errors() {
+    assert!(children_from_url("http://127.0.0.1:9/unreachable")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_emails_pipeline_caps_in_flight_fetches() {
+    let slugs = [
+        "paloma",
+        "ranch-water",
+        "margarita",
+        "mojito",
+        "negroni",
+        "spritz",
+    ];
+    let mut pages = HashMap::new();
+    pages.insert(
+        "https://example.com/cocktails".to_string(),
+        roundup_page(&slugs.map(|slug| format!("recipes/{slug}"))),
+    );
+    for slug in slugs {
+        pages.insert(
+            format!("https://example.com/recipes/{slug}"),
+            format!(
+                r#"<html><body><a href="https://example.com/chefs/{slug}">Chef</a></body></html>"#
+            ),
+        );
+        pages.insert(
+            format!("https://example.com/chefs/{slug}"),
+            format!(r#"<html><body><a href="mailto:{slug}@example.com">Email</a></body></html>"#),
+        );
+    }
+
+    // Counting mock fetcher: tracks the peak number of concurrent fetches.
+    let pages = Arc::new(pages);
+    let (in_flight, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let fetch = {
+        let (in_flight, peak) = (in_flight.clone(), peak.clone());
+        move |url: String| {
+            let (pages, in_flight, peak) = (pages.clone(), in_flight.clone(), peak.clone());
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                pages.get(&url).cloned()
+            }
+        }
+    };
+
+    let mut emails = CTX
+        .scope(
+            Arc::new(Context::fast()),
+            FETCH_CACHE.scope(
+                fetch_cache_new(),
+                emails_pipeline(vec!["https://example.com/cocktails".to_string()], 2, fetch),
+            ),
+        )
+        .await;
+    emails.sort();
+
+    assert_eq!(emails.len(), slugs.len(), "{emails:?}");
+    assert!(emails.contains(&"paloma@example.com".to_string()));
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,80 @@
+//! Test Support
+#![cfg(test)]
+
+use std::sync::{Arc, Mutex};
+
+/// Requests a [`mock_server`] has answered, as request lines
+/// (`"GET /path HTTP/1.1"`) in arrival order.
+#[derive(Clone, Default)]
+pub(crate) struct RequestLog(Arc<Mutex<Vec<String>>>);
+
+impl RequestLog {
+    pub(crate) fn lines(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+}
+
+/// HTTP server on 127.0.0.1 answering every connection with `response` until
+/// the test process exits, logging each request line before replying. Returns
+/// the base URL and the log.
+pub(crate) fn mock_server(response: impl Into<String>) -> (String, RequestLog) {
+    use std::io::{BufRead, BufReader, Write};
+    let response = response.into();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let log = RequestLog::default();
+    let seen = log.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                continue;
+            }
+            // Drain headers.
+            loop {
+                let mut header = String::new();
+                if reader.read_line(&mut header).unwrap_or(0) == 0 || header == "\r\n" {
+                    break;
+                }
+            }
+            seen.0.lock().unwrap().push(line.trim().to_string());
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (format!("http://{addr}"), log)
+}
+
+/// A `200 OK` `text/html` response carrying `body`, for [`mock_server`].
+pub(crate) fn html_response(body: &str) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// A roundup page whose cards each link a heading to one of `paths` above a
+/// short blurb — enough for sibling detection to pick the links as children.
+/// Paths are relative to `https://example.com/` unless already absolute.
+pub(crate) fn roundup_page(paths: &[impl AsRef<str>]) -> String {
+    let cards: String = paths
+        .iter()
+        .map(|path| {
+            let path = path.as_ref();
+            let href = if path.starts_with("http") {
+                path.to_string()
+            } else {
+                format!("https://example.com/{path}")
+            };
+            format!(
+                r#"<div><h3><a href="{href}">{path}</a></h3><p>A refreshing tequila cocktail with lime, salt and plenty of ice.</p></div>"#
+            )
+        })
+        .collect();
+    format!("<!DOCTYPE html><html><body><main>{cards}</main></body></html>")
+}
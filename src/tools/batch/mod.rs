@@ -4,7 +4,18 @@ mod tests;
 
 use futures_util::stream::{self, StreamExt};
 
+use crate::types::{get_cancellation, get_concurrency};
+
 /// Batch execute async operations with bounded concurrency.
+///
+/// A `concurrency` of 0 uses the pipeline context's
+/// ([`Context::with_concurrency`](crate::types::Context::with_concurrency)),
+/// so callers can set it once; an explicit value overrides it.
+///
+/// When the context's cancellation token
+/// ([`Context::with_cancellation`](crate::types::Context::with_cancellation))
+/// fires, in-flight operations are dropped and the results completed so far
+/// are returned.
 pub async fn batch<T, F, Fut, R>(items: Vec<T>, concurrency: usize, operation: F) -> Vec<R>
 where
     T: Send + 'static,
@@ -12,9 +23,15 @@ where
     Fut: std::future::Future<Output = R> + Send + 'static,
     R: Send + 'static,
 {
-    stream::iter(items)
+    let concurrency = match concurrency {
+        0 => get_concurrency(),
+        n => n,
+    };
+    let results = stream::iter(items)
         .map(operation)
-        .buffer_unordered(concurrency)
-        .collect()
-        .await
+        .buffer_unordered(concurrency);
+    match get_cancellation() {
+        Some(token) => results.take_until(token.cancelled_owned()).collect().await,
+        None => results.collect().await,
+    }
 }
@@ -1,5 +1,8 @@
 #![cfg(test)]
 use crate::tools::batch::batch;
+use crate::types::{Context, CTX, DEFAULT_CONCURRENCY};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_batch_basic() {
@@ -83,3 +86,77 @@ async fn test_batch_concurrency_limit() {
     // Should respect concurrency limit (allow 3-4 due to buffer_unordered behavior)
     assert!(*max <= 4, "Max concurrent was {}, expected <= 4", *max);
 }
+
+/// Runs `n` items through `batch(.., concurrency, ..)` and returns the peak
+/// number of operations in flight.
+async fn peak_in_flight(n: usize, concurrency: usize) -> usize {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (in_flight_op, peak_op) = (in_flight.clone(), peak.clone());
+    batch((0..n).collect(), concurrency, move |_| {
+        let (in_flight, peak) = (in_flight_op.clone(), peak_op.clone());
+        async move {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        }
+    })
+    .await;
+    peak.load(Ordering::SeqCst)
+}
+
+#[tokio::test]
+async fn test_batch_zero_concurrency_uses_context() {
+    let ctx = Context::auto().with_concurrency(2);
+    assert_eq!(CTX.scope(Arc::new(ctx), peak_in_flight(6, 0)).await, 2);
+
+    // An explicit value overrides the context.
+    let ctx = Context::auto().with_concurrency(2);
+    assert_eq!(CTX.scope(Arc::new(ctx), peak_in_flight(6, 3)).await, 3);
+
+    // 0 everywhere falls back to the default rather than stalling.
+    let ctx = Context::auto().with_concurrency(0);
+    assert_eq!(ctx.effective_concurrency(), DEFAULT_CONCURRENCY);
+    assert_eq!(CTX.scope(Arc::new(ctx), peak_in_flight(4, 0)).await, 4);
+}
+
+#[tokio::test]
+async fn test_batch_cancellation_returns_completed() {
+    let token = tokio_util::sync::CancellationToken::new();
+    let ctx = Context::auto().with_cancellation(token.clone());
+    let started = Arc::new(AtomicUsize::new(0));
+
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(tokio::time::Duration::from_millis(120)).await;
+        canceller.cancel();
+    });
+
+    // A slow fetcher: each item takes 50ms, two at a time.
+    let counter = started.clone();
+    let results = CTX
+        .scope(
+            Arc::new(ctx),
+            batch((0..20).collect(), 2, move |n: u64| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+                    n
+                }
+            }),
+        )
+        .await;
+
+    assert!(!results.is_empty() && results.len() < 10, "{results:?}");
+    let started = started.load(Ordering::SeqCst) as u64;
+    assert!(started < 20);
+    assert!(results.iter().all(|&n| n < started));
+
+    // Already cancelled: nothing runs.
+    let ctx = Context::auto().with_cancellation(token);
+    let results = CTX
+        .scope(Arc::new(ctx), batch(vec![1, 2, 3], 2, |n| async move { n }))
+        .await;
+    assert!(results.is_empty());
+}
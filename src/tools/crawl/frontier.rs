@@ -0,0 +1,165 @@
+//! Crawl Frontier
+//!
+//! Persistent crawl frontier for resumable crawls.
+
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::QrawlError;
+use crate::tools::normalize::normalize_url;
+
+/// Queue of pending URLs plus the set of visited ones, saved to a JSON file.
+///
+/// URLs are deduplicated by [`normalize_url`]: pushing one that is pending or
+/// visited under any spelling is a no-op. Drive a crawl with [`pop`], process
+/// the page, [`push`] its children, [`mark_visited`] it and [`save`]
+/// periodically; after an interruption, [`open`] the same file and carry on —
+/// pages popped but not yet saved as visited are simply crawled again.
+///
+/// [`pop`]: Frontier::pop
+/// [`push`]: Frontier::push
+/// [`mark_visited`]: Frontier::mark_visited
+/// [`save`]: Frontier::save
+/// [`open`]: Frontier::open
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "FrontierFile")]
+pub struct Frontier {
+    /// URLs to crawl, in FIFO order.
+    pending: VecDeque<String>,
+    /// Normalized URLs already crawled.
+    visited: BTreeSet<String>,
+    /// Normalized `pending` URLs, for O(1) dedup.
+    #[serde(skip)]
+    queued: HashSet<String>,
+}
+
+/// On-disk form of a [`Frontier`] (the dedup index is rebuilt on load).
+#[derive(Deserialize)]
+struct FrontierFile {
+    pending: VecDeque<String>,
+    visited: BTreeSet<String>,
+}
+
+impl From<FrontierFile> for Frontier {
+    fn from(file: FrontierFile) -> Self {
+        let mut frontier = Frontier {
+            visited: file.visited,
+            ..Default::default()
+        };
+        file.pending.into_iter().for_each(|url| {
+            frontier.push(&url);
+        });
+        frontier
+    }
+}
+
+impl Frontier {
+    /// An empty frontier.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The frontier saved at `path`, or an empty one when the file doesn't
+    /// exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, QrawlError> {
+        let path = path.as_ref();
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
+    /// Load a frontier saved with [`save`](Frontier::save).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, QrawlError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            QrawlError::new(format!("failed to read frontier {}: {e}", path.display()))
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|e| QrawlError::new(format!("invalid frontier {}: {e}", path.display())))
+    }
+
+    /// Save to `path` as JSON. Writes a sibling temp file and renames it over
+    /// `path`, so a crash mid-save leaves the previous state intact.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), QrawlError> {
+        let path = path.as_ref();
+        let json = serde_json::to_string(self)
+            .map_err(|e| QrawlError::new(format!("failed to serialize frontier: {e}")))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                QrawlError::new(format!("failed to write frontier {}: {e}", path.display()))
+            })
+    }
+
+    /// Queue `url` unless it is already pending or visited. Returns whether it
+    /// was queued.
+    pub fn push(&mut self, url: &str) -> bool {
+        let key = normalize_url(url);
+        if self.visited.contains(&key) || !self.queued.insert(key) {
+            return false;
+        }
+        self.pending.push_back(url.to_string());
+        true
+    }
+
+    /// Take the next pending URL. It is not marked visited — call
+    /// [`mark_visited`](Frontier::mark_visited) once it has been processed.
+    pub fn pop(&mut self) -> Option<String> {
+        let url = self.pending.pop_front()?;
+        self.queued.remove(&normalize_url(&url));
+        Some(url)
+    }
+
+    /// Record `url` as crawled; later pushes of it are ignored.
+    pub fn mark_visited(&mut self, url: &str) {
+        self.visited.insert(normalize_url(url));
+    }
+
+    /// Record a fetched `url` whose page declares `canonical` (e.g. from
+    /// [`extract_url_signals`](crate::tools::extract::extract_url_signals)):
+    /// both are marked visited and a pending copy of the canonical is dropped,
+    /// so it isn't fetched again. Returns the URL to emit the page under — the
+    /// canonical when there is one, else `url`.
+    pub fn mark_visited_as(&mut self, url: &str, canonical: Option<&str>) -> String {
+        self.mark_visited(url);
+        let Some(canonical) = canonical.map(str::trim).filter(|c| !c.is_empty()) else {
+            return url.to_string();
+        };
+        let key = normalize_url(canonical);
+        if self.queued.remove(&key) {
+            self.pending.retain(|pending| normalize_url(pending) != key);
+        }
+        self.visited.insert(key);
+        canonical.to_string()
+    }
+
+    /// Whether `url` has been marked visited.
+    pub fn is_visited(&self, url: &str) -> bool {
+        self.visited.contains(&normalize_url(url))
+    }
+
+    /// Pending URLs, in crawl order.
+    pub fn pending(&self) -> impl Iterator<Item = &str> {
+        self.pending.iter().map(String::as_str)
+    }
+
+    /// Number of pending URLs.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether no URLs are pending.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Number of visited URLs.
+    pub fn visited_count(&self) -> usize {
+        self.visited.len()
+    }
+}
@@ -0,0 +1,21 @@
+//! Crawl Tools
+
+pub mod frontier;
+mod tests;
+
+pub use frontier::Frontier;
+
+/// Number of non-empty path segments in `url`, for crawl depth policies.
+///
+/// `https://example.com/` is 0, `/recipes/` is 1 and `/2019/05/12/post` is 4;
+/// trailing and doubled slashes, query and fragment don't count. Unparseable
+/// URLs are 0.
+pub fn url_path_depth(url: &str) -> usize {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            url.path_segments()
+                .map(|segments| segments.filter(|s| !s.is_empty()).count())
+        })
+        .unwrap_or(0)
+}
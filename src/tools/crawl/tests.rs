@@ -0,0 +1,112 @@
+#![cfg(test)]
+use crate::tools::crawl::*;
+
+#[test]
+fn test_url_path_depth() {
+    assert_eq!(url_path_depth("https://example.com"), 0);
+    assert_eq!(url_path_depth("https://example.com/"), 0);
+    assert_eq!(url_path_depth("https://example.com/recipes/"), 1);
+    assert_eq!(
+        url_path_depth("https://example.com/recipes/paloma?x=1#top"),
+        2
+    );
+    assert_eq!(url_path_depth("https://example.com//2019/05/12/post/"), 4);
+    assert_eq!(url_path_depth("not a url"), 0);
+}
+
+fn frontier_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("qrawl-frontier-{name}-{}.json", std::process::id()))
+}
+
+#[test]
+fn test_frontier_dedups_pending_and_visited() {
+    let mut frontier = Frontier::new();
+    assert!(frontier.push("https://example.com/a"));
+    assert!(!frontier.push("http://www.example.com/a/"));
+    assert!(frontier.push("https://example.com/b"));
+
+    assert_eq!(frontier.pop().as_deref(), Some("https://example.com/a"));
+    frontier.mark_visited("https://example.com/a");
+    assert!(!frontier.push("https://example.com/a#comments"));
+    assert!(frontier.is_visited("https://www.example.com/a"));
+    assert_eq!(
+        frontier.pending().collect::<Vec<_>>(),
+        vec!["https://example.com/b"]
+    );
+}
+
+#[test]
+fn test_frontier_survives_crash_and_reload() {
+    let path = frontier_path("resume");
+    let _ = std::fs::remove_file(&path);
+
+    {
+        let mut frontier = Frontier::open(&path).unwrap();
+        assert!(frontier.is_empty());
+        for url in [
+            "https://example.com/",
+            "https://example.com/x",
+            "https://example.com/y",
+        ] {
+            frontier.push(url);
+        }
+        let first = frontier.pop().unwrap();
+        frontier.mark_visited(&first);
+        frontier.push("https://example.com/z");
+        frontier.save(&path).unwrap();
+        // Popped after the last save: lost on "crash", so crawled again.
+        frontier.pop();
+    }
+
+    let mut resumed = Frontier::open(&path).unwrap();
+    assert_eq!(resumed.visited_count(), 1);
+    assert!(resumed.is_visited("https://example.com"));
+    assert_eq!(
+        resumed.pending().collect::<Vec<_>>(),
+        vec![
+            "https://example.com/x",
+            "https://example.com/y",
+            "https://example.com/z"
+        ]
+    );
+    assert!(!resumed.push("https://example.com/y"));
+    assert!(!resumed.push("https://example.com/"));
+    assert_eq!(resumed.pop().as_deref(), Some("https://example.com/x"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_frontier_load_errors() {
+    assert!(Frontier::load(frontier_path("missing")).is_err());
+
+    let path = frontier_path("corrupt");
+    std::fs::write(&path, "{not json").unwrap();
+    let err = Frontier::load(&path).unwrap_err();
+    assert!(err.message().contains("invalid frontier"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_frontier_mark_visited_as_canonical() {
+    let mut frontier = Frontier::new();
+    assert!(frontier.push("https://example.com/a?utm_source=feed"));
+    assert!(frontier.push("https://example.com/a-canonical"));
+    assert!(frontier.push("https://example.com/b"));
+
+    let url = frontier.pop().unwrap();
+    let emitted = frontier.mark_visited_as(&url, Some("https://www.example.com/a-canonical/"));
+    assert_eq!(emitted, "https://www.example.com/a-canonical/");
+    assert!(frontier.is_visited(&url));
+    assert!(frontier.is_visited("https://example.com/a-canonical"));
+    assert!(!frontier.push("https://example.com/a-canonical"));
+    assert_eq!(
+        frontier.pending().collect::<Vec<_>>(),
+        vec!["https://example.com/b"]
+    );
+
+    assert_eq!(
+        frontier.mark_visited_as("https://example.com/b", None),
+        "https://example.com/b"
+    );
+    assert!(frontier.is_visited("https://example.com/b"));
+}
@@ -0,0 +1,31 @@
+//! Dedup Tools
+
+mod tests;
+pub(crate) mod utils;
+
+/// 64-bit SimHash of `text`'s word 3-shingles (words when the text is shorter).
+///
+/// Text is normalized and lowercased and split on non-alphanumerics, so
+/// casing, punctuation and whitespace don't matter. Near-duplicate texts hash
+/// to values a small [`hamming_distance`] apart; empty text hashes to `0`.
+pub fn simhash(text: &str) -> u64 {
+    utils::simhash(&utils::shingles(text))
+}
+
+/// Number of differing bits between two [`simhash`]es (0 = same features).
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Cluster near-duplicate texts (e.g. pages' main content) by SimHash.
+///
+/// Texts whose hashes are within `max_distance` bits are linked, and linked
+/// texts form one cluster (single-link, so chains of close pairs merge).
+/// Returns clusters of indices into `texts`, each ascending, ordered by their
+/// first index; unique texts are singleton clusters. A crawl can keep the
+/// first of each cluster and skip the rest. 3 bits is a reasonable threshold
+/// for boilerplate-heavy pages.
+pub fn cluster_near_duplicates<S: AsRef<str>>(texts: &[S], max_distance: u32) -> Vec<Vec<usize>> {
+    let hashes: Vec<u64> = texts.iter().map(|t| simhash(t.as_ref())).collect();
+    utils::cluster(&hashes, max_distance)
+}
@@ -0,0 +1,60 @@
+#![cfg(test)]
+use crate::tools::dedup::*;
+
+const ARTICLE: &str = "Preheat the oven to 350 degrees and grease a nine inch pan. \
+    Whisk the flour, baking soda and salt in a bowl. In a separate bowl beat the \
+    butter and sugar until light and fluffy, then add the eggs one at a time. \
+    Stir in the mashed bananas and vanilla, fold in the dry ingredients and pour \
+    the batter into the pan. Bake for sixty minutes until a toothpick comes out clean.";
+
+const UNRELATED: &str = "The city council voted on Tuesday to extend the bike lane \
+    network along the waterfront, citing a rise in commuter cycling since the bridge \
+    reopened. Opponents argued that parking revenue would fall and that deliveries \
+    to local shops would be disrupted during construction next spring.";
+
+#[test]
+fn test_simhash_identical_text_hashes_equal() {
+    assert_eq!(simhash(ARTICLE), simhash(ARTICLE));
+    // Casing, punctuation and whitespace are not features.
+    let reformatted = ARTICLE.to_uppercase().replace(". ", " ...\n\n");
+    assert_eq!(simhash(ARTICLE), simhash(&reformatted));
+    assert_eq!(simhash(""), 0);
+}
+
+#[test]
+fn test_simhash_small_edit_is_near() {
+    let edited = ARTICLE.replace("sixty minutes", "fifty five minutes");
+    assert_ne!(simhash(ARTICLE), simhash(&edited));
+    assert!(hamming_distance(simhash(ARTICLE), simhash(&edited)) <= 8);
+}
+
+#[test]
+fn test_simhash_unrelated_text_is_far() {
+    assert!(hamming_distance(simhash(ARTICLE), simhash(UNRELATED)) >= 20);
+}
+
+#[test]
+fn test_hamming_distance() {
+    assert_eq!(hamming_distance(0, 0), 0);
+    assert_eq!(hamming_distance(0b1011, 0b0001), 2);
+    assert_eq!(hamming_distance(u64::MAX, 0), 64);
+}
+
+#[test]
+fn test_cluster_near_duplicates() {
+    let pages = vec![
+        ARTICLE.to_string(),
+        UNRELATED.to_string(),
+        ARTICLE.replace("sixty minutes", "fifty five minutes"),
+        format!("{ARTICLE} "),
+    ];
+    assert_eq!(
+        cluster_near_duplicates(&pages, 8),
+        vec![vec![0, 2, 3], vec![1]]
+    );
+    assert_eq!(
+        cluster_near_duplicates(&pages, 0),
+        vec![vec![0, 3], vec![1], vec![2]]
+    );
+    assert!(cluster_near_duplicates::<&str>(&[], 3).is_empty());
+}
@@ -0,0 +1,69 @@
+use crate::hash::fnv1a;
+use crate::tools::normalize::normalize_text;
+
+/// Words per shingle.
+const SHINGLE_WORDS: usize = 3;
+
+/// Lowercased word shingles of `text`; the words themselves when there are
+/// fewer than [`SHINGLE_WORDS`].
+pub(super) fn shingles(text: &str) -> Vec<String> {
+    let text = normalize_text(text).to_lowercase();
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.len() < SHINGLE_WORDS {
+        return words.into_iter().map(str::to_string).collect();
+    }
+    words.windows(SHINGLE_WORDS).map(|w| w.join(" ")).collect()
+}
+
+/// SimHash over equally weighted features: each bit is set when more
+/// feature hashes have it set than not.
+pub(super) fn simhash(features: &[String]) -> u64 {
+    let mut weights = [0i64; 64];
+    for feature in features {
+        let hash = fnv1a(feature.as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            *weight += if hash >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    }
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| **w > 0)
+        .fold(0, |acc, (bit, _)| acc | 1 << bit)
+}
+
+/// Single-link clusters of `hashes` within `max_distance` bits (union-find).
+pub(super) fn cluster(hashes: &[u64], max_distance: u32) -> Vec<Vec<usize>> {
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut parent: Vec<usize> = (0..hashes.len()).collect();
+    for i in 0..hashes.len() {
+        for j in i + 1..hashes.len() {
+            if super::hamming_distance(hashes[i], hashes[j]) <= max_distance {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut cluster_of_root = std::collections::HashMap::new();
+    for i in 0..hashes.len() {
+        let r = root(&mut parent, i);
+        let idx = *cluster_of_root.entry(r).or_insert_with(|| {
+            clusters.push(Vec::new());
+            clusters.len() - 1
+        });
+        clusters[idx].push(i);
+    }
+    clusters
+}
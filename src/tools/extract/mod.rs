@@ -2,10 +2,27 @@
 
 mod tests;
 pub mod types;
-mod utils;
+pub(crate) mod utils;
 
 use crate::types::{Html, Jsonld, Metadata};
-pub use types::ExtractPreviewResult;
+pub use types::{
+    Author, ExtractPreviewResult, HreflangLink, ImageCredit, ImageOptions, InstructionStep,
+    InteractionStats, PreviewOrder, ReadingStats, RecipeInfo, SearchAction, TitleSource,
+    UrlSignals,
+};
+
+/// Default reading speed for [`extract_reading_stats`], in words per minute.
+pub const DEFAULT_WORDS_PER_MINUTE: f64 = 225.0;
+
+/// Title precedence of [`extract_title`]: `og:title` is usually the cleanest
+/// (no " | Site Name" suffix), then the JSON-LD headline, the visible `<h1>`,
+/// and finally `<title>`.
+pub const DEFAULT_TITLE_ORDER: &[TitleSource] = &[
+    TitleSource::OgTitle,
+    TitleSource::JsonLdHeadline,
+    TitleSource::H1,
+    TitleSource::TitleTag,
+];
 
 /// Extract schema.org `@type` values from JSON-LD.
 pub fn extract_schema_types(jsonld: &Jsonld) -> Vec<String> {
@@ -30,21 +47,492 @@ pub fn extract_schema_types(jsonld: &Jsonld) -> Vec<String> {
     types
 }
 
+/// Extract a page's declared keywords/tags from metadata and JSON-LD.
+///
+/// Reads `<meta name="keywords">` / `news_keywords` (comma-split), every
+/// `article:tag`, and JSON-LD `keywords` (comma-separated string, array of
+/// strings, or `DefinedTerm`s with a `name`). Keywords are trimmed,
+/// whitespace-normalized, lowercased and deduplicated in first-seen order.
+pub fn extract_keywords(metadata: &Metadata, jsonld: &Jsonld) -> Vec<String> {
+    let mut keywords = Vec::new();
+    let mut push = |raw: &str, split: bool| {
+        let parts: Vec<&str> = if split {
+            raw.split(',').collect()
+        } else {
+            vec![raw]
+        };
+        for part in parts {
+            let keyword = utils::normalize_keyword(part);
+            if !keyword.is_empty() {
+                utils::push_unique(&mut keywords, keyword);
+            }
+        }
+    };
+
+    for key in ["keywords", "news_keywords"] {
+        for (k, v) in metadata {
+            if k.eq_ignore_ascii_case(key) {
+                push(v, true);
+            }
+        }
+    }
+    for (k, v) in metadata {
+        if k.eq_ignore_ascii_case("article:tag") {
+            push(v, false);
+        }
+    }
+    for value in jsonld {
+        match value.get("keywords") {
+            Some(serde_json::Value::String(s)) => push(s, true),
+            Some(serde_json::Value::Array(arr)) => {
+                for item in arr {
+                    match item {
+                        serde_json::Value::String(s) => push(s, false),
+                        other => {
+                            if let Some(name) = other.get("name").and_then(|n| n.as_str()) {
+                                push(name, false);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    keywords
+}
+
+/// Extract engagement signals (ratings, reviews, comments, interaction
+/// counters) from JSON-LD.
+///
+/// Reads `aggregateRating` (`ratingValue`, `ratingCount`, `reviewCount`),
+/// `commentCount`, and `interactionStatistic` counters. The first object
+/// carrying a field wins; numeric strings like `"1,204"` are accepted. A
+/// `CommentAction` counter fills `comment_count` when `commentCount` is absent.
+pub fn extract_interaction_stats(jsonld: &Jsonld) -> InteractionStats {
+    let mut stats = InteractionStats::default();
+
+    for value in jsonld {
+        let Some(obj) = value.as_object() else {
+            continue;
+        };
+        if let Some(rating) = obj.get("aggregateRating") {
+            let rating = utils::first_object(rating);
+            let field = |key: &str| rating.and_then(|r| r.get(key));
+            stats.rating_value = stats
+                .rating_value
+                .or_else(|| field("ratingValue").and_then(utils::json_f64));
+            stats.rating_count = stats
+                .rating_count
+                .or_else(|| field("ratingCount").and_then(utils::json_u64));
+            stats.review_count = stats
+                .review_count
+                .or_else(|| field("reviewCount").and_then(utils::json_u64));
+        }
+        stats.comment_count = stats
+            .comment_count
+            .or_else(|| obj.get("commentCount").and_then(utils::json_u64));
+        for (kind, count) in utils::interaction_counters(obj.get("interactionStatistic")) {
+            stats.interaction_counts.entry(kind).or_insert(count);
+        }
+    }
+
+    if stats.comment_count.is_none() {
+        stats.comment_count = stats.interaction_counts.get("CommentAction").copied();
+    }
+    stats
+}
+
+/// Extract the first schema.org `Recipe` in JSON-LD (including `@graph`);
+/// see [`extract_recipes`]. `None` when there's no Recipe.
+pub fn extract_recipe(jsonld: &Jsonld) -> Option<RecipeInfo> {
+    extract_recipes(jsonld).into_iter().next()
+}
+
+/// Extract every schema.org `Recipe` in JSON-LD (including `@graph`), in
+/// document order — both halves of a "double feature" page. Microdata, RDFa
+/// and microformat recipes are included when `jsonld` is the unified view
+/// from [`scrape_jsonld`](crate::tools::scrape::scrape_jsonld).
+///
+/// `recipeInstructions` are flattened into ordered [`InstructionStep`]s:
+/// plain strings (split on newlines), `HowToStep`s (`text`, else `name`), and
+/// `HowToSection`s, whose steps carry the section's `name`. Text is
+/// normalized; empty steps are dropped.
+///
+/// `recipeYield` may be a string, a number, or an array of either (often
+/// `["8", "8 servings"]`): the first entry is kept as
+/// [`RecipeInfo::recipe_yield`] and the first whole number in any entry
+/// becomes [`RecipeInfo::servings`]. `prepTime`, `cookTime` and `totalTime`
+/// are parsed with
+/// [`parse_iso_duration`](crate::tools::normalize::parse_iso_duration);
+/// unparseable times are `None`.
+pub fn extract_recipes(jsonld: &Jsonld) -> Vec<RecipeInfo> {
+    utils::find_schema_objects(jsonld, "Recipe")
+        .into_iter()
+        .map(|recipe| {
+            let text = |key: &str| {
+                recipe
+                    .get(key)
+                    .and_then(serde_json::Value::as_str)
+                    .map(crate::tools::normalize::normalize_text)
+                    .filter(|s| !s.is_empty())
+            };
+
+            let mut instructions = Vec::new();
+            if let Some(value) = recipe.get("recipeInstructions") {
+                utils::flatten_instructions(value, None, &mut instructions);
+            }
+
+            let duration = |key: &str| {
+                recipe
+                    .get(key)
+                    .and_then(serde_json::Value::as_str)
+                    .and_then(crate::tools::normalize::parse_iso_duration)
+            };
+            let yields = utils::yield_strings(recipe.get("recipeYield").or(recipe.get("yield")));
+
+            RecipeInfo {
+                name: text("name"),
+                description: text("description"),
+                ingredients: utils::json_strings(recipe.get("recipeIngredient")),
+                instructions,
+                servings: yields.iter().find_map(|y| utils::leading_integer(y)),
+                recipe_yield: yields.into_iter().next(),
+                prep_time: duration("prepTime"),
+                cook_time: duration("cookTime"),
+                total_time: duration("totalTime"),
+            }
+        })
+        .collect()
+}
+
+/// Extract content authors from schema.org `author` properties in JSON-LD.
+///
+/// Each author may be a plain name or a Person/Organization object (or an
+/// array of either); `{"@id": …}` references are resolved against `@graph`
+/// nodes. `sameAs` (string or array) fills [`Author::same_as`]. Authors are
+/// deduplicated by case-insensitive name, first occurrence wins; nameless
+/// entries are skipped.
+pub fn extract_authors(jsonld: &Jsonld) -> Vec<Author> {
+    let nodes = utils::nodes_by_id(jsonld);
+    let mut authors: Vec<Author> = Vec::new();
+    for value in utils::find_property_values(jsonld, "author") {
+        let items = match value {
+            serde_json::Value::Array(arr) => arr.iter().collect(),
+            other => vec![other],
+        };
+        for item in items {
+            let author = match item {
+                serde_json::Value::String(name) => Author {
+                    name: crate::tools::normalize::normalize_text(name),
+                    url: None,
+                    same_as: Vec::new(),
+                },
+                serde_json::Value::Object(obj) => {
+                    let obj = obj
+                        .get("@id")
+                        .and_then(serde_json::Value::as_str)
+                        .and_then(|id| nodes.get(id).copied())
+                        .unwrap_or(obj);
+                    let Some(name) = obj.get("name").and_then(serde_json::Value::as_str) else {
+                        continue;
+                    };
+                    Author {
+                        name: crate::tools::normalize::normalize_text(name),
+                        url: obj
+                            .get("url")
+                            .and_then(serde_json::Value::as_str)
+                            .map(str::trim)
+                            .filter(|url| !url.is_empty())
+                            .map(str::to_string),
+                        same_as: utils::json_strings(obj.get("sameAs")),
+                    }
+                }
+                _ => continue,
+            };
+            if !author.name.is_empty()
+                && !authors
+                    .iter()
+                    .any(|seen| seen.name.eq_ignore_ascii_case(&author.name))
+            {
+                authors.push(author);
+            }
+        }
+    }
+    authors
+}
+
+/// Extract the site search endpoint from schema.org `WebSite.potentialAction`
+/// in JSON-LD (including `@graph`).
+///
+/// The first `SearchAction` with a target wins. `target` may be a URL
+/// template or an `EntryPoint` (`urlTemplate`); `query-input` may be the
+/// shorthand `"required name=search_term_string"` or a
+/// `PropertyValueSpecification` (`valueName`).
+pub fn extract_search_action(jsonld: &Jsonld) -> Option<SearchAction> {
+    utils::find_schema_objects(jsonld, "WebSite")
+        .into_iter()
+        .filter_map(|site| site.get("potentialAction"))
+        .flat_map(|actions| match actions {
+            serde_json::Value::Array(arr) => arr.iter().collect(),
+            other => vec![other],
+        })
+        .filter_map(serde_json::Value::as_object)
+        .filter(|action| utils::has_schema_type(action, "SearchAction"))
+        .find_map(|action| {
+            Some(SearchAction {
+                target: utils::url_template(action.get("target")?)?,
+                query_input: action.get("query-input").and_then(utils::query_input_name),
+            })
+        })
+}
+
+/// Extract caption and credit of every schema.org `ImageObject` in JSON-LD,
+/// including ones nested in other objects (a Recipe's or Article's `image`).
+///
+/// Objects without a URL are skipped, as are ones with no caption, credit or
+/// copyright holder. When several objects share a URL (compared with
+/// [`normalize_url`](crate::tools::normalize::normalize_url)), the first
+/// wins.
+pub fn extract_image_credits(jsonld: &Jsonld) -> Vec<ImageCredit> {
+    let mut seen = std::collections::HashSet::new();
+    utils::find_nested_schema_objects(jsonld, "ImageObject")
+        .into_iter()
+        .filter_map(|obj| {
+            let url = ["contentUrl", "url"]
+                .iter()
+                .find_map(|key| obj.get(*key)?.as_str())
+                .map(str::trim)
+                .filter(|url| !url.is_empty())?;
+            let credit = ImageCredit {
+                url: url.to_string(),
+                caption: utils::json_name(obj.get("caption")),
+                credit_text: utils::json_name(obj.get("creditText")),
+                copyright_holder: utils::json_name(obj.get("copyrightHolder")),
+            };
+            let has_credit = credit.caption.is_some()
+                || credit.credit_text.is_some()
+                || credit.copyright_holder.is_some();
+            Some(credit).filter(|_| has_credit)
+        })
+        .filter(|credit| seen.insert(crate::tools::normalize::normalize_url(&credit.url)))
+        .collect()
+}
+
+/// Find the JSON-LD credit for an extracted image URL (e.g. from
+/// [`extract_primary_image`] or the og preview).
+///
+/// URLs are compared with
+/// [`normalize_url`](crate::tools::normalize::normalize_url), so scheme,
+/// `www.` and tracking-parameter differences still match.
+pub fn extract_image_credit(jsonld: &Jsonld, image_url: &str) -> Option<ImageCredit> {
+    let target = crate::tools::normalize::normalize_url(image_url);
+    extract_image_credits(jsonld)
+        .into_iter()
+        .find(|credit| crate::tools::normalize::normalize_url(&credit.url) == target)
+}
+
 /// Extract Open Graph preview (title, description, image) from metadata.
+///
+/// Each field takes the first non-empty key of [`PreviewOrder::default`]:
+/// `title` → `og:title` → `twitter:title`, and likewise for description;
+/// image is `og:image` → `twitter:image` → `twitter:image:src` →
+/// `og:image:secure_url`.
 pub fn extract_og_preview(metadata: &Metadata) -> ExtractPreviewResult {
+    extract_og_preview_with_order(metadata, &PreviewOrder::default())
+}
+
+/// Extract Open Graph preview with a caller-chosen key precedence per field —
+/// e.g. `twitter:title` first on sites whose `og:title` is SEO filler.
+pub fn extract_og_preview_with_order(
+    metadata: &Metadata,
+    order: &PreviewOrder,
+) -> ExtractPreviewResult {
     ExtractPreviewResult {
-        title: utils::find_metadata_value(metadata, &["title", "og:title", "twitter:title"]),
-        description: utils::find_metadata_value(
-            metadata,
-            &["description", "og:description", "twitter:description"],
-        ),
-        image: utils::find_metadata_value(
-            metadata,
-            &["og:image", "twitter:image", "og:image:secure_url"],
-        ),
+        title: utils::find_metadata_value(metadata, &order.title),
+        description: utils::find_metadata_value(metadata, &order.description),
+        image: utils::find_metadata_value(metadata, &order.image),
     }
 }
 
+/// Extract every preview image from metadata — all repeated `og:image` tags
+/// of a gallery, not just the first.
+///
+/// Keys follow [`PreviewOrder::default`]'s image order, each key's values in
+/// document order; duplicates are dropped. The first entry is
+/// [`extract_og_preview`]'s `image`.
+pub fn extract_og_images(metadata: &Metadata) -> Vec<String> {
+    utils::find_metadata_values(metadata, &PreviewOrder::default().image)
+}
+
+/// Extract the text of the page's first visible `<h1>` — the on-page title,
+/// for comparing against the metadata title.
+///
+/// Hidden headings (`hidden`, `aria-hidden="true"`, inline `display: none`,
+/// inside a hidden ancestor) and empty ones are skipped; the text is
+/// entity-decoded and whitespace-normalized.
+pub async fn extract_h1(html: &Html) -> Option<String> {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || utils::first_h1(&scraper::Html::parse_document(&html)))
+        .await
+        .expect("extract_h1: spawn_blocking failed")
+}
+
+/// Extract the page title, taking the first source of
+/// [`DEFAULT_TITLE_ORDER`] that has one.
+pub async fn extract_title(html: &Html, metadata: &Metadata, jsonld: &Jsonld) -> Option<String> {
+    extract_title_with_order(html, metadata, jsonld, DEFAULT_TITLE_ORDER).await
+}
+
+/// Extract the page title with a caller-chosen source precedence — e.g.
+/// [`TitleSource::TitleTag`] first on sites whose `og:title` is SEO filler.
+///
+/// The first source in `order` with a non-empty value wins; values are
+/// normalized. The HTML is only parsed when [`TitleSource::H1`] is reached.
+pub async fn extract_title_with_order(
+    html: &Html,
+    metadata: &Metadata,
+    jsonld: &Jsonld,
+    order: &[TitleSource],
+) -> Option<String> {
+    for source in order {
+        let title = match source {
+            TitleSource::JsonLdHeadline => utils::json_headline(jsonld),
+            TitleSource::OgTitle => utils::find_metadata_value(metadata, &["og:title"]),
+            TitleSource::TitleTag => utils::find_metadata_value(metadata, &["title"]),
+            TitleSource::H1 => extract_h1(html).await,
+        };
+        let title = title.map(|t| crate::tools::normalize::normalize_text(&t));
+        if let Some(title) = title.filter(|t| !t.is_empty()) {
+            return Some(title);
+        }
+    }
+    None
+}
+
+/// Extract an Open Graph preview, falling back to the page's hero image.
+///
+/// Like [`extract_og_preview`], but when the metadata carries no image the
+/// legacy `<link rel="image_src">` is used, else the page's primary content
+/// image (see [`extract_primary_image`]), preferring its largest `srcset`
+/// candidate over a possibly placeholder `src`. The URL is returned as
+/// written.
+pub async fn extract_og_preview_with_html(
+    metadata: &Metadata,
+    html: &Html,
+) -> ExtractPreviewResult {
+    let mut preview = extract_og_preview(metadata);
+    if preview.image.is_none() {
+        let html = html.to_string();
+        preview.image = tokio::task::spawn_blocking(move || {
+            let doc = scraper::Html::parse_document(&html);
+            utils::link_image_src(&doc).or_else(|| utils::primary_image(&doc))
+        })
+        .await
+        .expect("extract_og_preview_with_html: spawn_blocking failed");
+    }
+    preview
+}
+
+/// Extract the page's primary content image, resolved against `base_url`.
+///
+/// Logos, avatars, icons and spacer/tiny images (declared width or height
+/// under 100px) are skipped; among the rest, large images inside
+/// `<main>`/`<article>` win and images in header/footer/nav/aside lose. The
+/// best `srcset` candidate is preferred over `src`. With no content image,
+/// the legacy `<link rel="image_src">` is the fallback. `None` when neither
+/// remains or the URL can't be resolved.
+pub async fn extract_primary_image(html: &Html, base_url: &str) -> Option<String> {
+    extract_primary_image_with(html, base_url, &ImageOptions::default()).await
+}
+
+/// [`extract_primary_image`] with [`ImageOptions`], e.g. stripping image-CDN
+/// transforms (`?resize=640:*`) for dedup and caching.
+pub async fn extract_primary_image_with(
+    html: &Html,
+    base_url: &str,
+    options: &ImageOptions,
+) -> Option<String> {
+    let html = html.to_string();
+    let base = url::Url::parse(base_url).ok();
+    let src = tokio::task::spawn_blocking(move || {
+        let doc = scraper::Html::parse_document(&html);
+        utils::primary_image(&doc).or_else(|| utils::link_image_src(&doc))
+    })
+    .await
+    .expect("extract_primary_image_with: spawn_blocking failed")?;
+    let mut url = match base {
+        Some(base) => base.join(&src),
+        None => url::Url::parse(&src),
+    }
+    .ok()?;
+    if options.strip_image_transforms && url.host_str().is_some_and(utils::is_image_cdn_host) {
+        url.set_query(None);
+        url.set_fragment(None);
+    }
+    Some(url.into())
+}
+
+/// Extract the page's canonical and `og:url`, resolved against `base_url`,
+/// flagging when they disagree.
+///
+/// URLs are compared with
+/// [`normalize_url`](crate::tools::normalize::normalize_url), so scheme,
+/// `www.`, trailing-slash and tracking-parameter differences aren't a
+/// mismatch. A URL that can't be resolved is `None`.
+pub async fn extract_url_signals(metadata: &Metadata, html: &Html, base_url: &str) -> UrlSignals {
+    let html = html.to_string();
+    let canonical = tokio::task::spawn_blocking(move || {
+        utils::canonical_href(&scraper::Html::parse_document(&html))
+    })
+    .await
+    .expect("extract_url_signals: spawn_blocking failed");
+    let base = url::Url::parse(base_url).ok();
+    let resolve = |href: String| {
+        match &base {
+            Some(base) => base.join(&href),
+            None => url::Url::parse(&href),
+        }
+        .ok()
+        .map(String::from)
+    };
+
+    let canonical = canonical.and_then(resolve);
+    let og_url = utils::find_metadata_value(metadata, &["og:url"]).and_then(resolve);
+    let mismatch = match (&canonical, &og_url) {
+        (Some(canonical), Some(og_url)) => {
+            crate::tools::normalize::normalize_url(canonical)
+                != crate::tools::normalize::normalize_url(og_url)
+        }
+        _ => false,
+    };
+    UrlSignals {
+        canonical,
+        og_url,
+        mismatch,
+    }
+}
+
+/// Extract a roundup's child links with their titles, as `(title, url)`
+/// pairs in page order.
+///
+/// Uses the same sibling detection and per-sibling link choice as
+/// [`map_children`](crate::tools::map::map_children); each link is titled
+/// by its sibling's first heading (`<h1>`–`<h4>`), else the link's anchor
+/// text. Unlike `map_children` there's no JSON-LD `ItemList` merge and no
+/// fallback to the page URL: a page without siblings yields nothing.
+pub async fn extract_children_with_titles(html: &Html, url: &str) -> Vec<(String, String)> {
+    let html = html.to_string();
+    let url = url.to_string();
+    crate::types::spawn_blocking_in_ctx(move || {
+        let doc = scraper::Html::parse_document(&html);
+        crate::tools::map::utils::map_titled_siblings_from_doc(&doc, &url)
+    })
+    .await
+    .expect("extract_children_with_titles: spawn_blocking failed")
+}
+
 /// Extract email addresses from HTML.
 pub async fn extract_emails(html: &Html) -> Vec<String> {
     let html = html.to_string();
@@ -53,6 +541,19 @@ pub async fn extract_emails(html: &Html) -> Vec<String> {
         .expect("extract_emails: spawn_blocking failed")
 }
 
+/// Extract email addresses from HTML, keeping only strictly valid ones.
+///
+/// Same sources as [`extract_emails`], but each address must match a tight
+/// grammar (alphanumeric-bounded local part, hostname labels, alphabetic TLD of
+/// 2+ chars) and must not look like an asset or version string (`image@2x`,
+/// `v1@1.0`, `logo@2x.png`).
+pub async fn extract_emails_strict(html: &Html) -> Vec<String> {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || utils::extract_email_elements_strict(&html))
+        .await
+        .expect("extract_emails_strict: spawn_blocking failed")
+}
+
 /// Extract phone numbers from HTML.
 pub async fn extract_phones(html: &Html) -> Vec<String> {
     let html = html.to_string();
@@ -60,3 +561,93 @@ pub async fn extract_phones(html: &Html) -> Vec<String> {
         .await
         .expect("extract_phones: spawn_blocking failed")
 }
+
+/// Extract hreflang language alternates, in document order.
+///
+/// Relative hrefs are resolved against `base_url`; lang codes are normalized
+/// (`EN_us` → `en-US`, `X-Default` → `x-default`). Entries with an empty lang or
+/// an unresolvable href are skipped, and duplicate (lang, href) pairs dropped.
+pub async fn extract_hreflang(html: &Html, base_url: &str) -> Vec<HreflangLink> {
+    let html = html.to_string();
+    let base_url = base_url.to_string();
+    tokio::task::spawn_blocking(move || utils::extract_hreflang_links(&html, &base_url))
+        .await
+        .expect("extract_hreflang: spawn_blocking failed")
+}
+
+/// Extract the third-party hosts a page connects to, in document order.
+///
+/// Collects hosts from `<script src>`, `<img src>` and `<link href>` (which
+/// covers `rel="preconnect"` / `"dns-prefetch"` hints), resolving relative and
+/// protocol-relative URLs against `page_url`. Hosts equal to the page's own —
+/// ignoring a leading `www.` — are first-party and skipped; the rest are
+/// lowercased and deduplicated. Non-HTTP(S) references (`data:`, …) are ignored.
+pub async fn extract_third_party_hosts(html: &Html, page_url: &str) -> Vec<String> {
+    let html = html.to_string();
+    let page_url = page_url.to_string();
+    tokio::task::spawn_blocking(move || utils::extract_third_party_hosts(&html, &page_url))
+        .await
+        .expect("extract_third_party_hosts: spawn_blocking failed")
+}
+
+/// Extract the target of a recipe article's "Jump to Recipe" link.
+///
+/// Finds the first anchor whose text contains "jump to recipe" or "go to
+/// recipe" (case-insensitive) and returns its `href`. A bare in-page fragment
+/// (`#recipe-card`, percent-decoded) whose target exists is returned as that
+/// element's CSS selector — `#id`, or `[id="…"]` / `[name="…"]` when it isn't
+/// a plain identifier — ready to scope a scraper; anything else, including a
+/// full URL with a fragment, is returned raw. `None` when there's no such link.
+pub async fn extract_recipe_anchor(html: &Html) -> Option<String> {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || utils::recipe_anchor(&scraper::Html::parse_document(&html)))
+        .await
+        .expect("extract_recipe_anchor: spawn_blocking failed")
+}
+
+/// Extract the lead of an article: its first `n` main-content paragraphs,
+/// joined with blank lines.
+///
+/// Uses the same main-content root as [`extract_reading_stats`]; image captions
+/// and empty paragraphs are skipped. Fewer than `n` paragraphs on the page
+/// yields all of them.
+pub async fn extract_lead(html: &Html, n: usize) -> String {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = scraper::Html::parse_document(&html);
+        utils::lead_paragraphs(&doc, n).join("\n\n")
+    })
+    .await
+    .expect("extract_lead: spawn_blocking failed")
+}
+
+/// Extract word count and reading time (at [`DEFAULT_WORDS_PER_MINUTE`]) from
+/// the page's main content.
+pub async fn extract_reading_stats(html: &Html) -> ReadingStats {
+    extract_reading_stats_with_wpm(html, DEFAULT_WORDS_PER_MINUTE).await
+}
+
+/// Extract word count and reading time at a custom reading speed.
+///
+/// Words are counted over the main-content text — the first `<main>`/`<article>`,
+/// else `<body>` minus nav/header/footer/aside — with scripts and styles skipped,
+/// entities decoded and whitespace normalized. A non-positive `words_per_minute`
+/// yields a reading time of `0.0`.
+pub async fn extract_reading_stats_with_wpm(html: &Html, words_per_minute: f64) -> ReadingStats {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = scraper::Html::parse_document(&html);
+        let word_count = utils::main_text(&doc).split_whitespace().count();
+        let reading_time_minutes = if words_per_minute > 0.0 {
+            word_count as f64 / words_per_minute
+        } else {
+            0.0
+        };
+        ReadingStats {
+            word_count,
+            reading_time_minutes,
+        }
+    })
+    .await
+    .expect("extract_reading_stats: spawn_blocking failed")
+}
@@ -114,4 +114,971 @@ mod tests {
         assert!(phones.contains(&"+1-555-123-4567".to_string()));
         assert!(phones.contains(&"+1 (555) 123-4567".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_extract_reading_stats_counts_main_content_words() {
+        let html = r#"
+            <html>
+                <head><title>Ignored Title</title></head>
+                <body>
+                    <nav>Home Recipes About</nav>
+                    <main>
+                        <h1>Easy Soup</h1>
+                        <p>Chop&nbsp;the onions and   carrots.</p>
+                        <script>var ignored = "not words";</script>
+                        <p>Simmer for ten<br>minutes.</p>
+                    </main>
+                    <footer>Copyright</footer>
+                </body>
+            </html>
+        "#;
+
+        let stats = extract_reading_stats(&html.into()).await;
+        // Easy Soup | Chop the onions and carrots. | Simmer for ten minutes.
+        assert_eq!(stats.word_count, 11);
+    }
+
+    #[tokio::test]
+    async fn test_extract_reading_stats_derives_minutes() {
+        let html = format!("<html><body><p>{}</p></body></html>", "word ".repeat(450));
+
+        let stats = extract_reading_stats(&html.as_str().into()).await;
+        assert_eq!(stats.word_count, 450);
+        assert!((stats.reading_time_minutes - 2.0).abs() < f64::EPSILON);
+
+        let fast = extract_reading_stats_with_wpm(&html.as_str().into(), 450.0).await;
+        assert!((fast.reading_time_minutes - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_extract_lead_returns_first_n_paragraphs() {
+        let html = r#"
+            <html><body>
+                <nav><p>Home | Recipes</p></nav>
+                <article>
+                    <figure><img src="/a.jpg"><figcaption><p>Photo: Jane</p></figcaption></figure>
+                    <p>First   paragraph.</p>
+                    <p>   </p>
+                    <p class="wp-caption-text">Styled by Sam</p>
+                    <p>Second paragraph.</p>
+                    <p>Third paragraph.</p>
+                    <p>Fourth paragraph.</p>
+                </article>
+            </body></html>
+        "#;
+
+        let lead = extract_lead(&html.into(), 2).await;
+        assert_eq!(lead, "First paragraph.\n\nSecond paragraph.");
+        assert_eq!(
+            extract_lead(&html.into(), 10).await.split("\n\n").count(),
+            4
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_hreflang_alternates() {
+        let html = r#"
+            <html><head>
+                <link rel="alternate" hreflang="EN_us" href="/en/cocktails">
+                <link rel="alternate" hreflang="fr" href="https://example.com/fr/cocktails">
+                <link rel="alternate" hreflang="x-default" href="https://example.com/cocktails">
+                <link rel="alternate" hreflang="fr" href="/fr/cocktails">
+                <link rel="canonical" href="https://example.com/cocktails">
+            </head><body></body></html>
+        "#;
+
+        let links = extract_hreflang(&html.into(), "https://example.com/page").await;
+        let pairs: Vec<(&str, &str)> = links
+            .iter()
+            .map(|l| (l.lang.as_str(), l.href.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("en-US", "https://example.com/en/cocktails"),
+                ("fr", "https://example.com/fr/cocktails"),
+                ("x-default", "https://example.com/cocktails"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_hreflang_keeps_non_ascii_subtags() {
+        let html = r#"<link rel="alternate" hreflang="EN-éab" href="/en/cocktails">"#;
+
+        let links = extract_hreflang(&html.into(), "https://example.com/page").await;
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].lang, "en-éab");
+    }
+
+    #[tokio::test]
+    async fn test_extract_emails_strict_rejects_lookalikes() {
+        let html = r#"
+            <html><body>
+                <a href="mailto:image@2x">Retina</a>
+                <a href="mailto:v1@1.0">Version</a>
+                <img alt="logo@2x.png">
+                <p>Write to a.b@example.co.uk or see logo@2x.png</p>
+            </body></html>
+        "#;
+
+        let lenient = extract_emails(&html.into()).await;
+        assert!(lenient.contains(&"image@2x".to_string()));
+        assert!(lenient.contains(&"logo@2x.png".to_string()));
+
+        let strict = extract_emails_strict(&html.into()).await;
+        assert_eq!(strict, vec!["a.b@example.co.uk".to_string()]);
+    }
+
+    #[test]
+    fn test_extract_interaction_stats_from_recipe() {
+        let jsonld = vec![json!({
+            "@type": "Recipe",
+            "name": "Spicy Margarita",
+            "aggregateRating": {
+                "@type": "AggregateRating",
+                "ratingValue": "4.8",
+                "ratingCount": 1204,
+                "reviewCount": "312"
+            },
+            "interactionStatistic": [
+                {
+                    "@type": "InteractionCounter",
+                    "interactionType": "https://schema.org/CommentAction",
+                    "userInteractionCount": 87
+                },
+                {
+                    "@type": "InteractionCounter",
+                    "interactionType": { "@type": "LikeAction" },
+                    "userInteractionCount": "2,045"
+                }
+            ]
+        })];
+
+        let stats = extract_interaction_stats(&jsonld);
+        assert_eq!(stats.rating_value, Some(4.8));
+        assert_eq!(stats.rating_count, Some(1204));
+        assert_eq!(stats.review_count, Some(312));
+        assert_eq!(stats.comment_count, Some(87));
+        assert_eq!(stats.interaction_counts.get("LikeAction"), Some(&2045));
+
+        assert_eq!(
+            extract_interaction_stats(&vec![]),
+            InteractionStats::default()
+        );
+    }
+
+    fn meta(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_keywords_from_meta_keywords() {
+        let metadata = meta(&[
+            ("title", "Spicy Margarita"),
+            (
+                "keywords",
+                " Cocktails, Tequila ,  Happy   Hour,,cocktails ",
+            ),
+        ]);
+        let jsonld = vec![json!({"@type": "Recipe", "keywords": "tequila, lime"})];
+
+        assert_eq!(
+            extract_keywords(&metadata, &jsonld),
+            vec!["cocktails", "tequila", "happy hour", "lime"]
+        );
+    }
+
+    #[test]
+    fn test_extract_keywords_from_repeated_article_tags() {
+        let metadata = meta(&[
+            ("article:tag", "Summer Drinks"),
+            ("article:tag", "Mezcal, Smoky"),
+            ("article:tag", "summer drinks"),
+        ]);
+        let jsonld = vec![json!({
+            "@type": "Article",
+            "keywords": ["Party", {"@type": "DefinedTerm", "name": "Mixology"}]
+        })];
+
+        // `article:tag` values are whole tags — not comma-split.
+        assert_eq!(
+            extract_keywords(&metadata, &jsonld),
+            vec!["summer drinks", "mezcal, smoky", "party", "mixology"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_og_preview_with_html_falls_back_to_hero_srcset() {
+        let html = r#"
+            <html><body>
+                <header><img src="/logo.png"></header>
+                <article>
+                    <img src="data:image/gif;base64,R0lG"
+                         srcset="/hero-320.jpg 320w, /hero-1600.jpg 1600w, /hero-800.jpg 800w">
+                    <img src="/second.jpg">
+                </article>
+            </body></html>
+        "#;
+        let metadata = vec![("og:title".to_string(), "Hero".to_string())];
+
+        let preview = extract_og_preview_with_html(&metadata, &html.into()).await;
+        assert_eq!(preview.title, Some("Hero".to_string()));
+        assert_eq!(preview.image, Some("/hero-1600.jpg".to_string()));
+
+        // Metadata images still win.
+        let metadata = vec![("og:image".to_string(), "/og.jpg".to_string())];
+        let preview = extract_og_preview_with_html(&metadata, &html.into()).await;
+        assert_eq!(preview.image, Some("/og.jpg".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_extract_third_party_hosts_skips_first_party() {
+        let html = r#"
+            <html><head>
+                <link rel="preconnect" href="https://fonts.gstatic.com">
+                <link rel="dns-prefetch" href="//cdn.jsdelivr.net">
+                <link rel="stylesheet" href="/assets/site.css">
+                <script src="https://www.example.com/js/app.js"></script>
+                <script src="https://cdn.jsdelivr.net/npm/lib.min.js"></script>
+            </head><body>
+                <img src="images/hero.jpg">
+                <img src="data:image/gif;base64,R0lG">
+                <img src="https://FONTS.gstatic.com/logo.png">
+            </body></html>
+        "#;
+
+        let hosts = extract_third_party_hosts(&html.into(), "https://example.com/recipes/").await;
+        assert_eq!(hosts, vec!["fonts.gstatic.com", "cdn.jsdelivr.net"]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_primary_image_skips_logo_for_hero() {
+        let html = r#"
+            <html><body>
+                <header><a href="/"><img src="/img/site-logo.png" width="180" height="60"></a></header>
+                <div class="sidebar"><img src="/img/pixel.gif" width="1" height="1"></div>
+                <article>
+                    <p>By <img class="author-avatar" src="/img/jane.jpg" width="48" height="48"> Jane</p>
+                    <figure><img src="/img/margarita-hero.jpg" width="1200" height="800" alt="Margarita"></figure>
+                    <img src="/img/step-1.jpg" width="400" height="300">
+                </article>
+            </body></html>
+        "#;
+
+        assert_eq!(
+            extract_primary_image(&html.into(), "https://example.com/recipes/margarita").await,
+            Some("https://example.com/img/margarita-hero.jpg".to_string())
+        );
+        for img in [
+            r#"<header><img src="/logo.svg"></header>"#,
+            r#"<img src="/img/logo@2x.png" width="360" height="120">"#,
+            r#"<img src="/img/siteLogo.svg" width="360" height="120">"#,
+            r#"<img src="/img/asset.png?v=icon" width="360" height="120">"#,
+        ] {
+            assert_eq!(
+                extract_primary_image(&img.into(), "https://example.com").await,
+                None,
+                "{img}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_primary_image_keeps_hint_lookalikes() {
+        for img in [
+            r#"<img src="/img/iconic-margarita.jpg" width="1200" height="800">"#,
+            r#"<img src="/img/silicone-mold.jpg" width="1200" height="800">"#,
+            r#"<img src="/img/paloma.jpg" width="1200" height="800" alt="Iconic cocktails">"#,
+        ] {
+            let html =
+                format!("<html><body><article><figure>{img}</figure></article></body></html>");
+            assert!(
+                extract_primary_image(&html.into(), "https://example.com/recipes/")
+                    .await
+                    .is_some(),
+                "{img}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_recipe_flattens_howto_sections() {
+        let jsonld = vec![json!({
+            "@context": "https://schema.org",
+            "@graph": [
+                {"@type": "WebPage", "name": "Lasagna"},
+                {
+                    "@type": ["Recipe", "NewsArticle"],
+                    "name": "Classic Lasagna",
+                    "recipeIngredient": ["1 lb ground beef", " 2 cups ricotta "],
+                    "recipeInstructions": [
+                        {
+                            "@type": "HowToSection",
+                            "name": "For the sauce",
+                            "itemListElement": [
+                                {"@type": "HowToStep", "text": "Brown the beef."},
+                                {"@type": "HowToStep", "text": "Add tomatoes and simmer."}
+                            ]
+                        },
+                        {
+                            "@type": ["HowToSection"],
+                            "name": "For the assembly",
+                            "itemListElement": [
+                                {"@type": "HowToStep", "name": "Layer noodles, sauce and ricotta."},
+                                {"@type": "HowToStep", "text": "Bake 45 minutes."}
+                            ]
+                        }
+                    ]
+                }
+            ]
+        })];
+
+        let recipe = extract_recipe(&jsonld).unwrap();
+        assert_eq!(recipe.name.as_deref(), Some("Classic Lasagna"));
+        assert_eq!(
+            recipe.ingredients,
+            vec!["1 lb ground beef", "2 cups ricotta"]
+        );
+        let steps: Vec<(&str, Option<&str>)> = recipe
+            .instructions
+            .iter()
+            .map(|s| (s.text.as_str(), s.section.as_deref()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("Brown the beef.", Some("For the sauce")),
+                ("Add tomatoes and simmer.", Some("For the sauce")),
+                (
+                    "Layer noodles, sauce and ricotta.",
+                    Some("For the assembly")
+                ),
+                ("Bake 45 minutes.", Some("For the assembly")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_recipe_plain_instructions() {
+        let jsonld = vec![json!({
+            "@type": "Recipe",
+            "name": "Toast",
+            "recipeInstructions": "Toast the bread.\nButter it."
+        })];
+        let recipe = extract_recipe(&jsonld).unwrap();
+        assert_eq!(
+            recipe.instructions,
+            vec![
+                InstructionStep {
+                    text: "Toast the bread.".into(),
+                    section: None
+                },
+                InstructionStep {
+                    text: "Butter it.".into(),
+                    section: None
+                },
+            ]
+        );
+        assert_eq!(extract_recipe(&vec![json!({"@type": "Article"})]), None);
+    }
+
+    #[test]
+    fn test_extract_recipe_times() {
+        let jsonld = vec![json!({
+            "@type": "Recipe",
+            "prepTime": "PT15M",
+            "cookTime": "PT1H15M",
+            "totalTime": "about an hour and a half"
+        })];
+        let recipe = extract_recipe(&jsonld).unwrap();
+        assert_eq!(
+            recipe.prep_time,
+            Some(std::time::Duration::from_secs(15 * 60))
+        );
+        assert_eq!(
+            recipe.cook_time,
+            Some(std::time::Duration::from_secs(75 * 60))
+        );
+        assert_eq!(recipe.total_time, None);
+    }
+
+    #[test]
+    fn test_extract_recipe_yield_and_servings() {
+        let recipe_with = |recipe_yield: serde_json::Value| {
+            extract_recipe(&vec![
+                json!({"@type": "Recipe", "recipeYield": recipe_yield}),
+            ])
+            .unwrap()
+        };
+
+        let recipe = recipe_with(json!("8 servings"));
+        assert_eq!(recipe.recipe_yield.as_deref(), Some("8 servings"));
+        assert_eq!(recipe.servings, Some(8));
+
+        let recipe = recipe_with(json!("Makes 12 cookies"));
+        assert_eq!(recipe.recipe_yield.as_deref(), Some("Makes 12 cookies"));
+        assert_eq!(recipe.servings, Some(12));
+
+        let recipe = recipe_with(json!(4));
+        assert_eq!(recipe.recipe_yield.as_deref(), Some("4"));
+        assert_eq!(recipe.servings, Some(4));
+
+        let recipe = recipe_with(json!(["One loaf", "1 loaf (10 slices)"]));
+        assert_eq!(recipe.recipe_yield.as_deref(), Some("One loaf"));
+        assert_eq!(recipe.servings, Some(1));
+
+        let recipe =
+            extract_recipe(&vec![json!({"@type": "Recipe", "yield": "Serves 6"})]).unwrap();
+        assert_eq!(recipe.servings, Some(6));
+
+        let recipe = recipe_with(json!("A crowd"));
+        assert_eq!(recipe.recipe_yield.as_deref(), Some("A crowd"));
+        assert_eq!(recipe.servings, None);
+        assert_eq!(
+            extract_recipe(&vec![json!({"@type": "Recipe"})])
+                .unwrap()
+                .servings,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_recipe_anchor() {
+        let html = r##"
+            <article>
+                <a href="#comments">Leave a comment</a>
+                <a class="btn" href="#recipe-card"><svg></svg> Jump to Recipe</a>
+                <p>A long story about grandma.</p>
+                <div id="recipe-card" class="wprm-recipe">...</div>
+            </article>
+        "##;
+        assert_eq!(
+            extract_recipe_anchor(&html.into()).await.as_deref(),
+            Some("#recipe-card")
+        );
+
+        let html = r##"
+            <a href="#1-recipe">GO TO RECIPE</a>
+            <a name="1-recipe"></a>
+        "##;
+        assert_eq!(
+            extract_recipe_anchor(&html.into()).await.as_deref(),
+            Some("[name=\"1-recipe\"]")
+        );
+
+        let html = r##"
+            <a href="#r%C3%A9cette">Jump to recipe</a>
+            <div id="récette"></div>
+        "##;
+        assert_eq!(
+            extract_recipe_anchor(&html.into()).await.as_deref(),
+            Some("[id=\"récette\"]")
+        );
+
+        // Another site's `#card` isn't this page's `#card`.
+        let html = r##"
+            <a href="https://other.com/recipe#card">Jump to recipe</a>
+            <div id="card"></div>
+        "##;
+        assert_eq!(
+            extract_recipe_anchor(&html.into()).await.as_deref(),
+            Some("https://other.com/recipe#card")
+        );
+
+        let html = r#"<a href="/tacos/print">Jump to recipe</a>"#;
+        assert_eq!(
+            extract_recipe_anchor(&html.into()).await.as_deref(),
+            Some("/tacos/print")
+        );
+        assert_eq!(extract_recipe_anchor(&"<p>No links</p>".into()).await, None);
+    }
+
+    #[test]
+    fn test_extract_og_preview_with_custom_order() {
+        let metadata = vec![
+            (
+                "og:title".to_string(),
+                "BEST Lasagna Recipe EVER (Easy!) | Site".to_string(),
+            ),
+            ("twitter:title".to_string(), "Classic Lasagna".to_string()),
+            ("og:description".to_string(), "Layers of pasta.".to_string()),
+        ];
+
+        assert_eq!(
+            extract_og_preview(&metadata).title.as_deref(),
+            Some("BEST Lasagna Recipe EVER (Easy!) | Site")
+        );
+
+        let order = PreviewOrder {
+            title: vec!["twitter:title".into(), "og:title".into()],
+            ..PreviewOrder::default()
+        };
+        let preview = extract_og_preview_with_order(&metadata, &order);
+        assert_eq!(preview.title.as_deref(), Some("Classic Lasagna"));
+        assert_eq!(preview.description.as_deref(), Some("Layers of pasta."));
+        assert_eq!(
+            extract_og_preview_with_order(&metadata, &PreviewOrder::default()),
+            extract_og_preview(&metadata)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_image_credit_attaches_to_matching_image() {
+        let html = r#"
+            <html><body><article>
+                <img src="/photos/paloma.jpg" width="1200" height="800">
+            </article></body></html>
+        "#;
+        let jsonld = vec![json!({
+            "@context": "https://schema.org",
+            "@type": "Recipe",
+            "name": "Paloma",
+            "image": [
+                "https://example.com/photos/thumb.jpg",
+                {
+                    "@type": "ImageObject",
+                    "url": "http://www.example.com/photos/paloma.jpg",
+                    "caption": "A Paloma  with a salted rim",
+                    "creditText": "Photo: Jane Doe",
+                    "copyrightHolder": {"@type": "Organization", "name": "Example Media"}
+                },
+                {"@type": "ImageObject", "url": "https://example.com/photos/bare.jpg"}
+            ]
+        })];
+
+        let credits = extract_image_credits(&jsonld);
+        assert_eq!(credits.len(), 1);
+
+        let image = extract_primary_image(&html.into(), "https://example.com/recipes/paloma")
+            .await
+            .unwrap();
+        let credit = extract_image_credit(&jsonld, &image).unwrap();
+        assert_eq!(
+            credit.caption.as_deref(),
+            Some("A Paloma with a salted rim")
+        );
+        assert_eq!(credit.credit_text.as_deref(), Some("Photo: Jane Doe"));
+        assert_eq!(credit.copyright_holder.as_deref(), Some("Example Media"));
+        assert_eq!(
+            extract_image_credit(&jsonld, "https://example.com/photos/thumb.jpg"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_link_image_src_is_the_image_fallback() {
+        let html = r#"
+            <html><head>
+                <title>Grandma's Pound Cake</title>
+                <link rel="IMAGE_SRC" href="/images/pound-cake.jpg">
+            </head><body><p>Butter, sugar, eggs, flour.</p></body></html>
+        "#;
+        let metadata = vec![("title".to_string(), "Grandma's Pound Cake".to_string())];
+
+        assert_eq!(
+            extract_primary_image(&html.into(), "https://example.com/recipes/cake").await,
+            Some("https://example.com/images/pound-cake.jpg".to_string())
+        );
+        let preview = extract_og_preview_with_html(&metadata, &html.into()).await;
+        assert_eq!(preview.image.as_deref(), Some("/images/pound-cake.jpg"));
+
+        let twitter = vec![(
+            "twitter:image:src".to_string(),
+            "https://cdn.example.com/cake.jpg".to_string(),
+        )];
+        assert_eq!(
+            extract_og_preview(&twitter).image.as_deref(),
+            Some("https://cdn.example.com/cake.jpg")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_og_images_keeps_repeated_tags() {
+        let html = r#"
+            <html><head>
+                <meta property="og:image" content="https://example.com/gallery/1.jpg">
+                <meta property="og:image" content="https://example.com/gallery/2.jpg">
+                <meta property="og:image" content=" https://example.com/gallery/3.jpg ">
+                <meta name="twitter:image" content="https://example.com/gallery/1.jpg">
+                <meta name="twitter:image" content="https://example.com/twitter.jpg">
+            </head><body></body></html>
+        "#;
+        let metadata = crate::tools::scrape::scrape_metadata(&html.into()).await;
+
+        assert_eq!(
+            extract_og_images(&metadata),
+            vec![
+                "https://example.com/gallery/1.jpg",
+                "https://example.com/gallery/2.jpg",
+                "https://example.com/gallery/3.jpg",
+                "https://example.com/twitter.jpg",
+            ]
+        );
+        assert_eq!(
+            extract_og_preview(&metadata).image.as_deref(),
+            Some("https://example.com/gallery/1.jpg")
+        );
+    }
+
+    #[test]
+    fn test_extract_authors_captures_same_as() {
+        let jsonld = vec![json!({
+            "@context": "https://schema.org",
+            "@graph": [
+                {
+                    "@type": "Article",
+                    "headline": "Why Pound Cake Sinks",
+                    "author": [
+                        {"@id": "https://example.com/#/schema/person/jane"},
+                        "Test Kitchen"
+                    ],
+                    "review": [{"@type": "Review", "author": {"@type": "Person", "name": "A Reader"}}]
+                },
+                {
+                    "@type": "Person",
+                    "@id": "https://example.com/#/schema/person/jane",
+                    "name": "Jane  Doe",
+                    "url": "https://example.com/authors/jane",
+                    "sameAs": [
+                        "https://twitter.com/janedoe",
+                        "https://www.instagram.com/janedoe/"
+                    ]
+                }
+            ]
+        })];
+
+        let authors = extract_authors(&jsonld);
+        assert_eq!(
+            authors,
+            vec![
+                Author {
+                    name: "Jane Doe".to_string(),
+                    url: Some("https://example.com/authors/jane".to_string()),
+                    same_as: vec![
+                        "https://twitter.com/janedoe".to_string(),
+                        "https://www.instagram.com/janedoe/".to_string(),
+                    ],
+                },
+                Author {
+                    name: "Test Kitchen".to_string(),
+                    url: None,
+                    same_as: Vec::new(),
+                },
+            ]
+        );
+
+        let single = vec![json!({
+            "@type": "Recipe",
+            "author": {"@type": "Person", "name": "Chef A", "sameAs": "https://chef-a.example"}
+        })];
+        assert_eq!(
+            extract_authors(&single)[0].same_as,
+            vec!["https://chef-a.example"]
+        );
+    }
+
+    #[test]
+    fn test_extract_search_action_reads_website_target_template() {
+        let jsonld = vec![json!({
+            "@context": "https://schema.org",
+            "@graph": [
+                {"@type": "Organization", "name": "Example"},
+                {
+                    "@type": "WebSite",
+                    "url": "https://example.com/",
+                    "potentialAction": [
+                        {"@type": "ReadAction", "target": "https://example.com/read"},
+                        {
+                            "@type": "SearchAction",
+                            "target": {
+                                "@type": "EntryPoint",
+                                "urlTemplate": "https://example.com/search?q={search_term_string}"
+                            },
+                            "query-input": "required name=search_term_string"
+                        }
+                    ]
+                }
+            ]
+        })];
+
+        let action = extract_search_action(&jsonld).unwrap();
+        assert_eq!(
+            action,
+            SearchAction {
+                target: "https://example.com/search?q={search_term_string}".to_string(),
+                query_input: Some("search_term_string".to_string()),
+            }
+        );
+        assert_eq!(
+            action.search_url("spicy margarita").as_deref(),
+            Some("https://example.com/search?q=spicy+margarita")
+        );
+
+        let plain = vec![json!({
+            "@type": "WebSite",
+            "potentialAction": {
+                "@type": "SearchAction",
+                "target": "https://example.com/?s={query}",
+                "query-input": {"@type": "PropertyValueSpecification", "valueName": "query"}
+            }
+        })];
+        assert_eq!(
+            extract_search_action(&plain)
+                .unwrap()
+                .query_input
+                .as_deref(),
+            Some("query")
+        );
+        assert_eq!(
+            extract_search_action(&vec![json!({"@type": "WebSite"})]),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_title_precedence_picks_configured_source() {
+        let html = r#"
+            <html><head><title>Spicy Margarita Recipe | Example Kitchen</title></head>
+            <body><h1></h1><h1>The  Best Spicy Margarita</h1></body></html>
+        "#
+        .into();
+        let metadata = meta(&[
+            ("title", "Spicy Margarita Recipe | Example Kitchen"),
+            ("og:title", "Spicy Margarita"),
+        ]);
+        let jsonld = vec![json!({
+            "@graph": [{"@type": "Article", "headline": "How to Make a Spicy Margarita"}]
+        })];
+
+        assert_eq!(
+            extract_title(&html, &metadata, &jsonld).await.as_deref(),
+            Some("Spicy Margarita")
+        );
+        let title = |order: Vec<TitleSource>| {
+            let (html, metadata, jsonld) = (&html, &metadata, &jsonld);
+            async move { extract_title_with_order(html, metadata, jsonld, &order).await }
+        };
+        assert_eq!(
+            title(vec![TitleSource::JsonLdHeadline, TitleSource::OgTitle])
+                .await
+                .as_deref(),
+            Some("How to Make a Spicy Margarita")
+        );
+        assert_eq!(
+            title(vec![TitleSource::H1, TitleSource::TitleTag])
+                .await
+                .as_deref(),
+            Some("The Best Spicy Margarita")
+        );
+        assert_eq!(
+            title(vec![TitleSource::TitleTag, TitleSource::H1])
+                .await
+                .as_deref(),
+            Some("Spicy Margarita Recipe | Example Kitchen")
+        );
+
+        // Missing sources fall through to the next one.
+        let no_jsonld = extract_title_with_order(
+            &html,
+            &metadata,
+            &vec![],
+            &[TitleSource::JsonLdHeadline, TitleSource::H1],
+        )
+        .await;
+        assert_eq!(no_jsonld.as_deref(), Some("The Best Spicy Margarita"));
+        assert_eq!(
+            extract_title_with_order(&html, &metadata, &jsonld, &[]).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_h1_skips_hidden_and_empty() {
+        let html = r#"<html><body>
+            <h1 class="sr-only" aria-hidden="true">Example Kitchen</h1>
+            <div style="display: none"><h1>Mobile Title</h1></div>
+            <h1 hidden>Print Title</h1>
+            <h1>   </h1>
+            <h1>  Tom &amp; Jerry&#39;s <em>Punch</em>
+            </h1>
+            <h1>Another Heading</h1>
+        </body></html>"#;
+        assert_eq!(
+            extract_h1(&html.into()).await.as_deref(),
+            Some("Tom & Jerry's Punch")
+        );
+        assert_eq!(extract_h1(&"<p>No heading</p>".into()).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_extract_url_signals_flags_canonical_og_url_mismatch() {
+        let html = r#"<html><head>
+            <link rel="canonical" href="https://partner.example.org/drinks/paloma">
+        </head><body></body></html>"#
+            .into();
+        let metadata = meta(&[("og:url", "/recipes/paloma")]);
+
+        let signals = extract_url_signals(
+            &metadata,
+            &html,
+            "https://example.com/recipes/paloma?ref=feed",
+        )
+        .await;
+        assert_eq!(
+            signals,
+            UrlSignals {
+                canonical: Some("https://partner.example.org/drinks/paloma".to_string()),
+                og_url: Some("https://example.com/recipes/paloma".to_string()),
+                mismatch: true,
+            }
+        );
+
+        // Cosmetic differences aren't a mismatch.
+        let html = r#"<link rel="canonical" href="/recipes/paloma/">"#.into();
+        let metadata = meta(&[(
+            "og:url",
+            "https://www.example.com/recipes/paloma?utm_source=x",
+        )]);
+        let signals = extract_url_signals(&metadata, &html, "https://example.com/").await;
+        assert_eq!(
+            signals.canonical.as_deref(),
+            Some("https://example.com/recipes/paloma/")
+        );
+        assert!(!signals.mismatch);
+
+        let signals = extract_url_signals(&vec![], &html, "https://example.com/").await;
+        assert_eq!(signals.og_url, None);
+        assert!(!signals.mismatch);
+    }
+
+    #[tokio::test]
+    async fn test_extract_children_with_titles_pairs_headings_and_links() {
+        let html = r#"
+            <html><body>
+                <nav><a href="/">Home</a></nav>
+                <article>
+                    <div><h3>1. Paloma</h3><p>Grapefruit and tequila.</p><a href="/recipes/paloma">Get the recipe</a></div>
+                    <div><h3><a href="/recipes/negroni">Negroni</a></h3><p>Bitter and bright.</p><a href="/shop">Shop</a></div>
+                    <div><h3></h3><p><a href="https://other.example/daiquiri">Classic  Daiquiri</a></p></div>
+                </article>
+            </body></html>
+        "#
+        .into();
+
+        assert_eq!(
+            extract_children_with_titles(&html, "https://example.com/roundup").await,
+            vec![
+                (
+                    "1. Paloma".to_string(),
+                    "https://example.com/recipes/paloma".to_string()
+                ),
+                (
+                    "Negroni".to_string(),
+                    "https://example.com/recipes/negroni".to_string()
+                ),
+                (
+                    "Classic Daiquiri".to_string(),
+                    "https://other.example/daiquiri".to_string()
+                ),
+            ]
+        );
+        assert!(
+            extract_children_with_titles(&"<p>Just a page</p>".into(), "https://example.com/")
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_primary_image_strips_cdn_transforms() {
+        let page = |src: &str| -> crate::types::Html {
+            format!(r#"<html><body><article><img src="{src}" width="1200" height="800"></article></body></html>"#)
+                .into()
+        };
+        let hearst = page(
+            "https://hips.hearstapps.com/hmg-prod/images/paloma.jpg?crop=1xw:1xh;center,top&resize=640:*",
+        );
+        let plain = page("/images/paloma.jpg?v=2");
+        let base = "https://example.com/recipes/paloma";
+        let strip = ImageOptions {
+            strip_image_transforms: true,
+        };
+
+        assert_eq!(
+            extract_primary_image_with(&hearst, base, &strip)
+                .await
+                .as_deref(),
+            Some("https://hips.hearstapps.com/hmg-prod/images/paloma.jpg")
+        );
+        assert_eq!(
+            extract_primary_image_with(&plain, base, &strip)
+                .await
+                .as_deref(),
+            Some("https://example.com/images/paloma.jpg?v=2")
+        );
+        // Off by default.
+        assert_eq!(
+            extract_primary_image(&hearst, base).await.as_deref(),
+            Some("https://hips.hearstapps.com/hmg-prod/images/paloma.jpg?crop=1xw:1xh;center,top&resize=640:*")
+        );
+    }
+
+    #[test]
+    fn test_extract_recipes_returns_every_recipe() {
+        let jsonld = vec![
+            json!({
+                "@type": "Recipe",
+                "name": "Classic Margarita",
+                "recipeIngredient": ["2 oz tequila", "1 oz lime juice"]
+            }),
+            json!({"@type": "WebPage", "name": "Margarita Night"}),
+            json!({
+                "@graph": [
+                    {"@type": "Organization", "name": "Example Kitchen"},
+                    {"@type": ["Recipe", "HowTo"], "name": "Frozen  Margarita"}
+                ]
+            }),
+        ];
+
+        let recipes = extract_recipes(&jsonld);
+        let names: Vec<_> = recipes.iter().map(|r| r.name.as_deref()).collect();
+        assert_eq!(
+            names,
+            vec![Some("Classic Margarita"), Some("Frozen Margarita")]
+        );
+        assert_eq!(recipes[0].ingredients.len(), 2);
+        assert_eq!(extract_recipe(&jsonld).as_ref(), recipes.first());
+        assert!(extract_recipes(&vec![json!({"@type": "Article"})]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_extract_emails_and_phones_from_link_hrefs() {
+        let html = r#"
+            <a href="mailto:foo@bar.com?subject=Hi">Write to us</a>
+            <a href=" MAILTO:Sales%40example.com,press@example.com ">Sales and press</a>
+            <a href="tel:+1-555-123-4567">Call</a>
+            <a href="tel:+1%20555%20987%206543;ext=12">Call the shop</a>
+            <a href="tel:+1-555-246-8100,,89">Call the desk</a>
+            <a href="/contact">Contact</a>
+        "#;
+
+        assert_eq!(
+            extract_emails(html).await,
+            vec!["foo@bar.com", "Sales@example.com", "press@example.com"]
+        );
+        let phones = extract_phones(html).await;
+        // The `,,89` dial pause is cut off, not read as a second phone.
+        assert_eq!(
+            phones,
+            vec!["+1-555-123-4567", "+1 555 987 6543", "+1-555-246-8100"]
+        );
+        assert_eq!(
+            crate::tools::normalize::normalize_phones(&phones),
+            vec!["+15551234567", "+15559876543", "+15552468100"]
+        );
+    }
 }
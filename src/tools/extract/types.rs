@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 /// Extract preview result.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
@@ -8,3 +10,187 @@ pub struct ExtractPreviewResult {
     pub description: Option<String>,
     pub image: Option<String>,
 }
+
+/// Metadata key precedence for each preview field, first present wins
+/// (keys match case-insensitively). `Default` is [`extract_og_preview`]'s
+/// order.
+///
+/// [`extract_og_preview`]: super::extract_og_preview
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewOrder {
+    pub title: Vec<String>,
+    pub description: Vec<String>,
+    pub image: Vec<String>,
+}
+
+impl Default for PreviewOrder {
+    fn default() -> Self {
+        let keys = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect();
+        Self {
+            title: keys(&["title", "og:title", "twitter:title"]),
+            description: keys(&["description", "og:description", "twitter:description"]),
+            image: keys(&[
+                "og:image",
+                "twitter:image",
+                "twitter:image:src",
+                "og:image:secure_url",
+            ]),
+        }
+    }
+}
+
+/// A page-title source for [`extract_title_with_order`].
+///
+/// [`extract_title_with_order`]: super::extract_title_with_order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TitleSource {
+    /// schema.org `headline` in JSON-LD (top level or `@graph`).
+    JsonLdHeadline,
+    /// `og:title` metadata.
+    OgTitle,
+    /// The first non-empty `<h1>`.
+    H1,
+    /// The `<title>` tag (`title` metadata).
+    TitleTag,
+}
+
+/// A page's declared URLs, for duplicate / syndication detection.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlSignals {
+    /// `<link rel="canonical">`, resolved to an absolute URL.
+    pub canonical: Option<String>,
+    /// `og:url`, resolved to an absolute URL.
+    pub og_url: Option<String>,
+    /// Both are present and point at different pages once normalized.
+    pub mismatch: bool,
+}
+
+/// Options for [`extract_primary_image_with`]. Defaults reproduce plain
+/// `extract_primary_image`.
+///
+/// [`extract_primary_image_with`]: super::extract_primary_image_with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageOptions {
+    /// Drop the query string and fragment from URLs on known image-CDN hosts
+    /// (Hearst's `hips.hearstapps.com`, imgix, Jetpack, Contentful, …), whose
+    /// queries are resize/crop transforms, leaving the base image URL. Other
+    /// hosts' URLs are kept as is. Off by default.
+    pub strip_image_transforms: bool,
+}
+
+/// Word count and estimated reading time of a page's main content.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingStats {
+    pub word_count: usize,
+    pub reading_time_minutes: f64,
+}
+
+/// A language alternate from `<link rel="alternate" hreflang="…">`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct HreflangLink {
+    /// BCP 47 code (`en`, `en-US`, `zh-Hant`) or `x-default`.
+    pub lang: String,
+    /// Absolute alternate URL.
+    pub href: String,
+}
+
+/// Engagement signals from schema.org `aggregateRating`, `commentCount` and
+/// `interactionStatistic`. Every field is optional — absent on the page means
+/// `None` / empty.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InteractionStats {
+    pub rating_value: Option<f64>,
+    pub rating_count: Option<u64>,
+    pub review_count: Option<u64>,
+    pub comment_count: Option<u64>,
+    /// `userInteractionCount` per interaction type (`LikeAction`,
+    /// `ShareAction`, …), keyed by the bare type name.
+    pub interaction_counts: BTreeMap<String, u64>,
+}
+
+/// A schema.org `Recipe`, reduced to the fields callers use.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeInfo {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// `recipeIngredient` lines, in order.
+    pub ingredients: Vec<String>,
+    /// `recipeInstructions`, flattened to ordered steps.
+    pub instructions: Vec<InstructionStep>,
+    /// `recipeYield` (else `yield`) as written, normalized: "8 servings",
+    /// "Makes 12 cookies", "4".
+    pub recipe_yield: Option<String>,
+    /// The first whole number in the yield, when there is one.
+    pub servings: Option<u32>,
+    /// `prepTime`, parsed from its ISO-8601 duration (`PT15M`).
+    pub prep_time: Option<Duration>,
+    /// `cookTime`, parsed from its ISO-8601 duration.
+    pub cook_time: Option<Duration>,
+    /// `totalTime`, parsed from its ISO-8601 duration. Not derived from the
+    /// other two when missing.
+    pub total_time: Option<Duration>,
+}
+
+/// A content author from schema.org `author` (a Person, Organization or
+/// plain name).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Author {
+    pub name: String,
+    /// Profile page (`url`), as written.
+    pub url: Option<String>,
+    /// Social / profile URLs from `sameAs`, in order.
+    pub same_as: Vec<String>,
+}
+
+/// Attribution for one schema.org `ImageObject`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageCredit {
+    /// `contentUrl`, else `url`, as written.
+    pub url: String,
+    pub caption: Option<String>,
+    pub credit_text: Option<String>,
+    /// `copyrightHolder`: a plain name, or a Person/Organization's `name`.
+    pub copyright_holder: Option<String>,
+}
+
+/// One recipe instruction step.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionStep {
+    pub text: String,
+    /// Name of the enclosing `HowToSection` ("For the sauce"), if any.
+    pub section: Option<String>,
+}
+
+/// A site's search endpoint from schema.org `WebSite.potentialAction`
+/// `SearchAction`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchAction {
+    /// URL template (`target`, or its `urlTemplate`), as written:
+    /// `https://example.com/search?q={search_term_string}`.
+    pub target: String,
+    /// Placeholder name from `query-input` (`search_term_string`), if given.
+    pub query_input: Option<String>,
+}
+
+impl SearchAction {
+    /// The search URL for `query`: the target with the `query_input`
+    /// placeholder replaced by the form-encoded query. `None` when there's no
+    /// placeholder name or the target doesn't contain it.
+    pub fn search_url(&self, query: &str) -> Option<String> {
+        let placeholder = format!("{{{}}}", self.query_input.as_deref()?);
+        if !self.target.contains(&placeholder) {
+            return None;
+        }
+        let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+        Some(self.target.replace(&placeholder, &encoded))
+    }
+}
@@ -1,8 +1,55 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use scraper::Html;
+use scraper::{ElementRef, Html};
 
-use crate::selectors::LINK_SELECTOR;
+use super::types::{HreflangLink, InstructionStep};
+use crate::selectors::{
+    BODY_SELECTOR, CANONICAL_SELECTOR, H1_SELECTOR, HREFLANG_SELECTOR, IMAGE_SRC_LINK_SELECTOR,
+    IMG_SELECTOR, LINK_SELECTOR, MAIN_CONTENT_SELECTOR, P_SELECTOR, SUBRESOURCE_SELECTOR,
+};
+use crate::tools::map::utils::{collect_text, TextJoinPolicy};
+use crate::tools::normalize::normalize_text;
+use crate::tools::scrape::best_srcset_url;
+
+/// Tags whose text is never page content.
+const NON_CONTENT_TAGS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "iframe", "svg",
+];
+
+/// Page chrome skipped when the main content falls back to `<body>`.
+const CHROME_TAGS: &[&str] = &["nav", "header", "footer", "aside"];
+
+/// Whole tokens (of src, class or id, split on non-alphanumerics and camelCase
+/// humps) marking an image as decorative.
+const DECORATIVE_IMAGE_HINTS: &[&str] = &[
+    "logo", "logos", "avatar", "avatars", "icon", "icons", "spacer", "sprite", "sprites",
+];
+
+/// Declared width or height (px) below which an image is decorative (spacer
+/// gifs, tracking pixels, badges).
+const DECORATIVE_MAX_DIMENSION: u32 = 100;
+
+/// Image-CDN hosts whose query strings are resize/crop transforms, matched
+/// exactly or as a parent domain (`hearstapps.com` covers
+/// `hips.hearstapps.com`).
+const IMAGE_CDN_HOSTS: &[&str] = &[
+    "hearstapps.com",
+    "imgix.net",
+    "i0.wp.com",
+    "i1.wp.com",
+    "i2.wp.com",
+    "images.ctfassets.net",
+    "images.unsplash.com",
+    "cdn.sanity.io",
+    "cdn.shopify.com",
+    "images.squarespace-cdn.com",
+];
+
+/// Declared area (px²) from which an image counts as large.
+const LARGE_IMAGE_AREA: u32 = 300 * 200;
+
+/// Image-caption containers skipped by the lead-paragraph walk.
+const CAPTION_TAGS: &[&str] = &["figure", "figcaption"];
 
 // Lazy static regex patterns
 static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
@@ -13,21 +60,51 @@ static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
     // - TLD: must be letters only (2-24 chars), excludes file extensions like .js, .css, .jpg
     Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9]([a-z0-9-]*[a-z0-9])?(\.[a-z0-9]([a-z0-9-]*[a-z0-9])?)*\.[a-z]{2,24}\b").expect("valid regex")
 });
+
+/// Strict email grammar: local part of `[a-z0-9._%+-]` starting and ending
+/// alphanumeric; two or more hostname labels; alphabetic TLD of 2+ chars.
+static STRICT_EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^[a-z0-9](?:[a-z0-9._%+-]{0,62}[a-z0-9])?@(?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\.)+[a-z]{2,24}$").expect("valid regex")
+});
+
+/// Asset extensions that pass the TLD grammar but mark a filename, e.g. the
+/// retina image `logo@2x.png`.
+const FILE_EXTENSION_TLDS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "avif", "svg", "ico", "bmp", "css", "js", "json", "xml",
+    "pdf", "mp4", "webm",
+];
+
 static PHONE_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?:\+?1[-.\s]?)?\(?\d{3}\)?[-.\s]?\d{3}[-.\s]?\d{4}").expect("valid regex")
 });
 
-/// Extract values from links with a specific href prefix (e.g., "mailto:", "tel:")
+/// Extract values from links with a specific href scheme prefix (e.g.
+/// "mailto:", "tel:"), matched case-insensitively. Query strings
+/// (`?subject=…`) and `;` parameters (`;ext=12`) are dropped, the rest is
+/// percent-decoded, and comma-separated `mailto:` recipients
+/// (`mailto:a@x.com,b@x.com`) are split. Other schemes yield one value per
+/// link, cut at the first `,` — a dial pause in `tel:` (`tel:+1-555-0100,,89`).
 fn extract_with_prefix(doc: &Html, prefix: &str) -> Vec<String> {
+    let multi_value = prefix.eq_ignore_ascii_case("mailto:");
     let mut results = Vec::new();
     for link in doc.select(&LINK_SELECTOR) {
-        if let Some(href) = link.value().attr("href") {
-            if let Some(value) = href.strip_prefix(prefix) {
-                // Trim whitespace and remove query parameters
-                let clean = value.split('?').next().unwrap_or(value).trim();
-                if !clean.is_empty() {
-                    results.push(clean.to_string());
-                }
+        let Some(href) = link.value().attr("href").map(str::trim) else {
+            continue;
+        };
+        let has_prefix = href
+            .get(..prefix.len())
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case(prefix));
+        if !has_prefix {
+            continue;
+        }
+        let value = &href[prefix.len()..];
+        let value = value.split(['?', ';']).next().unwrap_or(value);
+        let decoded = urlencoding::decode(value).map_or_else(|_| value.into(), |v| v.into_owned());
+        let max_parts = if multi_value { usize::MAX } else { 1 };
+        for part in decoded.split(',').take(max_parts) {
+            let clean = part.trim();
+            if !clean.is_empty() {
+                results.push(clean.to_string());
             }
         }
     }
@@ -47,6 +124,358 @@ fn extract_with_regex(doc: &Html, regex: &Regex) -> Vec<String> {
         .collect()
 }
 
+/// Normalized text of the page's main content: the first `<main>`/`<article>`,
+/// else `<body>` without its nav/header/footer/aside chrome. Text nodes are
+/// joined with spaces (so adjacent blocks don't fuse into one word), then run
+/// through `normalize_text`.
+pub(super) fn main_text(doc: &Html) -> String {
+    let (root, skip_chrome) = main_root(doc);
+    let mut parts = Vec::new();
+    collect_content_text(root, skip_chrome, &mut parts);
+    normalize_text(&parts.join(" "))
+}
+
+/// First `n` non-empty main-content paragraphs (normalized text), skipping
+/// image captions (`<figure>`/`<figcaption>`, `*caption*` classes) and — when
+/// falling back to `<body>` — page chrome.
+pub(super) fn lead_paragraphs(doc: &Html, n: usize) -> Vec<String> {
+    let (root, skip_chrome) = main_root(doc);
+    root.select(&P_SELECTOR)
+        .filter(|p| !is_caption_or_chrome(*p, root, skip_chrome))
+        .map(|p| normalize_text(&collect_text(&p, &TextJoinPolicy::SPACED)))
+        .filter(|text| !text.is_empty())
+        .take(n)
+        .collect()
+}
+
+/// The main-content root and whether body chrome must be skipped under it.
+fn main_root(doc: &Html) -> (ElementRef<'_>, bool) {
+    match doc.select(&MAIN_CONTENT_SELECTOR).next() {
+        Some(main) => (main, false),
+        None => (
+            doc.select(&BODY_SELECTOR)
+                .next()
+                .unwrap_or_else(|| doc.root_element()),
+            true,
+        ),
+    }
+}
+
+/// Normalized text of the first visible `<h1>` (see [`is_hidden`]) that has
+/// any.
+pub(super) fn first_h1(doc: &Html) -> Option<String> {
+    doc.select(&H1_SELECTOR)
+        .filter(|h1| !is_hidden(*h1))
+        .map(|h1| normalize_text(&collect_text(&h1, &TextJoinPolicy::SPACED)))
+        .find(|text| !text.is_empty())
+}
+
+/// Whether `element` or an ancestor is hidden from readers: the `hidden`
+/// attribute, `aria-hidden="true"`, or an inline `display: none` /
+/// `visibility: hidden` style.
+pub(super) fn is_hidden(element: ElementRef) -> bool {
+    std::iter::once(element)
+        .chain(element.ancestors().filter_map(ElementRef::wrap))
+        .any(|el| {
+            let el = el.value();
+            let style = el
+                .attr("style")
+                .map(|style| style.to_ascii_lowercase().replace(char::is_whitespace, ""))
+                .unwrap_or_default();
+            el.attr("hidden").is_some()
+                || el
+                    .attr("aria-hidden")
+                    .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"))
+                || style.contains("display:none")
+                || style.contains("visibility:hidden")
+        })
+}
+
+/// The first non-empty `<link rel="canonical">` href, trimmed.
+pub(super) fn canonical_href(doc: &Html) -> Option<String> {
+    doc.select(&CANONICAL_SELECTOR)
+        .filter_map(|link| link.value().attr("href"))
+        .map(str::trim)
+        .find(|href| !href.is_empty())
+        .map(str::to_string)
+}
+
+/// The first non-empty `<link rel="image_src">` href, as written.
+pub(super) fn link_image_src(doc: &Html) -> Option<String> {
+    doc.select(&IMAGE_SRC_LINK_SELECTOR)
+        .filter_map(|link| link.value().attr("href"))
+        .map(str::trim)
+        .find(|href| !href.is_empty())
+        .map(str::to_string)
+}
+
+/// The page's primary content image: the best-scoring `<img>` with a usable
+/// source (its best `srcset` candidate, else lazy-load `data-src`, else a
+/// non-`data:` `src`), as written.
+///
+/// Decorative images — "logo"/"avatar"/"icon"/"spacer" in src, class or id,
+/// or either dimension under [`DECORATIVE_MAX_DIMENSION`] — are never
+/// picked. The rest score up for sitting in `<main>`/`<article>` or a
+/// `<figure>` and for large declared sizes, and down for page chrome
+/// (header/footer/nav/aside). Ties go to the earlier image.
+pub(super) fn primary_image(doc: &Html) -> Option<String> {
+    let mut best: Option<(i32, String)> = None;
+    for img in doc.select(&IMG_SELECTOR) {
+        let attr = |name: &str| {
+            img.value()
+                .attr(name)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let Some(src) = attr("srcset")
+            .or_else(|| attr("data-srcset"))
+            .and_then(best_srcset_url)
+            .or_else(|| attr("data-src").map(str::to_string))
+            .or_else(|| {
+                attr("src")
+                    .filter(|src| !src.starts_with("data:"))
+                    .map(str::to_string)
+            })
+        else {
+            continue;
+        };
+
+        if is_decorative_image(attr, &src) {
+            continue;
+        }
+        let (width, height) = (
+            attr("width").and_then(pixel_dimension),
+            attr("height").and_then(pixel_dimension),
+        );
+
+        let ancestors: Vec<&str> = img
+            .ancestors()
+            .filter_map(|node| node.value().as_element())
+            .map(|el| el.name())
+            .collect();
+        if ancestors.iter().any(|tag| NON_CONTENT_TAGS.contains(tag)) {
+            continue;
+        }
+        let mut score = 0;
+        if ancestors
+            .iter()
+            .any(|tag| matches!(*tag, "main" | "article"))
+        {
+            score += 3;
+        }
+        if ancestors.contains(&"figure") {
+            score += 1;
+        }
+        if ancestors.iter().any(|tag| CHROME_TAGS.contains(tag)) {
+            score -= 4;
+        }
+        if let (Some(w), Some(h)) = (width, height) {
+            if w.saturating_mul(h) >= LARGE_IMAGE_AREA {
+                score += 2;
+            }
+        }
+
+        if best
+            .as_ref()
+            .map_or(true, |(best_score, _)| score > *best_score)
+        {
+            best = Some((score, src));
+        }
+    }
+    best.map(|(_, src)| src)
+}
+
+/// Whether an `<img>` (read through `attr`, already trimmed) is decorative:
+/// a "logo"/"avatar"/"icon"/"spacer"/"sprite" token in `src` (as resolved, or
+/// as written), class or id — `site-logo.png`, not `iconic-margarita.jpg` — or
+/// either declared dimension under [`DECORATIVE_MAX_DIMENSION`]. Alt text is
+/// prose ("Iconic cocktails"), so it's no hint.
+pub(crate) fn is_decorative_image<'a>(attr: impl Fn(&str) -> Option<&'a str>, src: &str) -> bool {
+    let hinted = [attr("src"), Some(src), attr("class"), attr("id")]
+        .into_iter()
+        .flatten()
+        .flat_map(hint_tokens)
+        .any(|token| DECORATIVE_IMAGE_HINTS.contains(&token.as_str()));
+    hinted
+        || [attr("width"), attr("height")]
+            .into_iter()
+            .flatten()
+            .filter_map(pixel_dimension)
+            .any(|d| d < DECORATIVE_MAX_DIMENSION)
+}
+
+/// Lowercased alphanumeric runs of `text`, also split where a lowercase letter
+/// meets an uppercase one: `siteLogo@2x.png?v=icon` → `site`, `logo`, `2x`,
+/// `png`, `v`, `icon`.
+fn hint_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut prev_lower = false;
+    for c in text.chars() {
+        let boundary = !c.is_alphanumeric() || (prev_lower && c.is_uppercase());
+        if boundary && !token.is_empty() {
+            tokens.push(std::mem::take(&mut token));
+        }
+        if c.is_alphanumeric() {
+            token.extend(c.to_lowercase());
+        }
+        prev_lower = c.is_lowercase();
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Whether `host` is a known image CDN ([`IMAGE_CDN_HOSTS`]).
+pub(super) fn is_image_cdn_host(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    IMAGE_CDN_HOSTS.iter().any(|cdn| {
+        host == *cdn
+            || host
+                .strip_suffix(cdn)
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+/// A `width`/`height` attribute as whole pixels (`"600"`, `"600px"`);
+/// percentages and garbage are `None`.
+fn pixel_dimension(value: &str) -> Option<u32> {
+    let value = value.strip_suffix("px").unwrap_or(value).trim();
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+        .map(|n| n as u32)
+}
+
+fn is_caption_or_chrome(p: ElementRef<'_>, root: ElementRef<'_>, skip_chrome: bool) -> bool {
+    let is_caption_class = |el: &scraper::node::Element| {
+        el.classes()
+            .any(|c| c.to_ascii_lowercase().contains("caption"))
+    };
+    if is_caption_class(p.value()) {
+        return true;
+    }
+    p.ancestors()
+        .take_while(|node| node.id() != root.id())
+        .filter_map(|node| node.value().as_element())
+        .any(|el| {
+            let tag = el.name();
+            CAPTION_TAGS.contains(&tag)
+                || NON_CONTENT_TAGS.contains(&tag)
+                || (skip_chrome && CHROME_TAGS.contains(&tag))
+                || is_caption_class(el)
+        })
+}
+
+fn collect_content_text<'a>(element: ElementRef<'a>, skip_chrome: bool, out: &mut Vec<&'a str>) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            out.push(text);
+        } else if let Some(el) = ElementRef::wrap(child) {
+            let tag = el.value().name();
+            if NON_CONTENT_TAGS.contains(&tag) || (skip_chrome && CHROME_TAGS.contains(&tag)) {
+                continue;
+            }
+            collect_content_text(el, skip_chrome, out);
+        }
+    }
+}
+
+/// All hreflang alternates in the document (see `extract_hreflang`).
+pub(super) fn extract_hreflang_links(html: &str, base_url: &str) -> Vec<HreflangLink> {
+    let doc = Html::parse_document(html);
+    let base = url::Url::parse(base_url).ok();
+    let mut links: Vec<HreflangLink> = Vec::new();
+    for el in doc.select(&HREFLANG_SELECTOR) {
+        let (Some(lang), Some(href)) = (el.value().attr("hreflang"), el.value().attr("href"))
+        else {
+            continue;
+        };
+        let lang = normalize_lang_code(lang);
+        if lang.is_empty() {
+            continue;
+        }
+        let resolved = match &base {
+            Some(base) => base.join(href.trim()),
+            None => url::Url::parse(href.trim()),
+        };
+        let Ok(href) = resolved else {
+            continue;
+        };
+        let link = HreflangLink {
+            lang,
+            href: href.to_string(),
+        };
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+    links
+}
+
+/// Hosts of `<script src>`, `<img src>` and `<link href>` resolved against
+/// `page_url`, lowercased and deduplicated in document order. First-party
+/// hosts — the page's own, ignoring a leading `www.` — and non-HTTP(S)
+/// references are skipped.
+pub(super) fn extract_third_party_hosts(html: &str, page_url: &str) -> Vec<String> {
+    let Ok(base) = url::Url::parse(page_url) else {
+        return Vec::new();
+    };
+    let own_host = base
+        .host_str()
+        .map(|h| h.trim_start_matches("www.").to_ascii_lowercase());
+
+    let doc = Html::parse_document(html);
+    let mut hosts = Vec::new();
+    for el in doc.select(&SUBRESOURCE_SELECTOR) {
+        let attr = if el.value().name() == "link" {
+            "href"
+        } else {
+            "src"
+        };
+        let Some(Ok(resolved)) = el.value().attr(attr).map(|v| base.join(v.trim())) else {
+            continue;
+        };
+        if !matches!(resolved.scheme(), "http" | "https") {
+            continue;
+        }
+        let Some(host) = resolved.host_str().map(str::to_ascii_lowercase) else {
+            continue;
+        };
+        if own_host.as_deref() != Some(host.trim_start_matches("www.")) {
+            push_unique(&mut hosts, host);
+        }
+    }
+    hosts
+}
+
+/// Normalize a BCP 47 tag's casing: language lowercase, 4-letter script
+/// titlecase, 2-letter region uppercase; `_` separators become `-`.
+fn normalize_lang_code(lang: &str) -> String {
+    let lang = lang.trim().replace('_', "-");
+    if lang.eq_ignore_ascii_case("x-default") {
+        return "x-default".to_string();
+    }
+    lang.split('-')
+        .filter(|s| !s.is_empty())
+        .enumerate()
+        .map(|(i, sub)| match (i, sub.len()) {
+            // Not a well-formed subtag; its byte length says nothing.
+            _ if !sub.is_ascii() => sub.to_string(),
+            (0, _) => sub.to_ascii_lowercase(),
+            (_, 2) => sub.to_ascii_uppercase(),
+            (_, 4) => {
+                let (head, tail) = sub.split_at(1);
+                head.to_ascii_uppercase() + &tail.to_ascii_lowercase()
+            }
+            _ => sub.to_ascii_lowercase(),
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
 /// Extract all email addresses from HTML document.
 pub(super) fn extract_email_elements(html: &str) -> Vec<String> {
     let doc = Html::parse_fragment(html);
@@ -56,6 +485,34 @@ pub(super) fn extract_email_elements(html: &str) -> Vec<String> {
     )
 }
 
+/// Extract email addresses that pass [`is_strict_email`].
+pub(super) fn extract_email_elements_strict(html: &str) -> Vec<String> {
+    extract_email_elements(html)
+        .into_iter()
+        .filter(|email| is_strict_email(email))
+        .collect()
+}
+
+/// Whether `email` matches the strict grammar and isn't a file/version
+/// lookalike (`image@2x`, `v1@1.0`, `logo@2x.png`).
+pub(super) fn is_strict_email(email: &str) -> bool {
+    if !STRICT_EMAIL_REGEX.is_match(email) || email.contains("..") {
+        return false;
+    }
+    let Some((_, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    let tld = domain.rsplit('.').next().unwrap_or_default();
+    let first_label = domain.split('.').next().unwrap_or_default();
+    // `@2x.` / `@3x.` scale suffixes and purely numeric hosts are asset names.
+    let is_scale_suffix = first_label
+        .strip_suffix(['x', 'X'])
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+    !FILE_EXTENSION_TLDS.contains(&tld.to_ascii_lowercase().as_str())
+        && !is_scale_suffix
+        && !first_label.bytes().all(|b| b.is_ascii_digit())
+}
+
 /// Extract all phone numbers from HTML document.
 pub(super) fn extract_phone_elements(html: &str) -> Vec<String> {
     let doc = Html::parse_fragment(html);
@@ -66,10 +523,13 @@ pub(super) fn extract_phone_elements(html: &str) -> Vec<String> {
 }
 
 /// Find the first non-empty value for any of the given keys in metadata pairs.
-pub(super) fn find_metadata_value(pairs: &[(String, String)], keys: &[&str]) -> Option<String> {
+pub(super) fn find_metadata_value<K: AsRef<str>>(
+    pairs: &[(String, String)],
+    keys: &[K],
+) -> Option<String> {
     for key in keys {
         for (k, v) in pairs {
-            if k.eq_ignore_ascii_case(key) {
+            if k.eq_ignore_ascii_case(key.as_ref()) {
                 let cleaned = v.trim().to_string();
                 if !cleaned.is_empty() {
                     return Some(cleaned);
@@ -80,6 +540,400 @@ pub(super) fn find_metadata_value(pairs: &[(String, String)], keys: &[&str]) ->
     None
 }
 
+/// Every non-empty value for `keys`, in key order then document order —
+/// repeated tags (a gallery's `og:image`s) all included. Values are trimmed
+/// and deduplicated.
+pub(super) fn find_metadata_values<K: AsRef<str>>(
+    pairs: &[(String, String)],
+    keys: &[K],
+) -> Vec<String> {
+    let mut values: Vec<String> = Vec::new();
+    for key in keys {
+        for (k, v) in pairs {
+            if k.eq_ignore_ascii_case(key.as_ref()) {
+                let cleaned = v.trim();
+                if !cleaned.is_empty() && !values.iter().any(|seen| seen == cleaned) {
+                    values.push(cleaned.to_string());
+                }
+            }
+        }
+    }
+    values
+}
+
+/// Keyword form for `extract_keywords`: normalized text, lowercased.
+pub(super) fn normalize_keyword(raw: &str) -> String {
+    normalize_text(raw).to_lowercase()
+}
+
+/// The object itself, or the first object of an array.
+pub(super) fn first_object(value: &serde_json::Value) -> Option<&serde_json::Value> {
+    match value {
+        serde_json::Value::Array(arr) => arr.iter().find(|v| v.is_object()),
+        serde_json::Value::Object(_) => Some(value),
+        _ => None,
+    }
+}
+
+/// A JSON number, or a numeric string (`"4.8"`, `"1,204"`).
+pub(super) fn json_f64(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().replace(',', "").parse().ok(),
+        _ => None,
+    }
+    .filter(|n: &f64| n.is_finite())
+}
+
+/// A non-negative whole count from a JSON number or numeric string.
+pub(super) fn json_u64(value: &serde_json::Value) -> Option<u64> {
+    json_f64(value)
+        .filter(|n| *n >= 0.0 && n.fract() == 0.0)
+        .map(|n| n as u64)
+}
+
+/// `(type name, count)` pairs from an `interactionStatistic` value (one
+/// `InteractionCounter` or an array). `interactionType` may be a URL
+/// (`https://schema.org/LikeAction`), a bare name, or a `{"@type": …}` object.
+pub(super) fn interaction_counters(value: Option<&serde_json::Value>) -> Vec<(String, u64)> {
+    let counters: Vec<&serde_json::Value> = match value {
+        Some(serde_json::Value::Array(arr)) => arr.iter().collect(),
+        Some(v @ serde_json::Value::Object(_)) => vec![v],
+        _ => Vec::new(),
+    };
+    counters
+        .into_iter()
+        .filter_map(|counter| {
+            let kind = match counter.get("interactionType")? {
+                serde_json::Value::String(s) => s.as_str(),
+                other => other.get("@type")?.as_str()?,
+            };
+            let kind = kind.rsplit('/').next().unwrap_or(kind).trim();
+            let count = counter.get("userInteractionCount").and_then(json_u64)?;
+            (!kind.is_empty()).then(|| (kind.to_string(), count))
+        })
+        .collect()
+}
+
+/// JSON-LD objects whose `@type` includes `ty` (short name, case-insensitive;
+/// IRIs like `https://schema.org/Recipe` accepted), searching arrays and
+/// `@graph`, in document order.
+pub(super) fn find_schema_objects<'a>(
+    jsonld: &'a [serde_json::Value],
+    ty: &str,
+) -> Vec<&'a serde_json::Map<String, serde_json::Value>> {
+    fn walk<'a>(
+        value: &'a serde_json::Value,
+        ty: &str,
+        out: &mut Vec<&'a serde_json::Map<String, serde_json::Value>>,
+    ) {
+        match value {
+            serde_json::Value::Array(arr) => arr.iter().for_each(|v| walk(v, ty, out)),
+            serde_json::Value::Object(obj) => {
+                if has_schema_type(obj, ty) {
+                    out.push(obj);
+                }
+                if let Some(graph) = obj.get("@graph") {
+                    walk(graph, ty, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    jsonld.iter().for_each(|v| walk(v, ty, &mut out));
+    out
+}
+
+/// Like [`find_schema_objects`], but also searching nested property values
+/// (e.g. a Recipe's `image` ImageObjects), in document order.
+pub(super) fn find_nested_schema_objects<'a>(
+    jsonld: &'a [serde_json::Value],
+    ty: &str,
+) -> Vec<&'a serde_json::Map<String, serde_json::Value>> {
+    fn walk<'a>(
+        value: &'a serde_json::Value,
+        ty: &str,
+        out: &mut Vec<&'a serde_json::Map<String, serde_json::Value>>,
+    ) {
+        match value {
+            serde_json::Value::Array(arr) => arr.iter().for_each(|v| walk(v, ty, out)),
+            serde_json::Value::Object(obj) => {
+                if has_schema_type(obj, ty) {
+                    out.push(obj);
+                }
+                obj.values().for_each(|v| walk(v, ty, out));
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    jsonld.iter().for_each(|v| walk(v, ty, &mut out));
+    out
+}
+
+/// Values of property `key` on top-level JSON-LD objects (searching arrays
+/// and `@graph`, not nested values — so a Recipe's `review[].author` isn't
+/// the Recipe's author), in document order.
+pub(super) fn find_property_values<'a>(
+    jsonld: &'a [serde_json::Value],
+    key: &str,
+) -> Vec<&'a serde_json::Value> {
+    fn walk<'a>(value: &'a serde_json::Value, key: &str, out: &mut Vec<&'a serde_json::Value>) {
+        match value {
+            serde_json::Value::Array(arr) => arr.iter().for_each(|v| walk(v, key, out)),
+            serde_json::Value::Object(obj) => {
+                if let Some(found) = obj.get(key) {
+                    out.push(found);
+                }
+                if let Some(graph) = obj.get("@graph") {
+                    walk(graph, key, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    jsonld.iter().for_each(|v| walk(v, key, &mut out));
+    out
+}
+
+/// JSON-LD objects carrying an `@id`, by id (first wins), for resolving
+/// `{"@id": …}` references within a `@graph`.
+pub(super) fn nodes_by_id(
+    jsonld: &[serde_json::Value],
+) -> std::collections::HashMap<&str, &serde_json::Map<String, serde_json::Value>> {
+    fn walk<'a>(
+        value: &'a serde_json::Value,
+        out: &mut std::collections::HashMap<
+            &'a str,
+            &'a serde_json::Map<String, serde_json::Value>,
+        >,
+    ) {
+        match value {
+            serde_json::Value::Array(arr) => arr.iter().for_each(|v| walk(v, out)),
+            serde_json::Value::Object(obj) => {
+                // A bare reference (`@id` only) isn't a node definition.
+                if let Some(id) = obj.get("@id").and_then(serde_json::Value::as_str) {
+                    if obj.len() > 1 {
+                        out.entry(id).or_insert(obj);
+                    }
+                }
+                obj.values().for_each(|v| walk(v, out));
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = std::collections::HashMap::new();
+    jsonld.iter().for_each(|v| walk(v, &mut out));
+    out
+}
+
+/// Whether a JSON-LD object's `@type` (string or array) includes `ty`.
+pub(super) fn has_schema_type(obj: &serde_json::Map<String, serde_json::Value>, ty: &str) -> bool {
+    fn short_type(t: &str) -> &str {
+        t.rsplit(['/', '#', ':']).next().unwrap_or(t)
+    }
+    match obj.get("@type") {
+        Some(serde_json::Value::String(t)) => short_type(t).eq_ignore_ascii_case(ty),
+        Some(serde_json::Value::Array(types)) => types
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .any(|t| short_type(t).eq_ignore_ascii_case(ty)),
+        _ => false,
+    }
+}
+
+/// The first string `headline` in JSON-LD (top level or `@graph`).
+pub(super) fn json_headline(jsonld: &[serde_json::Value]) -> Option<String> {
+    find_property_values(jsonld, "headline")
+        .into_iter()
+        .find_map(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// A `SearchAction` `target`: a URL template string or an `EntryPoint`'s
+/// `urlTemplate`, or an array of either (first wins).
+pub(super) fn url_template(value: &serde_json::Value) -> Option<String> {
+    let template = match value {
+        serde_json::Value::String(s) => s.as_str(),
+        serde_json::Value::Object(obj) => obj.get("urlTemplate")?.as_str()?,
+        serde_json::Value::Array(arr) => return arr.iter().find_map(url_template),
+        _ => return None,
+    };
+    Some(template.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+/// The placeholder name of a `query-input`: `name=…` in the
+/// `"required name=search_term_string"` shorthand, or a
+/// `PropertyValueSpecification`'s `valueName`.
+pub(super) fn query_input_name(value: &serde_json::Value) -> Option<String> {
+    let name = match value {
+        serde_json::Value::String(s) => s
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix("name="))?,
+        serde_json::Value::Object(obj) => obj.get("valueName")?.as_str()?.trim(),
+        serde_json::Value::Array(arr) => return arr.iter().find_map(query_input_name),
+        _ => return None,
+    };
+    Some(name.to_string()).filter(|n| !n.is_empty())
+}
+
+/// A text property that may be a string or a named entity
+/// (`{"@type": "Person", "name": …}`), or an array of either (first wins).
+pub(super) fn json_name(value: Option<&serde_json::Value>) -> Option<String> {
+    let text = match value? {
+        serde_json::Value::String(s) => s.as_str(),
+        serde_json::Value::Object(obj) => obj.get("name")?.as_str()?,
+        serde_json::Value::Array(arr) => return arr.iter().find_map(|v| json_name(Some(v))),
+        _ => return None,
+    };
+    Some(crate::tools::normalize::normalize_text(text)).filter(|s| !s.is_empty())
+}
+
+/// A string or array of strings as normalized, non-empty strings.
+pub(super) fn json_strings(value: Option<&serde_json::Value>) -> Vec<String> {
+    let items: Vec<&str> = match value {
+        Some(serde_json::Value::String(s)) => vec![s.as_str()],
+        Some(serde_json::Value::Array(arr)) => {
+            arr.iter().filter_map(serde_json::Value::as_str).collect()
+        }
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .map(normalize_text)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// A `recipeYield` value — a string, a number, or an array of either — as
+/// normalized, non-empty strings.
+pub(super) fn yield_strings(value: Option<&serde_json::Value>) -> Vec<String> {
+    let items = match value {
+        Some(serde_json::Value::Array(arr)) => arr.iter().collect(),
+        Some(value) => vec![value],
+        None => Vec::new(),
+    };
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            serde_json::Value::String(s) => Some(normalize_text(s)),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// The first run of ASCII digits in `text` as a number ("Makes 12 cookies"
+/// → 12); `None` without digits or on overflow.
+pub(super) fn leading_integer(text: &str) -> Option<u32> {
+    let start = text.find(|c: char| c.is_ascii_digit())?;
+    let digits = &text[start..];
+    let end = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    digits[..end].parse().ok()
+}
+
+/// Flatten `recipeInstructions` into `out`, tagging steps with the enclosing
+/// `HowToSection` name.
+pub(super) fn flatten_instructions(
+    value: &serde_json::Value,
+    section: Option<&str>,
+    out: &mut Vec<InstructionStep>,
+) {
+    let mut push = |text: &str| {
+        let text = normalize_text(text);
+        if !text.is_empty() {
+            out.push(InstructionStep {
+                text,
+                section: section.map(str::to_string),
+            });
+        }
+    };
+    match value {
+        serde_json::Value::String(s) => s.lines().for_each(push),
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                flatten_instructions(item, section, out);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            let is_section = has_schema_type(obj, "HowToSection");
+            let name = obj.get("name").and_then(serde_json::Value::as_str);
+            let text = obj.get("text").and_then(serde_json::Value::as_str);
+            match obj.get("itemListElement") {
+                Some(children) if is_section || text.is_none() => {
+                    let section = if is_section {
+                        name.map(str::trim).filter(|n| !n.is_empty()).or(section)
+                    } else {
+                        section
+                    };
+                    flatten_instructions(children, section, out);
+                }
+                _ => {
+                    if let Some(step) = text.or(name) {
+                        push(step);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Anchor texts that mark a recipe article's jump link.
+const RECIPE_JUMP_TEXTS: &[&str] = &["jump to recipe", "go to recipe"];
+
+/// `href` of the first "Jump to Recipe" anchor, with a bare `#fragment`
+/// (percent-decoded) resolved to its target's CSS selector.
+pub(super) fn recipe_anchor(doc: &Html) -> Option<String> {
+    let href = doc.select(&LINK_SELECTOR).find_map(|link| {
+        let text = normalize_text(&link.text().collect::<String>()).to_lowercase();
+        RECIPE_JUMP_TEXTS
+            .iter()
+            .any(|t| text.contains(t))
+            .then(|| link.value().attr("href"))
+            .flatten()
+    })?;
+    let href = href.trim();
+    // Only a bare fragment is known to point into this page; a URL with one
+    // may name another page (or site) entirely.
+    let fragment = href.strip_prefix('#').unwrap_or("");
+    let fragment = urlencoding::decode(fragment).map_or_else(|_| fragment.into(), |f| f);
+    Some(fragment_selector(doc, &fragment).unwrap_or_else(|| href.to_string()))
+}
+
+/// CSS selector of the element a URL fragment points at (`id`, else an
+/// `<a name>` anchor), if the document has one.
+fn fragment_selector(doc: &Html, fragment: &str) -> Option<String> {
+    if fragment.is_empty() {
+        return None;
+    }
+    let quoted = fragment.replace('\\', "\\\\").replace('"', "\\\"");
+    let plain_ident = fragment
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !fragment
+            .trim_start_matches('-')
+            .starts_with(|c: char| c.is_ascii_digit());
+    let mut candidates = Vec::new();
+    if plain_ident {
+        candidates.push(format!("#{fragment}"));
+    }
+    candidates.push(format!("[id=\"{quoted}\"]"));
+    candidates.push(format!("[name=\"{quoted}\"]"));
+    candidates.into_iter().find(|selector| {
+        scraper::Selector::parse(selector).is_ok_and(|sel| doc.select(&sel).next().is_some())
+    })
+}
+
 pub(super) fn push_unique(items: &mut Vec<String>, value: String) {
     if !items.iter().any(|existing| existing == &value) {
         items.push(value);
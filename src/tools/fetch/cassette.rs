@@ -0,0 +1,89 @@
+//! Record/replay ("VCR") fetching for offline, deterministic tests.
+
+use std::path::{Path, PathBuf};
+
+use crate::errors::QrawlError;
+use crate::types::Html;
+
+/// Whether a [`CassetteFetcher`] hits the network or serves recordings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Fetch with [`fetch_strategy`](super::fetch_strategy) and save each
+    /// page under the cassette directory (overwriting older recordings).
+    Record,
+    /// Serve saved pages only; a URL with no recording is an error.
+    Replay,
+}
+
+/// Fetcher that records HTML to one file per URL, then replays it.
+///
+/// Record once against live sites, commit the directory, and run network-heavy
+/// tests in `Replay` mode offline. Recordings are keyed by the exact URL
+/// string; only successful fetches are recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CassetteFetcher {
+    dir: PathBuf,
+    mode: CassetteMode,
+}
+
+impl CassetteFetcher {
+    /// A fetcher over the cassette directory `dir` (created on first record).
+    pub fn new(dir: impl Into<PathBuf>, mode: CassetteMode) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+        }
+    }
+
+    /// Record or replay.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// The recording file for `url`: a readable slug of the URL plus a hash
+    /// of the whole URL, so distinct URLs never share a file.
+    pub fn path_for(&self, url: &str) -> PathBuf {
+        let slug: String = url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(80)
+            .collect();
+        let hash = crate::hash::fnv1a(url.as_bytes());
+        self.dir.join(format!("{slug}-{hash:016x}.html"))
+    }
+
+    /// Fetch `url`: from the network (saving it) in `Record` mode, from its
+    /// recording in `Replay` mode.
+    pub async fn fetch(&self, url: &str) -> Result<Html, QrawlError> {
+        let path = self.path_for(url);
+        match self.mode {
+            CassetteMode::Record => {
+                let html = super::fetch_strategy(url).await?;
+                let body = html.to_string();
+                let dir = self.dir.clone();
+                tokio::task::spawn_blocking(move || write_recording(&dir, &path, &body))
+                    .await
+                    .expect("CassetteFetcher::fetch: spawn_blocking failed")?;
+                Ok(html)
+            }
+            CassetteMode::Replay => {
+                let url = url.to_string();
+                tokio::task::spawn_blocking(move || {
+                    std::fs::read_to_string(&path).map(Html::new).map_err(|e| {
+                        QrawlError::new(format!("no cassette recording for {url}: {e}"))
+                    })
+                })
+                .await
+                .expect("CassetteFetcher::fetch: spawn_blocking failed")
+            }
+        }
+    }
+}
+
+fn write_recording(dir: &Path, path: &Path, body: &str) -> Result<(), QrawlError> {
+    std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(path, body))
+        .map_err(|e| QrawlError::new(format!("failed to write cassette {}: {e}", path.display())))
+}
@@ -1,25 +1,65 @@
 use super::profile::FetchProfile;
+use super::types::PoolConfig;
 use crate::errors::QrawlError;
-use reqwest::{redirect, Client};
-use std::time::Duration;
+use crate::types::get_pool_config;
+use reqwest::{redirect, Client, ClientBuilder};
 
 const REDIRECT_LIMIT: usize = 10;
-const POOL_IDLE_TIMEOUT_SEC: u64 = 90;
+pub(super) const POOL_IDLE_TIMEOUT_SEC: u64 = 90;
 /// Match `PER_HOST_CONCURRENCY` (from `strategies.rs`) with 2× headroom so a brief burst of completions can all be reused. Anything more is wasted — in-flight requests per host are already capped by the semaphore.
-const POOL_MAX_IDLE_PER_HOST: usize = 16;
+pub(super) const POOL_MAX_IDLE_PER_HOST: usize = 16;
+
+/// Client-level settings that can't be applied per request. Part of the client cache key alongside the profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct ClientOptions {
+    /// Accept invalid / self-signed / expired TLS certificates.
+    ///
+    /// **Unsafe for production**: disables certificate verification entirely, so the connection can be intercepted. Dev-only escape hatch for sites with misconfigured certs (see `fetch_auto_insecure`).
+    pub danger_accept_invalid_certs: bool,
+    /// Connection-pool settings, from the pipeline's `Context` (see `with_context_pool`).
+    pub pool: PoolConfig,
+}
+
+impl ClientOptions {
+    pub(crate) const INSECURE: Self = Self {
+        danger_accept_invalid_certs: true,
+        pool: PoolConfig::DEFAULT,
+    };
+
+    /// These options with the task-local `Context`'s pool settings (the default pool outside a pipeline).
+    pub(crate) fn with_context_pool(self) -> Self {
+        Self {
+            pool: get_pool_config(),
+            ..self
+        }
+    }
+}
 
 /// Build a reqwest client optimized for the given profile.
 ///
 /// No default timeout is set here: every request applies its own timeout via `RequestBuilder::timeout(get_fetch_timeout())` so callers can override per `Context::with_fetch_timeout(...)` without rebuilding the client.
-pub(crate) fn build_client_for_profile(profile: FetchProfile) -> Result<Client, QrawlError> {
+pub(crate) fn build_client_for_profile(
+    profile: FetchProfile,
+    options: ClientOptions,
+) -> Result<Client, QrawlError> {
+    client_builder_for_profile(profile, options)
+        .build()
+        .map_err(|e| QrawlError::new(format!("Failed to build client: {}", e)))
+}
+
+/// Configured (unbuilt) client builder for the given profile and options.
+pub(crate) fn client_builder_for_profile(
+    profile: FetchProfile,
+    options: ClientOptions,
+) -> ClientBuilder {
     let builder = Client::builder()
         .cookie_store(true)
         .redirect(redirect::Policy::limited(REDIRECT_LIMIT))
         .gzip(true)
         .brotli(true)
         .deflate(true)
-        .pool_idle_timeout(Duration::from_secs(POOL_IDLE_TIMEOUT_SEC))
-        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST);
+        .pool_idle_timeout(options.pool.idle_timeout)
+        .pool_max_idle_per_host(options.pool.max_idle_per_host);
 
     // Minimal profile: simpler client
     let builder = match profile {
@@ -29,7 +69,5 @@ pub(crate) fn build_client_for_profile(profile: FetchProfile) -> Result<Client,
         _ => builder,
     };
 
-    builder
-        .build()
-        .map_err(|e| QrawlError::new(format!("Failed to build client: {}", e)))
+    builder.danger_accept_invalid_certs(options.danger_accept_invalid_certs)
 }
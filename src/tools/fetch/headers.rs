@@ -1,5 +1,5 @@
 use super::profile::FetchProfile;
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_LANGUAGE, REFERER, USER_AGENT};
 
 /// Build complete header map for the given profile, including User-Agent.
 pub(crate) fn headers_for_profile(profile: FetchProfile) -> HeaderMap {
@@ -24,6 +24,23 @@ pub(crate) fn headers_for_profile(profile: FetchProfile) -> HeaderMap {
     headers
 }
 
+/// Profile headers plus per-request overrides: an optional `Referer`, and an
+/// optional `Accept-Language` replacing the profile's default. Values that
+/// aren't valid header values are ignored.
+pub(crate) fn request_headers(
+    profile: FetchProfile,
+    referer: Option<&str>,
+    accept_language: Option<&str>,
+) -> HeaderMap {
+    let mut headers = headers_for_profile(profile);
+    for (name, value) in [(REFERER, referer), (ACCEPT_LANGUAGE, accept_language)] {
+        if let Some(val) = value.and_then(|v| HeaderValue::from_str(v).ok()) {
+            headers.insert(name, val);
+        }
+    }
+    headers
+}
+
 /// Get User-Agent string for the given profile (private, only used internally).
 fn user_agent_for_profile(profile: FetchProfile) -> &'static str {
     match profile {
@@ -1,5 +1,6 @@
 //! Fetch Tools
 
+pub mod cassette;
 mod client;
 mod headers;
 pub mod profile;
@@ -9,11 +10,13 @@ mod utils;
 mod tests;
 pub mod types;
 
+pub use cassette::{CassetteFetcher, CassetteMode};
 pub use types::*;
 
 use crate::errors::QrawlError;
 use crate::types::{
-    fetch_cache_get, fetch_cache_put, get_fetch_strategy, FetchStrategy, Html, CTX,
+    fetch_cache_get, fetch_cache_get_variant, fetch_cache_put, fetch_cache_put_variant,
+    get_fetch_strategy, FetchStrategy, Html, CTX,
 };
 
 fn host_matches(host: &str, domain: &str) -> bool {
@@ -61,10 +64,16 @@ pub(crate) fn is_url_allowed(
 
 fn check_domain_filter(url: &str) -> Result<(), QrawlError> {
     let filters = CTX
-        .try_with(|ctx| (ctx.allow_domains.clone(), ctx.block_domains.clone()))
+        .try_with(|ctx| {
+            (
+                ctx.allow_domains.clone(),
+                ctx.block_domains.clone(),
+                ctx.block_domain_patterns.clone(),
+            )
+        })
         .ok();
-    let (allow, block) = match filters {
-        Some((a, b)) if !a.is_empty() || !b.is_empty() => (a, b),
+    let (allow, block, patterns) = match filters {
+        Some((a, b, p)) if !a.is_empty() || !b.is_empty() || !p.is_empty() => (a, b, p),
         _ => return Ok(()),
     };
     let allow_d = (!allow.is_empty()).then_some(allow.as_slice());
@@ -76,6 +85,9 @@ fn check_domain_filter(url: &str) -> Result<(), QrawlError> {
         Some(h) if !is_host_allowed(&h, allow_d, block_d) => {
             Err(QrawlError::new(format!("blocked by domain filter: {h}")))
         }
+        Some(h) if patterns.iter().any(|p| p.matches(&h)) => {
+            Err(QrawlError::new(format!("blocked by domain pattern: {h}")))
+        }
         Some(_) => Ok(()),
         None if !allow.is_empty() => Err(QrawlError::new(format!(
             "blocked by domain filter: unparseable host in {url}"
@@ -84,6 +96,32 @@ fn check_domain_filter(url: &str) -> Result<(), QrawlError> {
     }
 }
 
+/// Detect a paywall, consent wall, captcha or bot check served in place of
+/// the page.
+///
+/// Looks for bot-check and captcha markers (Cloudflare challenge scripts,
+/// reCAPTCHA / hCaptcha widgets, …), then consent and paywall signals — CMP
+/// vendor markers (OneTrust, Quantcast, …), "accept cookies" / "subscribe to
+/// continue" calls to action. All only count on a page with little visible
+/// text, since ordinary pages carry challenge scripts, comment-form captchas,
+/// cookie banners and subscribe links too.
+pub fn detect_wall(html: &Html) -> Option<WallKind> {
+    utils::detect_wall(html.as_str())
+}
+
+/// Detect a "soft 404": a missing page served with a 2xx status.
+///
+/// Scores a not-found title or `<h1>` ("Page Not Found", "404", …), a
+/// not-found final URL (`/404`, `/not-found`, …, after an error redirect),
+/// thin visible content and a back-home / search call to action (a bare search
+/// box only on a thin page, since most headers carry one). A not-found
+/// heading plus one other signal is enough; without one, all three others are
+/// needed — so an article that merely mentions "404" in its title stays
+/// `false`. Pass the post-redirect URL as `final_url`.
+pub fn is_soft_404(html: &Html, final_url: &str) -> bool {
+    utils::is_soft_404(html.as_str(), final_url)
+}
+
 /// Fetch with fast strategy
 pub async fn fetch_fast(url: &str) -> Result<Html, QrawlError> {
     check_domain_filter(url)?;
@@ -110,9 +148,113 @@ pub async fn fetch_auto(url: &str) -> Result<Html, QrawlError> {
     Ok(Html::new(html))
 }
 
-/// Fetch with auto strategy, returning full result with metadata.
-pub async fn fetch_auto_with_result(url: &str) -> Result<FetchResult, QrawlError> {
-    strategies::fetch_auto_with_client(url).await
+/// Fetch with auto strategy, accepting invalid TLS certificates.
+///
+/// **Unsafe for production**: certificate verification is disabled, so the
+/// connection can be intercepted. Dev-only escape hatch for target sites with
+/// misconfigured certs; use [`fetch_auto`] everywhere else.
+///
+/// Bypasses the per-pipeline fetch cache in both directions, so a page fetched
+/// without verification never answers a later [`fetch_auto`] (and vice versa).
+pub async fn fetch_auto_insecure(url: &str) -> Result<Html, QrawlError> {
+    check_domain_filter(url)?;
+    let html = strategies::fetch_auto_with_client_options(
+        url,
+        &FetchOptions {
+            client: client::ClientOptions::INSECURE,
+            ..strategies::context_options()
+        },
+    )
+    .await
+    .map(|r| r.html)?;
+    Ok(Html::new(html))
+}
+
+/// Fetch with auto strategy, sending `Accept-Language: {lang}` on every
+/// attempt (e.g. `"en-US,en;q=0.9"`) in place of the profile's default.
+///
+/// Results are cached per pipeline under the URL *and* `lang`, so one URL
+/// fetched in two languages (or also via [`fetch_auto`]) keeps each body.
+pub async fn fetch_auto_lang(url: &str, lang: &str) -> Result<Html, QrawlError> {
+    check_domain_filter(url)?;
+    let variant = format!("accept-language: {lang}");
+    if let Some(cached) = fetch_cache_get_variant(url, &variant) {
+        return Ok(Html::new(cached));
+    }
+    let html = strategies::fetch_auto_with_client_options(
+        url,
+        &FetchOptions {
+            accept_language: Some(lang.to_string()),
+            ..strategies::context_options()
+        },
+    )
+    .await
+    .map(|r| r.html)?;
+    fetch_cache_put_variant(url, &variant, &html);
+    Ok(Html::new(html))
+}
+
+/// Fetch with auto strategy, sending the URL's own origin (`scheme://host`)
+/// as `Referer` on every attempt — defeats naive hotlink protection on image
+/// and recipe hosts that 403 refererless requests. [`fetch_auto`] stays
+/// refererless.
+///
+/// Results share the per-pipeline fetch cache with [`fetch_auto`], which is
+/// keyed by URL only.
+pub async fn fetch_auto_with_referer(url: &str) -> Result<Html, QrawlError> {
+    check_domain_filter(url)?;
+    if let Some(cached) = fetch_cache_get(url) {
+        return Ok(Html::new(cached));
+    }
+    let html = strategies::fetch_auto_with_client_options(
+        url,
+        &FetchOptions {
+            referer: utils::url_origin(url),
+            ..strategies::context_options()
+        },
+    )
+    .await
+    .map(|r| r.html)?;
+    fetch_cache_put(url, &html);
+    Ok(Html::new(html))
+}
+
+/// Fetch with auto strategy, returning full result with metadata, including
+/// any interstitial [`detect_wall`] finds in the page.
+pub async fn fetch_auto_with_result(url: &str) -> Result<FetchResult, FetchError> {
+    let result = strategies::fetch_auto_with_client(url).await?;
+    Ok(with_wall(result).await)
+}
+
+/// Fetch with auto strategy under an explicit [`StrategyConfig`] (e.g. a pinned
+/// profile order for reproducible tests), returning full result with metadata.
+/// The config replaces only the context's strategy; its domain filters, pool
+/// and cancellation still apply.
+pub async fn fetch_auto_with_config(
+    url: &str,
+    config: &strategies::StrategyConfig,
+) -> Result<FetchResult, FetchError> {
+    check_domain_filter(url)?;
+    let result = strategies::fetch_auto_with_client_options(
+        url,
+        &FetchOptions {
+            strategy: config.clone(),
+            ..strategies::context_options()
+        },
+    )
+    .await?;
+    Ok(with_wall(result).await)
+}
+
+/// Fill in `result.wall` off the async executor. Only the `FetchResult`
+/// entry points pay for the scan; `fetch_auto` and friends skip it.
+async fn with_wall(mut result: FetchResult) -> FetchResult {
+    tokio::task::spawn_blocking(move || {
+        result.wall = utils::detect_wall(&result.html);
+        result
+    })
+    .await
+    .expect("with_wall: spawn_blocking failed")
 }
 
 pub async fn fetch_strategy(url: &str) -> Result<Html, QrawlError> {
@@ -122,11 +264,23 @@ pub async fn fetch_strategy(url: &str) -> Result<Html, QrawlError> {
     }
 }
 
+/// Probe a URL's reachability without downloading its body.
+///
+/// Sends a HEAD (falling back to a one-byte ranged GET when HEAD is refused)
+/// and returns status, content type, size and post-redirect URL. Non-2xx
+/// statuses are returned, not errors — use them to pre-filter dead seeds.
+/// Errors only on domain-filter blocks and transport failures.
+pub async fn fetch_probe(url: &str) -> Result<ProbeResult, QrawlError> {
+    check_domain_filter(url)?;
+    Ok(strategies::fetch_probe_with_client(url).await?)
+}
+
 /// Fetch raw bytes (images, PDFs, other binary content) using same strategy
 pub async fn fetch_bytes(url: &str, referer: Option<&str>) -> Result<Vec<u8>, QrawlError> {
     check_domain_filter(url)?;
-    match get_fetch_strategy() {
+    let bytes = match get_fetch_strategy() {
         FetchStrategy::Fast => strategies::fetch_bytes_fast_with_client(url, referer).await,
         FetchStrategy::Auto => strategies::fetch_bytes_auto_with_client(url, referer).await,
-    }
+    };
+    Ok(bytes?)
 }
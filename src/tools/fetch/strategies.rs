@@ -1,19 +1,20 @@
-use super::client::build_client_for_profile;
-use super::headers::headers_for_profile;
+use super::client::{build_client_for_profile, ClientOptions};
+use super::headers::request_headers;
 use super::profile::FetchProfile;
 use super::types::*;
 use super::utils::*;
-use crate::errors::QrawlError;
-use crate::types::get_fetch_timeout;
+use crate::types::{
+    get_cancellation, get_fetch_timeout, get_per_host_concurrency, get_retry_settings,
+};
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
 use reqwest::Client;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
-static CLIENT_CACHE: Lazy<Arc<DashMap<FetchProfile, Client>>> =
+static CLIENT_CACHE: Lazy<Arc<DashMap<(FetchProfile, ClientOptions), Client>>> =
     Lazy::new(|| Arc::new(DashMap::new()));
 
 /// Last-successful fetch profile per host. Public for instrumentation only —
@@ -24,14 +25,25 @@ pub static HOST_PROFILE_CACHE: Lazy<Arc<DashMap<String, FetchProfile>>> =
     Lazy::new(|| Arc::new(DashMap::new()));
 
 /// Per-host concurrency gate. Limits how many in-flight fetches may target a
-/// single host simultaneously. One permit is acquired per URL and held through
-/// the full profile cascade, so retries don't consume extra slots.
+/// single host simultaneously. One gate per host, shared by every pipeline:
+/// each fetch takes `HOST_PERMITS / cap` of its [`HOST_PERMITS`] for its own
+/// `per_host_concurrency`, so concurrent pipelines with different caps can't
+/// add up past the strictest one's share. Held through the full profile
+/// cascade, so retries don't consume extra slots.
 pub(super) static HOST_SEMAPHORES: Lazy<DashMap<String, Arc<Semaphore>>> = Lazy::new(DashMap::new);
 
-/// Max simultaneous in-flight fetches per host. Set low enough that a
+/// Permits in each host's gate. Caps are clamped to [`MAX_PER_HOST_CAP`] so a
+/// fetch's share (rounded down) always leaves room for exactly `cap` of them.
+const HOST_PERMITS: u32 = 1 << 20;
+
+/// Largest honored `per_host_concurrency`.
+const MAX_PER_HOST_CAP: u32 = 1 << 10;
+
+/// Default max simultaneous in-flight fetches per host. Set low enough that a
 /// popular-domain burst from a search batch doesn't trip the host's rate
 /// limiter. Global concurrency (`Context::concurrency`) caps total fan-out;
-/// this caps per-host fan-out independently.
+/// this caps per-host fan-out independently (override with
+/// `Context::with_per_host_concurrency`).
 pub const PER_HOST_CONCURRENCY: usize = 8;
 
 /// Counts outgoing HTTP attempts. Used by perf tests to verify the per-pipeline
@@ -46,34 +58,325 @@ const ADAPTIVE_PROFILES: [FetchProfile; 3] = [
     FetchProfile::IOS,
 ];
 
+/// Profile selection for the Auto cascade. `Default` is the production
+/// behaviour (Minimal → Windows → IOS, resuming at the host's last-successful
+/// profile); tests can pin an explicit order and turn the host cache off so
+/// attempts are deterministic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrategyConfig {
+    /// Profiles to try, in order.
+    pub profiles: Vec<FetchProfile>,
+    /// Start at the host's last-successful profile (when it's in `profiles`)
+    /// and record the winner.
+    pub use_host_cache: bool,
+    /// Extra attempts per profile after a retryable failure (see
+    /// [`is_retryable`]) before cascading to the next profile. `0` = no retries.
+    pub max_retries: usize,
+    /// Budget shared across fetches; every retry spends one token, and once
+    /// it's empty failures cascade/fail without retrying.
+    pub retry_budget: Option<Arc<RetryBudget>>,
+}
+
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            profiles: ADAPTIVE_PROFILES.to_vec(),
+            use_host_cache: true,
+            max_retries: 0,
+            retry_budget: None,
+        }
+    }
+}
+
+impl StrategyConfig {
+    /// Pin an explicit profile order, ignoring the host cache.
+    pub fn with_profiles(profiles: impl Into<Vec<FetchProfile>>) -> Self {
+        Self {
+            profiles: profiles.into(),
+            use_host_cache: false,
+            ..Self::default()
+        }
+    }
+
+    /// Retry retryable failures up to `max_retries` times per profile.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Draw every retry from `budget`, typically shared by a whole batch.
+    pub fn with_retry_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.retry_budget = Some(budget);
+        self
+    }
+
+    /// Spend a retry token; always granted without a budget.
+    fn take_retry(&self) -> bool {
+        self.retry_budget
+            .as_ref()
+            .map_or(true, |budget| budget.try_acquire())
+    }
+
+    /// Profiles that will be attempted for `host`, in order.
+    pub fn attempt_order(&self, host: Option<&str>) -> &[FetchProfile] {
+        let starting_idx = host
+            .filter(|_| self.use_host_cache)
+            .and_then(|h| HOST_PROFILE_CACHE.get(h).map(|v| *v))
+            .and_then(|cached| self.profiles.iter().position(|p| *p == cached))
+            .unwrap_or(0);
+        &self.profiles[starting_idx..]
+    }
+
+    fn record_success(&self, host: Option<&str>, profile: FetchProfile) {
+        if let Some(h) = host.filter(|_| self.use_host_cache) {
+            HOST_PROFILE_CACHE.insert(h.to_string(), profile);
+        }
+    }
+}
+
+/// A token bucket of retries shared across fetches, so a batch against a host
+/// that is down fails fast instead of multiplying into a retry storm.
+///
+/// Holds up to `capacity` retries per `window`: each retry takes one token, and
+/// the bucket refills to `capacity` once `window` has passed since it was last
+/// refilled. Share it via `Arc` (see [`StrategyConfig::with_retry_budget`] and
+/// `Context::with_retry_budget`). Compares by identity.
+#[derive(Debug)]
+pub struct RetryBudget {
+    capacity: usize,
+    window: Duration,
+    state: Mutex<(usize, Instant)>,
+}
+
+impl RetryBudget {
+    /// A full budget of `capacity` retries per `window`.
+    pub fn new(capacity: usize, window: Duration) -> Self {
+        Self {
+            capacity,
+            window,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    /// Take one retry token; `false` when the budget is exhausted for the
+    /// current window.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (tokens, refilled_at) = &mut *state;
+        if refilled_at.elapsed() >= self.window {
+            *tokens = self.capacity;
+            *refilled_at = Instant::now();
+        }
+        if *tokens == 0 {
+            return false;
+        }
+        *tokens -= 1;
+        true
+    }
+
+    /// Tokens left in the current window (refill not applied).
+    pub fn remaining(&self) -> usize {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).0
+    }
+}
+
+impl PartialEq for RetryBudget {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for RetryBudget {}
+
+/// Backoff before retry `n` (0-based) when the server gives no `Retry-After`:
+/// 500ms, 1s, 2s, … capped at [`MAX_RETRY_DELAY`].
+const BASE_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound on any retry delay, including a server's `Retry-After`, so one
+/// hostile header can't park a fetch for hours.
+pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// How a single HTTP attempt failed, for retry classification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptFailure {
+    /// The server answered with a non-2xx status.
+    Status(reqwest::StatusCode),
+    /// The request timed out.
+    Timeout,
+    /// The connection couldn't be established or was dropped.
+    Connect,
+    /// Anything else (bad body, bot wall, decode error) — a different
+    /// profile may help, repeating the same request won't.
+    Other,
+}
+
+/// Retry policy: transient failures are retried, deterministic ones aren't.
+///
+/// - Retryable: `408`, `425`, `429`, `500`, `502`, `503`, `504`, timeouts,
+///   connection errors.
+/// - Not retryable: every other status — notably `403`, `404`, `410` (re-asking
+///   wastes time and risks a block) — and content failures.
+pub fn is_retryable(failure: &AttemptFailure) -> bool {
+    match failure {
+        AttemptFailure::Status(status) => {
+            matches!(status.as_u16(), 408 | 425 | 429 | 500 | 502 | 503 | 504)
+        }
+        AttemptFailure::Timeout | AttemptFailure::Connect => true,
+        AttemptFailure::Other => false,
+    }
+}
+
+/// Whether a failed attempt ends the whole profile cascade, not just its
+/// retries: a non-retryable status (`404`, `410`, …) is the server's answer for
+/// the URL, whichever profile asks. `403` is the exception — bot protection
+/// often blocks one profile's fingerprint and not the next — as are content
+/// failures, which are what the cascade exists for.
+fn ends_cascade(failure: &AttemptFailure) -> bool {
+    match failure {
+        AttemptFailure::Status(status) => status.as_u16() != 403 && !is_retryable(failure),
+        _ => false,
+    }
+}
+
+/// Delay before retry `retry` (0-based): the `Retry-After` seconds sent with a
+/// `429`/`503`, else exponential backoff — both capped at [`MAX_RETRY_DELAY`].
+pub fn retry_delay(
+    failure: &AttemptFailure,
+    headers: &reqwest::header::HeaderMap,
+    retry: usize,
+) -> Duration {
+    backoff_delay(retry_after(failure, headers), retry)
+}
+
+fn retry_after(failure: &AttemptFailure, headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    match failure {
+        AttemptFailure::Status(status) if matches!(status.as_u16(), 429 | 503) => headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+fn backoff_delay(retry_after: Option<Duration>, retry: usize) -> Duration {
+    let backoff = BASE_RETRY_DELAY.saturating_mul(1 << retry.min(16) as u32);
+    retry_after.unwrap_or(backoff).min(MAX_RETRY_DELAY)
+}
+
+/// A failed attempt: the error to report, its classification, and the
+/// server's `Retry-After`, if any.
+struct AttemptError {
+    error: FetchError,
+    failure: AttemptFailure,
+    retry_after: Option<Duration>,
+}
+
+/// Map a transport error to a [`FetchError`]. reqwest reports DNS and TLS
+/// failures as connect errors, so those are told apart by their source chain.
+fn fetch_error_from_reqwest(e: &reqwest::Error) -> FetchError {
+    if e.is_timeout() {
+        return FetchError::Timeout;
+    }
+    if e.is_decode() || e.is_body() {
+        return FetchError::Decode;
+    }
+    if e.is_connect() {
+        let mut chain = String::new();
+        let mut source: Option<&dyn std::error::Error> = Some(e);
+        while let Some(err) = source {
+            chain.push_str(&err.to_string().to_ascii_lowercase());
+            chain.push(' ');
+            source = err.source();
+        }
+        if chain.contains("dns error") || chain.contains("failed to lookup address") {
+            return FetchError::Dns;
+        }
+        if ["certificate", "tls", "ssl", "handshake"]
+            .iter()
+            .any(|m| chain.contains(m))
+        {
+            return FetchError::Tls;
+        }
+    }
+    FetchError::Other(format!("HTTP request failed: {}", e))
+}
+
+/// The error for a cascade where every profile failed: the shared error when
+/// all attempts agree, else a per-profile summary.
+fn cascade_error(errors: Vec<(FetchProfile, FetchError)>) -> FetchError {
+    if let Some((_, first)) = errors.first() {
+        if errors.iter().all(|(_, e)| e == first) {
+            return first.clone();
+        }
+    }
+    FetchError::Other(format!(
+        "All {} profiles failed: [{}]",
+        errors.len(),
+        errors
+            .iter()
+            .map(|(profile, e)| format!("{:?}: {}", profile, e))
+            .collect::<Vec<_>>()
+            .join("; ")
+    ))
+}
+
+fn classify_send_error(e: &reqwest::Error) -> AttemptFailure {
+    if e.is_timeout() {
+        AttemptFailure::Timeout
+    } else if e.is_connect() {
+        AttemptFailure::Connect
+    } else {
+        AttemptFailure::Other
+    }
+}
+
 fn host_from_url(url: &str) -> Option<String> {
     url::Url::parse(url)
         .ok()
         .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
 }
 
-/// Acquire a permit for this host. Returns `None` when the URL has no host
-/// (opaque, file://, etc.) — in that case we skip the cap entirely.
+/// Acquire a permit for this host, under the context's per-host cap. Returns
+/// `None` when the URL has no host (opaque, file://, etc.) — in that case we
+/// skip the cap entirely.
 pub(super) async fn acquire_host_permit(host: Option<&str>) -> Option<OwnedSemaphorePermit> {
     let host = host?;
+    let cap = u32::try_from(get_per_host_concurrency())
+        .unwrap_or(u32::MAX)
+        .clamp(1, MAX_PER_HOST_CAP);
     let sema = HOST_SEMAPHORES
         .entry(host.to_string())
-        .or_insert_with(|| Arc::new(Semaphore::new(PER_HOST_CONCURRENCY)))
+        .or_insert_with(|| Arc::new(Semaphore::new(HOST_PERMITS as usize)))
         .clone();
-    sema.acquire_owned().await.ok()
+    sema.acquire_many_owned(HOST_PERMITS / cap).await.ok()
+}
+
+/// Run `fut` unless the pipeline's cancellation token fires first (checked
+/// before `fut` is polled, so an already-cancelled pipeline sends nothing).
+async fn unless_cancelled<F: std::future::Future>(fut: F) -> Result<F::Output, FetchError> {
+    match get_cancellation() {
+        Some(token) => tokio::select! {
+            biased;
+            _ = token.cancelled() => Err(FetchError::Cancelled),
+            output = fut => Ok(output),
+        },
+        None => Ok(fut.await),
+    }
 }
 
 /// Fast: Minimal
-pub(super) async fn fetch_fast_with_client(url: &str) -> Result<FetchResult, QrawlError> {
+pub(super) async fn fetch_fast_with_client(url: &str) -> Result<FetchResult, FetchError> {
     let host = host_from_url(url);
-    let _permit = acquire_host_permit(host.as_deref()).await;
+    let _permit = unless_cancelled(acquire_host_permit(host.as_deref())).await?;
 
     let profile = FetchProfile::Minimal;
-    let client = get_or_build_client(profile, Some(&CLIENT_CACHE))?;
+    let client = get_or_build_client(profile, ClientOptions::default(), Some(&CLIENT_CACHE))?;
     let start = Instant::now();
 
-    match fetch_with_client(&client, url, profile).await {
+    match unless_cancelled(fetch_with_client(&client, url, profile)).await? {
         Ok(html) => Ok(FetchResult {
+            wall: None,
             html,
             profile_used: profile,
             duration_ms: start.elapsed().as_millis() as u64,
@@ -83,45 +386,84 @@ pub(super) async fn fetch_fast_with_client(url: &str) -> Result<FetchResult, Qra
     }
 }
 
-/// Auto: Minimal → Windows → IOS
-pub(super) async fn fetch_auto_with_client(url: &str) -> Result<FetchResult, QrawlError> {
+/// Auto: Minimal → Windows → IOS, with the pipeline context's retry settings.
+pub(super) async fn fetch_auto_with_client(url: &str) -> Result<FetchResult, FetchError> {
+    fetch_auto_with_client_options(url, &context_options()).await
+}
+
+/// [`FetchOptions`] carrying the pipeline context's retry settings — the base
+/// for per-call overrides that should otherwise behave like `fetch_auto`.
+pub(super) fn context_options() -> FetchOptions {
+    let (max_retries, retry_budget) = get_retry_settings();
+    FetchOptions {
+        strategy: StrategyConfig {
+            max_retries,
+            retry_budget,
+            ..StrategyConfig::default()
+        },
+        ..FetchOptions::default()
+    }
+}
+
+/// Auto cascade with per-call overrides applied to every attempt.
+pub(super) async fn fetch_auto_with_client_options(
+    url: &str,
+    options: &FetchOptions,
+) -> Result<FetchResult, FetchError> {
     let start = Instant::now();
     let mut all_errors = Vec::new();
 
     let host = host_from_url(url);
-    let _permit = acquire_host_permit(host.as_deref()).await;
-    let starting_idx = host
-        .as_ref()
-        .and_then(|h| HOST_PROFILE_CACHE.get(h).map(|v| *v))
-        .and_then(|cached| ADAPTIVE_PROFILES.iter().position(|p| *p == cached))
-        .unwrap_or(0);
-
-    for (offset, profile) in ADAPTIVE_PROFILES[starting_idx..].iter().enumerate() {
-        let client = get_or_build_client(*profile, Some(&CLIENT_CACHE))?;
-
-        match fetch_with_client(&client, url, *profile).await {
-            Ok(html) => {
-                if let Some(ref h) = host {
-                    HOST_PROFILE_CACHE.insert(h.clone(), *profile);
+    let _permit = unless_cancelled(acquire_host_permit(host.as_deref())).await?;
+    let profiles = options.strategy.attempt_order(host.as_deref());
+
+    let mut attempts = 0;
+
+    for profile in profiles.iter() {
+        let client = get_or_build_client(*profile, options.client, Some(&CLIENT_CACHE))?;
+
+        for retry in 0..=options.strategy.max_retries {
+            attempts += 1;
+            match unless_cancelled(send_html_attempt(
+                &client,
+                url,
+                *profile,
+                options.referer.as_deref(),
+                options.accept_language.as_deref(),
+            ))
+            .await?
+            {
+                Ok(html) => {
+                    options.strategy.record_success(host.as_deref(), *profile);
+                    return Ok(FetchResult {
+                        wall: None,
+                        html,
+                        profile_used: *profile,
+                        duration_ms: start.elapsed().as_millis() as u64,
+                        attempts,
+                    });
+                }
+                Err(e)
+                    if retry < options.strategy.max_retries
+                        && is_retryable(&e.failure)
+                        && options.strategy.take_retry() =>
+                {
+                    unless_cancelled(tokio::time::sleep(backoff_delay(e.retry_after, retry)))
+                        .await?;
+                }
+                Err(e) => {
+                    let final_status = ends_cascade(&e.failure);
+                    all_errors.push((*profile, e.error));
+                    if final_status {
+                        return Err(cascade_error(all_errors));
+                    }
+                    break;
                 }
-                return Ok(FetchResult {
-                    html,
-                    profile_used: *profile,
-                    duration_ms: start.elapsed().as_millis() as u64,
-                    attempts: offset + 1,
-                });
-            }
-            Err(e) => {
-                all_errors.push(format!("{:?}: {}", profile, e));
             }
         }
     }
 
-    Err(QrawlError::new(format!(
-        "All {} profiles failed: [{}]",
-        ADAPTIVE_PROFILES.len() - starting_idx,
-        all_errors.join("; ")
-    )))
+    Err(cascade_error(all_errors))
 }
 
 /// Fetch with client (no referer).
@@ -129,26 +471,32 @@ async fn fetch_with_client(
     client: &Client,
     url: &str,
     profile: FetchProfile,
-) -> Result<String, QrawlError> {
-    fetch_with_client_and_referer(client, url, profile, None).await
+) -> Result<String, FetchError> {
+    fetch_with_client_and_referer(client, url, profile, None, None).await
 }
 
-/// Fetch with client and optional referer header.
+/// Fetch with client and optional referer / accept-language headers.
 async fn fetch_with_client_and_referer(
     client: &Client,
     url: &str,
     profile: FetchProfile,
     referer: Option<&str>,
-) -> Result<String, QrawlError> {
-    // Build headers for this profile
-    let mut headers = headers_for_profile(profile);
-
-    // Add referer if provided
-    if let Some(ref_url) = referer {
-        if let Ok(ref_value) = reqwest::header::HeaderValue::from_str(ref_url) {
-            headers.insert(reqwest::header::REFERER, ref_value);
-        }
-    }
+    accept_language: Option<&str>,
+) -> Result<String, FetchError> {
+    send_html_attempt(client, url, profile, referer, accept_language)
+        .await
+        .map_err(|e| e.error)
+}
+
+/// One HTML attempt, keeping the failure classification for retries.
+async fn send_html_attempt(
+    client: &Client,
+    url: &str,
+    profile: FetchProfile,
+    referer: Option<&str>,
+    accept_language: Option<&str>,
+) -> Result<String, AttemptError> {
+    let headers = request_headers(profile, referer, accept_language);
 
     HTTP_ATTEMPTS.fetch_add(1, Ordering::Relaxed);
 
@@ -160,16 +508,31 @@ async fn fetch_with_client_and_referer(
         .timeout(get_fetch_timeout())
         .send()
         .await
-        .map_err(|e| QrawlError::new(format!("HTTP request failed: {}", e)))?;
+        .map_err(|e| AttemptError {
+            error: fetch_error_from_reqwest(&e),
+            failure: classify_send_error(&e),
+            retry_after: None,
+        })?;
 
     let status = response.status();
-    let body = response
-        .text()
-        .await
-        .map_err(|e| QrawlError::new(format!("Failed to read response: {}", e)))?;
+    let failure = AttemptFailure::Status(status);
+    let retry_after = retry_after(&failure, response.headers());
+    let body = response.text().await.map_err(|e| AttemptError {
+        error: FetchError::Decode,
+        failure: classify_send_error(&e),
+        retry_after: None,
+    })?;
 
     // Validate response
-    validate_response(status, &body)?;
+    validate_response(status, &body).map_err(|error| AttemptError {
+        error,
+        failure: if status.is_success() {
+            AttemptFailure::Other
+        } else {
+            failure
+        },
+        retry_after,
+    })?;
 
     Ok(body)
 }
@@ -184,14 +547,8 @@ async fn fetch_bytes_with_client_and_referer(
     url: &str,
     profile: FetchProfile,
     referer: Option<&str>,
-) -> Result<Vec<u8>, QrawlError> {
-    let mut headers = headers_for_profile(profile);
-
-    if let Some(ref_url) = referer {
-        if let Ok(ref_value) = reqwest::header::HeaderValue::from_str(ref_url) {
-            headers.insert(reqwest::header::REFERER, ref_value);
-        }
-    }
+) -> Result<Vec<u8>, FetchError> {
+    let headers = request_headers(profile, referer, None);
 
     HTTP_ATTEMPTS.fetch_add(1, Ordering::Relaxed);
 
@@ -201,17 +558,14 @@ async fn fetch_bytes_with_client_and_referer(
         .timeout(get_fetch_timeout())
         .send()
         .await
-        .map_err(|e| QrawlError::new(format!("HTTP request failed: {}", e)))?;
+        .map_err(|e| fetch_error_from_reqwest(&e))?;
 
     let status = response.status();
     if !status.is_success() {
-        return Err(QrawlError::new(format!("HTTP status {}", status.as_u16())));
+        return Err(FetchError::Status(status.as_u16()));
     }
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| QrawlError::new(format!("Failed to read response bytes: {}", e)))?;
+    let bytes = response.bytes().await.map_err(|_| FetchError::Decode)?;
 
     Ok(bytes.to_vec())
 }
@@ -220,13 +574,16 @@ async fn fetch_bytes_with_client_and_referer(
 pub(super) async fn fetch_bytes_fast_with_client(
     url: &str,
     referer: Option<&str>,
-) -> Result<Vec<u8>, QrawlError> {
+) -> Result<Vec<u8>, FetchError> {
     let host = host_from_url(url);
-    let _permit = acquire_host_permit(host.as_deref()).await;
+    let _permit = unless_cancelled(acquire_host_permit(host.as_deref())).await?;
 
     let profile = FetchProfile::Minimal;
-    let client = get_or_build_client(profile, Some(&CLIENT_CACHE))?;
-    fetch_bytes_with_client_and_referer(&client, url, profile, referer).await
+    let client = get_or_build_client(profile, ClientOptions::default(), Some(&CLIENT_CACHE))?;
+    unless_cancelled(fetch_bytes_with_client_and_referer(
+        &client, url, profile, referer,
+    ))
+    .await?
 }
 
 /// Auto bytes: same Minimal → Windows → iOS cascade as HTML fetch, with host
@@ -234,54 +591,120 @@ pub(super) async fn fetch_bytes_fast_with_client(
 pub(super) async fn fetch_bytes_auto_with_client(
     url: &str,
     referer: Option<&str>,
-) -> Result<Vec<u8>, QrawlError> {
+) -> Result<Vec<u8>, FetchError> {
     let mut all_errors = Vec::new();
 
     let host = host_from_url(url);
-    let _permit = acquire_host_permit(host.as_deref()).await;
-    let starting_idx = host
-        .as_ref()
-        .and_then(|h| HOST_PROFILE_CACHE.get(h).map(|v| *v))
-        .and_then(|cached| ADAPTIVE_PROFILES.iter().position(|p| *p == cached))
-        .unwrap_or(0);
-
-    for profile in ADAPTIVE_PROFILES[starting_idx..].iter() {
-        let client = get_or_build_client(*profile, Some(&CLIENT_CACHE))?;
-
-        match fetch_bytes_with_client_and_referer(&client, url, *profile, referer).await {
+    let _permit = unless_cancelled(acquire_host_permit(host.as_deref())).await?;
+    let strategy = StrategyConfig::default();
+    let profiles = strategy.attempt_order(host.as_deref());
+
+    for profile in profiles.iter() {
+        let client = get_or_build_client(*profile, ClientOptions::default(), Some(&CLIENT_CACHE))?;
+
+        match unless_cancelled(fetch_bytes_with_client_and_referer(
+            &client, url, *profile, referer,
+        ))
+        .await?
+        {
             Ok(bytes) => {
-                if let Some(ref h) = host {
-                    HOST_PROFILE_CACHE.insert(h.clone(), *profile);
-                }
+                strategy.record_success(host.as_deref(), *profile);
                 return Ok(bytes);
             }
-            Err(e) => all_errors.push(format!("{:?}: {}", profile, e)),
+            Err(e) => all_errors.push((*profile, e)),
         }
     }
 
-    Err(QrawlError::new(format!(
-        "All {} profiles failed: [{}]",
-        ADAPTIVE_PROFILES.len() - starting_idx,
-        all_errors.join("; ")
-    )))
+    Err(cascade_error(all_errors))
+}
+
+/// Probe: HEAD with the Minimal profile, falling back to `GET` with
+/// `Range: bytes=0-0` when HEAD errors or is refused (405/501). Bodies are
+/// never read.
+pub(super) async fn fetch_probe_with_client(url: &str) -> Result<ProbeResult, FetchError> {
+    let host = host_from_url(url);
+    let _permit = unless_cancelled(acquire_host_permit(host.as_deref())).await?;
+
+    let profile = FetchProfile::Minimal;
+    let client = get_or_build_client(profile, ClientOptions::default(), Some(&CLIENT_CACHE))?;
+
+    let head = unless_cancelled(async {
+        HTTP_ATTEMPTS.fetch_add(1, Ordering::Relaxed);
+        client
+            .head(url)
+            .headers(request_headers(profile, None, None))
+            .timeout(get_fetch_timeout())
+            .send()
+            .await
+    })
+    .await?;
+    match head {
+        Ok(response)
+            if !matches!(
+                response.status(),
+                reqwest::StatusCode::METHOD_NOT_ALLOWED | reqwest::StatusCode::NOT_IMPLEMENTED
+            ) =>
+        {
+            return Ok(probe_result(&response));
+        }
+        _ => {}
+    }
+
+    let response = unless_cancelled(async {
+        HTTP_ATTEMPTS.fetch_add(1, Ordering::Relaxed);
+        client
+            .get(url)
+            .headers(request_headers(profile, None, None))
+            .header(reqwest::header::RANGE, "bytes=0-0")
+            .timeout(get_fetch_timeout())
+            .send()
+            .await
+    })
+    .await?
+    .map_err(|e| fetch_error_from_reqwest(&e))?;
+    Ok(probe_result(&response))
+}
+
+fn probe_result(response: &reqwest::Response) -> ProbeResult {
+    let header = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+    };
+    // `Content-Range: bytes 0-0/12345` carries the full size on a ranged GET;
+    // its `Content-Length` would only be the 1-byte slice.
+    let content_length = match header(reqwest::header::CONTENT_RANGE) {
+        Some(range) => range.rsplit('/').next().and_then(|t| t.parse().ok()),
+        None => header(reqwest::header::CONTENT_LENGTH).and_then(|v| v.parse().ok()),
+    };
+    ProbeResult {
+        status: response.status().as_u16(),
+        content_type: header(reqwest::header::CONTENT_TYPE),
+        content_length,
+        final_url: response.url().to_string(),
+    }
 }
 
-/// Get or build client for profile (uses cache if available).
+/// Get or build client for profile + options (uses cache if available).
 fn get_or_build_client(
     profile: FetchProfile,
-    cache: Option<&Arc<DashMap<FetchProfile, Client>>>,
-) -> Result<Client, QrawlError> {
+    options: ClientOptions,
+    cache: Option<&Arc<DashMap<(FetchProfile, ClientOptions), Client>>>,
+) -> Result<Client, FetchError> {
+    let options = options.with_context_pool();
     if let Some(cache) = cache {
-        if let Some(client_ref) = cache.get(&profile) {
+        if let Some(client_ref) = cache.get(&(profile, options)) {
             return Ok(client_ref.clone());
         }
 
         // Not in cache, build and cache it
-        let client = build_client_for_profile(profile)?;
-        cache.insert(profile, client.clone());
+        let client = build_client_for_profile(profile, options)?;
+        cache.insert((profile, options), client.clone());
         Ok(client)
     } else {
         // No cache, just build
-        build_client_for_profile(profile)
+        Ok(build_client_for_profile(profile, options)?)
     }
 }
@@ -1,10 +1,22 @@
 #![cfg(test)]
-use crate::tools::fetch::headers::headers_for_profile;
+use crate::test_support::{html_response, mock_server};
+use crate::tools::fetch::client::{client_builder_for_profile, ClientOptions};
+use crate::tools::fetch::headers::{headers_for_profile, request_headers};
 use crate::tools::fetch::profile::FetchProfile;
-use crate::tools::fetch::strategies::{acquire_host_permit, HOST_SEMAPHORES, PER_HOST_CONCURRENCY};
-use crate::tools::fetch::utils::validate_response;
-use crate::tools::fetch::{host_matches, is_host_allowed};
+use crate::tools::fetch::strategies::{
+    acquire_host_permit, is_retryable, retry_delay, AttemptFailure, RetryBudget, StrategyConfig,
+    HOST_PROFILE_CACHE, HOST_SEMAPHORES, MAX_RETRY_DELAY, PER_HOST_CONCURRENCY,
+};
+use crate::tools::fetch::types::{FetchError, PoolConfig, WallKind};
+use crate::tools::fetch::utils::{detect_wall, is_soft_404, url_origin, validate_response};
+use crate::tools::fetch::{
+    check_domain_filter, fetch_auto, fetch_auto_insecure, fetch_auto_lang, fetch_auto_with_config,
+    fetch_auto_with_referer, fetch_auto_with_result, fetch_probe, host_matches, is_host_allowed,
+    CassetteFetcher, CassetteMode,
+};
+use crate::types::{Context, DomainPattern, CTX};
 use reqwest::StatusCode;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 fn padded_html(marker: &str) -> String {
@@ -124,6 +136,19 @@ fn validate_response_non_success_status() {
     assert!(validate_response(StatusCode::FORBIDDEN, html).is_err());
 }
 
+#[test]
+fn validate_response_status_is_typed() {
+    let html = r#"<!DOCTYPE html><html><body><h1>Page content</h1></body></html>"#;
+    assert_eq!(
+        validate_response(StatusCode::NOT_FOUND, html),
+        Err(FetchError::Status(404))
+    );
+    assert!(matches!(
+        validate_response(StatusCode::OK, html),
+        Err(FetchError::Other(_))
+    ));
+}
+
 #[test]
 fn detect_body_too_short() {
     let html = r#"<html><body>Short</body></html>"#;
@@ -180,6 +205,53 @@ fn is_host_allowed_block_beats_allow() {
     ));
 }
 
+#[test]
+fn domain_pattern_glob_matches_subdomains() {
+    let pattern = DomainPattern::glob("*.delish.com").unwrap();
+    assert!(pattern.matches("www.delish.com"));
+    assert!(pattern.matches("hips.delish.com"));
+    assert!(pattern.matches("A.B.Delish.com"));
+    assert!(!pattern.matches("delish.com"));
+    assert!(!pattern.matches("notdelish.com"));
+    assert!(!pattern.matches("delish.com.evil.com"));
+
+    let blogspot = DomainPattern::glob("*.blogspot.*").unwrap();
+    assert!(blogspot.matches("cooks.blogspot.com"));
+    assert!(blogspot.matches("cooks.blogspot.co.uk"));
+    assert!(!blogspot.matches("blogspot.example.com"));
+}
+
+#[test]
+fn domain_pattern_regex_matches_hearst_image_hosts() {
+    let pattern = DomainPattern::regex(r"^(hips|esquire-images)\.hearstapps\.com$").unwrap();
+    assert!(pattern.matches("hips.hearstapps.com"));
+    assert!(pattern.matches("esquire-images.hearstapps.com"));
+    assert!(!pattern.matches("www.hearstapps.com"));
+    assert_eq!(
+        pattern.as_str(),
+        r"^(hips|esquire-images)\.hearstapps\.com$"
+    );
+}
+
+#[test]
+fn domain_pattern_invalid_errors_at_construction() {
+    assert!(DomainPattern::regex("(unclosed").is_err());
+    assert!(DomainPattern::glob("").is_err());
+    assert!(DomainPattern::glob("bad host/*").is_err());
+}
+
+#[tokio::test]
+async fn domain_filter_blocks_pattern_matches() {
+    let ctx = Context::auto()
+        .with_block_domain_patterns(vec![DomainPattern::glob("*.hearstapps.com").unwrap()]);
+    CTX.scope(Arc::new(ctx), async {
+        let err = check_domain_filter("https://hips.hearstapps.com/x.jpg").unwrap_err();
+        assert!(err.to_string().contains("blocked by domain pattern"));
+        assert!(check_domain_filter("https://www.delish.com/recipes").is_ok());
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn host_cap_serializes_excess_callers() {
     // Reset the semaphore for this host to avoid pollution from other tests.
@@ -216,8 +288,619 @@ async fn host_cap_serializes_excess_callers() {
     );
 }
 
+#[tokio::test]
+async fn host_cap_from_context_limits_each_host_separately() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    let cap = 2;
+    let ctx = Arc::new(Context::auto().with_per_host_concurrency(cap));
+    let hosts = ["a.cap-ctx.invalid", "b.cap-ctx.invalid"];
+    let per_host: Arc<Vec<(AtomicUsize, AtomicUsize)>> = Arc::new(
+        hosts
+            .iter()
+            .map(|_| (AtomicUsize::new(0), AtomicUsize::new(0)))
+            .collect(),
+    );
+    let (in_flight, peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+
+    let mut handles = Vec::new();
+    for i in 0..12 {
+        let (ctx, per_host) = (ctx.clone(), per_host.clone());
+        let (in_flight, peak) = (in_flight.clone(), peak.clone());
+        handles.push(tokio::spawn(CTX.scope(ctx, async move {
+            let idx = i % hosts.len();
+            let _permit = acquire_host_permit(Some(hosts[idx])).await.unwrap();
+            let (host_now, host_peak) = &per_host[idx];
+            let now = host_now.fetch_add(1, Ordering::SeqCst) + 1;
+            host_peak.fetch_max(now, Ordering::SeqCst);
+            let total = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(total, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            host_now.fetch_sub(1, Ordering::SeqCst);
+        })));
+    }
+    for h in handles {
+        h.await.unwrap();
+    }
+
+    for (_, host_peak) in per_host.iter() {
+        assert_eq!(host_peak.load(Ordering::SeqCst), cap);
+    }
+    assert!(
+        peak.load(Ordering::SeqCst) > cap,
+        "different hosts should run in parallel"
+    );
+}
+
+#[tokio::test]
+async fn host_cap_is_shared_across_pipelines_with_different_caps() {
+    let host = Some("cap-shared.invalid");
+    let strict = Arc::new(Context::auto().with_per_host_concurrency(1));
+    let held = CTX.scope(strict, acquire_host_permit(host)).await.unwrap();
+
+    // A default-cap pipeline must wait behind the cap-1 one on the same host.
+    let loose = Arc::new(Context::auto());
+    let blocked = tokio::time::timeout(
+        Duration::from_millis(50),
+        CTX.scope(loose.clone(), acquire_host_permit(host)),
+    )
+    .await;
+    assert!(blocked.is_err(), "caps must not add up across pipelines");
+
+    drop(held);
+    let permits: Vec<_> = CTX
+        .scope(loose, async {
+            let mut permits = Vec::new();
+            for _ in 0..PER_HOST_CONCURRENCY {
+                permits.push(acquire_host_permit(host).await.unwrap());
+            }
+            permits
+        })
+        .await;
+    assert_eq!(permits.len(), PER_HOST_CONCURRENCY);
+}
+
 #[tokio::test]
 async fn host_cap_skipped_when_url_has_no_host() {
     let permit = acquire_host_permit(None).await;
     assert!(permit.is_none(), "no-host URLs should bypass the cap");
 }
+
+#[test]
+fn client_builder_accepts_invalid_certs_only_when_opted_in() {
+    let secure = format!(
+        "{:?}",
+        client_builder_for_profile(FetchProfile::Windows, ClientOptions::default())
+    );
+    assert!(!secure.contains("danger_accept_invalid_certs"));
+
+    let insecure = format!(
+        "{:?}",
+        client_builder_for_profile(FetchProfile::Windows, ClientOptions::INSECURE)
+    );
+    assert!(insecure.contains("danger_accept_invalid_certs: true"));
+}
+
+#[tokio::test]
+async fn client_pool_settings_come_from_context() {
+    assert_eq!(ClientOptions::default().pool, PoolConfig::DEFAULT);
+    assert_eq!(PoolConfig::DEFAULT.max_idle_per_host, 16);
+    assert_eq!(PoolConfig::DEFAULT.idle_timeout, Duration::from_secs(90));
+    assert_eq!(
+        ClientOptions::default().with_context_pool(),
+        ClientOptions::default()
+    );
+
+    let pool = PoolConfig {
+        max_idle_per_host: 128,
+        idle_timeout: Duration::from_secs(300),
+    };
+    let options = CTX
+        .scope(Arc::new(Context::auto().with_pool(pool)), async {
+            ClientOptions::INSECURE.with_context_pool()
+        })
+        .await;
+    assert_eq!(options.pool, pool);
+    assert!(options.danger_accept_invalid_certs);
+    assert_ne!(
+        options,
+        ClientOptions::INSECURE,
+        "pool is part of the cache key"
+    );
+    assert!(client_builder_for_profile(FetchProfile::Windows, options)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn accept_language_override_lands_in_built_request() {
+    let client = reqwest::Client::new();
+    let request = client
+        .get("https://example.com/")
+        .headers(request_headers(
+            FetchProfile::Minimal,
+            None,
+            Some("en-US,en;q=0.9"),
+        ))
+        .build()
+        .unwrap();
+    assert_eq!(
+        request
+            .headers()
+            .get("accept-language")
+            .and_then(|v| v.to_str().ok()),
+        Some("en-US,en;q=0.9")
+    );
+
+    // Overrides the profile default; absent override keeps it.
+    let windows = request_headers(FetchProfile::Windows, None, Some("de-DE"));
+    assert_eq!(windows.get("accept-language").unwrap(), "de-DE");
+    let default = request_headers(FetchProfile::Windows, None, None);
+    assert_eq!(default, headers_for_profile(FetchProfile::Windows));
+}
+
+#[test]
+fn same_site_referer_lands_in_built_request() {
+    let origin = url_origin("https://Images.Example.com/recipes/lasagna.jpg?w=800#top");
+    assert_eq!(origin.as_deref(), Some("https://images.example.com"));
+    assert_eq!(
+        url_origin("http://example.com:8080/a").as_deref(),
+        Some("http://example.com:8080")
+    );
+    assert_eq!(url_origin("mailto:hi@example.com"), None);
+
+    let request = reqwest::Client::new()
+        .get("https://images.example.com/recipes/lasagna.jpg")
+        .headers(request_headers(
+            FetchProfile::Windows,
+            origin.as_deref(),
+            None,
+        ))
+        .build()
+        .unwrap();
+    assert_eq!(
+        request
+            .headers()
+            .get("referer")
+            .and_then(|v| v.to_str().ok()),
+        Some("https://images.example.com")
+    );
+    assert!(headers_for_profile(FetchProfile::Windows)
+        .get("referer")
+        .is_none());
+}
+
+#[test]
+fn strategy_config_pins_profile_order() {
+    let host = "pinned-order.example";
+    HOST_PROFILE_CACHE.insert(host.to_string(), FetchProfile::Windows);
+
+    let pinned = StrategyConfig::with_profiles([FetchProfile::Android, FetchProfile::Windows]);
+    assert_eq!(
+        pinned.attempt_order(Some(host)).first(),
+        Some(&FetchProfile::Android)
+    );
+
+    // Production default resumes at the host's cached profile.
+    let default = StrategyConfig::default();
+    assert_eq!(
+        default.attempt_order(Some(host)),
+        &[FetchProfile::Windows, FetchProfile::IOS]
+    );
+    assert_eq!(default.attempt_order(None)[0], FetchProfile::Minimal);
+    HOST_PROFILE_CACHE.remove(host);
+}
+
+#[tokio::test]
+async fn fetch_probe_parses_head_response() {
+    let (base, requests) = mock_server(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: 5120\r\nConnection: close\r\n\r\n",
+    );
+
+    let probe = fetch_probe(&format!("{base}/seed")).await.unwrap();
+    assert_eq!(probe.status, 200);
+    assert_eq!(
+        probe.content_type.as_deref(),
+        Some("text/html; charset=utf-8")
+    );
+    assert_eq!(probe.content_length, Some(5120));
+    assert_eq!(probe.final_url, format!("{base}/seed"));
+    assert_eq!(requests.lines(), vec!["HEAD /seed HTTP/1.1"]);
+}
+
+#[tokio::test]
+async fn fetch_404_is_a_typed_status_error() {
+    let (base, requests) =
+        mock_server("HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    let url = format!("{base}/missing");
+    let config = StrategyConfig::with_profiles([FetchProfile::Minimal, FetchProfile::Windows])
+        .with_max_retries(2);
+
+    // A 404 is final: no retry, and no other profile asks again.
+    let err = fetch_auto_with_config(&url, &config).await.unwrap_err();
+    assert_eq!(err, FetchError::Status(404));
+    assert_eq!(err.to_string(), "HTTP status 404");
+    assert_eq!(requests.count(), 1);
+}
+
+#[tokio::test]
+async fn fetch_403_falls_through_to_next_profile() {
+    let (base, requests) =
+        mock_server("HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    let url = format!("{base}/blocked");
+    let config = StrategyConfig::with_profiles([FetchProfile::Minimal, FetchProfile::Windows])
+        .with_max_retries(2);
+
+    // Not retried, but the next profile may get past a fingerprint block.
+    let err = fetch_auto_with_config(&url, &config).await.unwrap_err();
+    assert_eq!(err, FetchError::Status(403));
+    assert_eq!(requests.count(), 2);
+}
+
+#[tokio::test]
+async fn fetch_auto_with_config_applies_domain_filters() {
+    let (base, requests) = mock_server(html_response(&padded_html("<h1>Hi</h1>")));
+    let url = format!("{base}/page");
+    let ctx =
+        Context::auto().with_block_domain_patterns(vec![DomainPattern::glob("127.0.0.*").unwrap()]);
+    let config = StrategyConfig::with_profiles([FetchProfile::Minimal]);
+
+    let err = CTX
+        .scope(Arc::new(ctx), fetch_auto_with_config(&url, &config))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("blocked by domain pattern"),
+        "{err}"
+    );
+    assert_eq!(requests.count(), 0);
+}
+
+#[tokio::test]
+async fn cassette_records_then_replays_offline() {
+    let body = padded_html("<h1>Recorded Recipe</h1>");
+    let (base, requests) = mock_server(html_response(&body));
+    let url = format!("{base}/recipes/lasagna?serves=4");
+    let dir = std::env::temp_dir().join(format!("qrawl-cassette-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let recorder = CassetteFetcher::new(&dir, CassetteMode::Record);
+    let recorded = CTX
+        .scope(Arc::new(Context::fast()), recorder.fetch(&url))
+        .await
+        .unwrap();
+    assert_eq!(recorded.as_str(), body);
+    assert_eq!(requests.count(), 1);
+    assert!(recorder.path_for(&url).exists());
+
+    // Replay is served from disk, never the server.
+    let player = CassetteFetcher::new(&dir, CassetteMode::Replay);
+    let replayed = player.fetch(&url).await.unwrap();
+    assert_eq!(replayed.as_str(), recorded.as_str());
+
+    let miss = player.fetch(&format!("{base}/never-recorded")).await;
+    assert!(miss
+        .unwrap_err()
+        .to_string()
+        .contains("no cassette recording"));
+    assert_eq!(requests.count(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn retry_policy_matrix() {
+    let retryable = [408, 425, 429, 500, 502, 503, 504];
+    let final_ = [400, 401, 403, 404, 405, 410, 451, 501];
+    for code in retryable {
+        let failure = AttemptFailure::Status(StatusCode::from_u16(code).unwrap());
+        assert!(is_retryable(&failure), "{code} should be retried");
+    }
+    for code in final_ {
+        let failure = AttemptFailure::Status(StatusCode::from_u16(code).unwrap());
+        assert!(!is_retryable(&failure), "{code} should not be retried");
+    }
+    assert!(is_retryable(&AttemptFailure::Timeout));
+    assert!(is_retryable(&AttemptFailure::Connect));
+    assert!(!is_retryable(&AttemptFailure::Other));
+}
+
+#[test]
+fn retry_delay_honors_retry_after() {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("retry-after", "5".parse().unwrap());
+    let too_many = AttemptFailure::Status(StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(retry_delay(&too_many, &headers, 0), Duration::from_secs(5));
+    let unavailable = AttemptFailure::Status(StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        retry_delay(&unavailable, &headers, 3),
+        Duration::from_secs(5)
+    );
+
+    // Without Retry-After: exponential backoff, capped.
+    let empty = reqwest::header::HeaderMap::new();
+    assert_eq!(
+        retry_delay(&too_many, &empty, 0),
+        Duration::from_millis(500)
+    );
+    assert_eq!(retry_delay(&too_many, &empty, 2), Duration::from_secs(2));
+    assert_eq!(
+        retry_delay(&AttemptFailure::Timeout, &empty, 30),
+        MAX_RETRY_DELAY
+    );
+
+    // Retry-After only applies to 429/503, and is capped too.
+    let server_error = AttemptFailure::Status(StatusCode::BAD_GATEWAY);
+    assert_eq!(
+        retry_delay(&server_error, &headers, 0),
+        Duration::from_millis(500)
+    );
+    headers.insert("retry-after", "86400".parse().unwrap());
+    assert_eq!(retry_delay(&too_many, &headers, 0), MAX_RETRY_DELAY);
+}
+
+#[test]
+fn detect_wall_onetrust_consent() {
+    let html = r#"<!DOCTYPE html><html><head>
+        <script src="https://cdn.cookielaw.org/scripttemplates/otSDKStub.js"></script>
+        </head><body>
+        <div id="onetrust-consent-sdk"><div id="onetrust-banner-sdk">
+          <p>We use cookies to personalise content and ads.</p>
+          <button id="onetrust-accept-btn-handler">Accept All Cookies</button>
+          <button>Cookie Settings</button>
+        </div></div>
+        </body></html>"#;
+    assert_eq!(detect_wall(html), Some(WallKind::ConsentWall));
+}
+
+#[test]
+fn detect_wall_cloudflare_challenge() {
+    let html = r#"<!DOCTYPE html><html><head><title>Just a moment...</title></head><body>
+        <div class="main-wrapper"><h1>example.com</h1>
+        <p>Checking your browser before accessing example.com.</p></div>
+        <script>window._cf_chl_opt={cvId: '3', cType: 'managed'};</script>
+        <script src="/cdn-cgi/challenge-platform/h/b/orchestrate/chl_page/v1"></script>
+        </body></html>"#;
+    assert_eq!(detect_wall(html), Some(WallKind::BotCheck));
+}
+
+#[test]
+fn detect_wall_ignores_cookie_banner_on_full_page() {
+    let article = "The margarita is a cocktail of tequila, triple sec and lime juice. ".repeat(20);
+    let html = format!(
+        r#"<html><body><div id="onetrust-consent-sdk"><button>Accept All Cookies</button></div>
+        <article><p>{article}</p><a href="/subscribe">Subscribe to read more recipes</a></article>
+        </body></html>"#
+    );
+    assert_eq!(detect_wall(&html), None);
+}
+
+#[test]
+fn detect_wall_ignores_challenge_scripts_on_full_page() {
+    let article = "The margarita is a cocktail of tequila, triple sec and lime juice. ".repeat(20);
+    let html = format!(
+        r#"<html><head><script src="/cdn-cgi/challenge-platform/scripts/jsd/main.js"></script>
+        <script src="https://client.perimeterx.net/PXabc/main.min.js"></script></head>
+        <body><article><p>{article}</p></article>
+        <form id="comments"><div class="g-recaptcha" data-sitekey="abc"></div></form>
+        </body></html>"#
+    );
+    assert_eq!(detect_wall(&html), None);
+}
+
+#[test]
+fn detect_wall_short_paywall() {
+    let html = r#"<html><body><h1>Premium recipe</h1>
+        <p>Subscribe to continue reading. Already a subscriber? Sign in.</p></body></html>"#;
+    assert_eq!(detect_wall(html), Some(WallKind::Paywall));
+}
+
+#[test]
+fn soft_404_page_not_found_fixture() {
+    let html = r#"<!DOCTYPE html><html><head><title>Page Not Found | Example Kitchen</title></head>
+        <body><header><nav><a href="/">Home</a><a href="/recipes">Recipes</a></nav></header>
+        <main><h1>Oops! We can't find that page.</h1>
+        <p>The recipe you're looking for may have moved.</p>
+        <form role="search" action="/search"><input type="search" name="q"></form>
+        <a href="/">Back to homepage</a></main></body></html>"#;
+    assert!(is_soft_404(html, "https://example.com/recipes/old-paloma"));
+    // A bare "not found" shell redirected to /404 with no heading still counts.
+    let bare = "<html><body><p>Nothing here.</p><a href='/'>Return to home</a></body></html>";
+    assert!(is_soft_404(bare, "https://example.com/404"));
+}
+
+#[test]
+fn soft_404_ignores_normal_articles() {
+    let article = "The margarita is a cocktail of tequila, triple sec and lime juice. ".repeat(20);
+    let html = format!(
+        r#"<html><head><title>Classic Margarita Recipe</title></head><body>
+        <form role="search"><input type="search" name="q"></form>
+        <article><h1>Classic Margarita</h1><p>{article}</p></article></body></html>"#
+    );
+    assert!(!is_soft_404(&html, "https://example.com/recipes/margarita"));
+
+    let review = format!(
+        r#"<html><head><title>Peugeot 404 review</title></head><body>
+        <article><h1>Peugeot 404: a 1960s classic</h1><p>{article}</p></article></body></html>"#
+    );
+    assert!(!is_soft_404(
+        &review,
+        "https://example.com/cars/peugeot-404"
+    ));
+
+    // A site-wide search box is no call to action on a full page.
+    let searchable = format!(
+        r#"<html><head><title>404 Cocktail Club</title></head><body>
+        <header><form role="search"><input type="search" name="s"></form></header>
+        <article><h1>Inside the 404 Cocktail Club</h1><p>{article}</p></article></body></html>"#
+    );
+    assert!(!is_soft_404(
+        &searchable,
+        "https://example.com/bars/404-cocktail-club"
+    ));
+}
+
+const ALWAYS_503: &str = "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+#[test]
+fn retry_budget_refills_after_window() {
+    let budget = RetryBudget::new(2, Duration::from_millis(20));
+    assert!(budget.try_acquire());
+    assert!(budget.try_acquire());
+    assert!(!budget.try_acquire());
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(budget.try_acquire());
+    assert_eq!(budget.remaining(), 1);
+}
+
+#[tokio::test]
+async fn retry_budget_caps_retries_across_batch() {
+    let (base, requests) = mock_server(ALWAYS_503);
+    let budget = Arc::new(RetryBudget::new(4, Duration::from_secs(60)));
+    let config = StrategyConfig::with_profiles([FetchProfile::Minimal])
+        .with_max_retries(3)
+        .with_retry_budget(budget.clone());
+
+    let urls: Vec<String> = (0..10).map(|i| format!("{base}/item/{i}")).collect();
+    let results = crate::tools::batch::batch(urls, 10, move |url| {
+        let config = config.clone();
+        async move { fetch_auto_with_config(&url, &config).await }
+    })
+    .await;
+
+    assert!(results.iter().all(Result::is_err));
+    // 10 first attempts + the 4 budgeted retries — 40 retries unbudgeted.
+    assert_eq!(requests.count(), 14);
+    assert_eq!(budget.remaining(), 0);
+}
+
+#[tokio::test]
+async fn fetch_auto_uses_context_retry_budget() {
+    let (base, requests) = mock_server(ALWAYS_503);
+    let profiles = StrategyConfig::default()
+        .attempt_order(Some("127.0.0.1"))
+        .len();
+    let ctx = Context::auto()
+        .with_max_retries(2)
+        .with_retry_budget(Arc::new(RetryBudget::new(1, Duration::from_secs(60))));
+
+    let result = CTX
+        .scope(Arc::new(ctx), fetch_auto(&format!("{base}/down")))
+        .await;
+    assert!(result.is_err());
+    // One attempt per profile, plus the single retry the budget allows.
+    assert_eq!(requests.count(), profiles + 1);
+}
+
+#[tokio::test]
+async fn fetch_auto_variants_use_context_retry_settings() {
+    let (base, requests) = mock_server(ALWAYS_503);
+    let profiles = StrategyConfig::default()
+        .attempt_order(Some("127.0.0.1"))
+        .len();
+    let ctx = Arc::new(Context::auto().with_max_retries(1));
+
+    let url = format!("{base}/down");
+    let results = CTX
+        .scope(ctx, async {
+            [
+                fetch_auto_with_referer(&url).await,
+                fetch_auto_lang(&url, "fr-FR").await,
+                fetch_auto_insecure(&url).await,
+            ]
+        })
+        .await;
+    assert!(results.iter().all(Result::is_err));
+    // Each profile retried once, as configured, on each of the three calls.
+    assert_eq!(requests.count(), 3 * 2 * profiles);
+}
+
+#[tokio::test]
+async fn cancellation_aborts_in_flight_fetch() {
+    // A server that accepts and never answers.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let held: Vec<_> = listener.incoming().take(1).collect();
+        std::thread::sleep(Duration::from_secs(10));
+        drop(held);
+    });
+
+    let token = tokio_util::sync::CancellationToken::new();
+    let ctx = Context::auto().with_cancellation(token.clone());
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        canceller.cancel();
+    });
+
+    let start = Instant::now();
+    let result = CTX
+        .scope(Arc::new(ctx.clone()), async {
+            fetch_auto_with_result(&format!("http://{addr}/slow")).await
+        })
+        .await;
+    assert_eq!(result.unwrap_err(), FetchError::Cancelled);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // Once cancelled, fetches fail before sending anything.
+    let result = CTX
+        .scope(
+            Arc::new(ctx),
+            fetch_auto_with_result("http://cancelled.invalid/"),
+        )
+        .await;
+    assert_eq!(result.unwrap_err(), FetchError::Cancelled);
+}
+
+#[tokio::test]
+async fn cancellation_stops_bytes_and_probe_fetches() {
+    use crate::tools::fetch::strategies::{
+        fetch_bytes_auto_with_client, fetch_bytes_fast_with_client, fetch_probe_with_client,
+    };
+
+    let (base, requests) = mock_server(html_response("bytes"));
+    let url = format!("{base}/image.png");
+    let token = tokio_util::sync::CancellationToken::new();
+    token.cancel();
+
+    let results = CTX
+        .scope(Arc::new(Context::auto().with_cancellation(token)), async {
+            (
+                fetch_bytes_fast_with_client(&url, None).await,
+                fetch_bytes_auto_with_client(&url, None).await,
+                fetch_probe_with_client(&url).await.map(|_| Vec::new()),
+            )
+        })
+        .await;
+    assert_eq!(results.0.unwrap_err(), FetchError::Cancelled);
+    assert_eq!(results.1.unwrap_err(), FetchError::Cancelled);
+    assert_eq!(results.2.unwrap_err(), FetchError::Cancelled);
+    assert_eq!(requests.count(), 0);
+}
+
+#[tokio::test]
+async fn fetch_auto_lang_caches_per_language() {
+    use crate::types::{fetch_cache_get, fetch_cache_new, fetch_cache_put, FETCH_CACHE};
+
+    let body = padded_html("<h1>Recette</h1>");
+    let (base, requests) = mock_server(html_response(&body));
+    let url = format!("{base}/recette");
+
+    FETCH_CACHE
+        .scope(fetch_cache_new(), async {
+            let english = padded_html("<h1>Recipe</h1>");
+            fetch_cache_put(&url, &english);
+
+            // A URL-only entry doesn't answer a French fetch...
+            let french = fetch_auto_lang(&url, "fr-FR").await.unwrap();
+            assert_eq!(french.as_str(), body);
+            // ...nor does the French body replace it.
+            assert_eq!(fetch_cache_get(&url).as_deref(), Some(english.as_str()));
+
+            // The second French fetch is served from the cache.
+            let again = fetch_auto_lang(&url, "fr-FR").await.unwrap();
+            assert_eq!(again.as_str(), body);
+        })
+        .await;
+    assert_eq!(requests.count(), 1);
+}
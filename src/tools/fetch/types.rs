@@ -1,7 +1,64 @@
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 pub use super::profile::FetchProfile;
 
+use super::client::{ClientOptions, POOL_IDLE_TIMEOUT_SEC, POOL_MAX_IDLE_PER_HOST};
+use super::strategies::StrategyConfig;
+
+/// Why a fetch failed.
+///
+/// Public fetchers that return [`QrawlError`](crate::errors::QrawlError)
+/// carry this as its message; [`fetch_auto_with_result`] and
+/// [`fetch_auto_with_config`] return it directly so callers can branch on it.
+///
+/// When every attempt of a cascade fails the same way (e.g. a 404 on every
+/// profile) that error is returned; mixed failures are summarized in
+/// [`FetchError::Other`].
+///
+/// [`fetch_auto_with_result`]: super::fetch_auto_with_result
+/// [`fetch_auto_with_config`]: super::fetch_auto_with_config
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FetchError {
+    /// The request timed out.
+    #[error("request timed out")]
+    Timeout,
+    /// The server answered with a non-2xx status.
+    #[error("HTTP status {0}")]
+    Status(u16),
+    /// The host name couldn't be resolved.
+    #[error("DNS lookup failed")]
+    Dns,
+    /// The TLS handshake or certificate check failed.
+    #[error("TLS handshake failed")]
+    Tls,
+    /// The response body exceeded the size limit.
+    #[error("response body too large")]
+    TooLarge,
+    /// The response body couldn't be read or decoded.
+    #[error("failed to decode response body")]
+    Decode,
+    /// The pipeline's cancellation token fired before the fetch finished; see
+    /// [`Context::with_cancellation`](crate::types::Context::with_cancellation).
+    #[error("fetch cancelled")]
+    Cancelled,
+    /// Anything else: connection errors, invalid or bot-walled content, blocks.
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<crate::errors::QrawlError> for FetchError {
+    fn from(error: crate::errors::QrawlError) -> Self {
+        FetchError::Other(error.message().to_string())
+    }
+}
+
+impl From<FetchError> for crate::errors::QrawlError {
+    fn from(error: FetchError) -> Self {
+        crate::errors::QrawlError::new(error.to_string())
+    }
+}
+
 /// Fetch Result
 ///
 /// Contains the fetched HTML and metadata about the fetch operation:
@@ -18,4 +75,78 @@ pub struct FetchResult {
     pub duration_ms: u64,
     /// Number of attempts before success
     pub attempts: usize,
+    /// Interstitial detected in the (otherwise valid) response; see
+    /// [`detect_wall`](super::detect_wall). Only filled in by the public
+    /// `FetchResult` entry points, `None` elsewhere.
+    #[serde(default)]
+    pub wall: Option<WallKind>,
+}
+
+/// Interstitial served in place of page content, usually with a 200 status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WallKind {
+    /// Subscription / metered-article wall.
+    Paywall,
+    /// Cookie / tracking consent wall (OneTrust, Quantcast, …) hiding the page.
+    ConsentWall,
+    /// A captcha to solve (reCAPTCHA, hCaptcha, …).
+    Captcha,
+    /// An automated bot check (Cloudflare challenge, PerimeterX, DataDome, …).
+    BotCheck,
+}
+
+/// Reachability probe result — response metadata without the body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProbeResult {
+    /// HTTP status code of the final response (after redirects).
+    pub status: u16,
+    /// `Content-Type` header, if sent.
+    pub content_type: Option<String>,
+    /// Full resource size in bytes: `Content-Length` on HEAD, the
+    /// `Content-Range` total on the ranged-GET fallback.
+    pub content_length: Option<u64>,
+    /// URL after redirects.
+    pub final_url: String,
+}
+
+/// Connection-pool settings for the fetch clients, set per pipeline with
+/// [`Context::with_pool`](crate::types::Context::with_pool).
+///
+/// Clients are cached per profile and pool settings, so every fetch under the
+/// same settings shares one pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoolConfig {
+    /// Idle keep-alive connections kept per host.
+    pub max_idle_per_host: usize,
+    /// How long an idle connection is kept before it's closed.
+    pub idle_timeout: Duration,
+}
+
+impl PoolConfig {
+    /// 16 idle connections per host, kept for 90 seconds.
+    pub const DEFAULT: Self = Self {
+        max_idle_per_host: POOL_MAX_IDLE_PER_HOST,
+        idle_timeout: Duration::from_secs(POOL_IDLE_TIMEOUT_SEC),
+    };
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Per-call fetch overrides threaded through the profile cascade. Defaults
+/// ignore the pipeline context; start from `strategies::context_options()` to
+/// reproduce plain `fetch_auto`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FetchOptions {
+    /// Client-level settings (part of the client cache key).
+    pub client: ClientOptions,
+    /// `Accept-Language` sent on every attempt, replacing the profile's own.
+    pub accept_language: Option<String>,
+    /// `Referer` sent on every attempt.
+    pub referer: Option<String>,
+    /// Profile order for the Auto cascade.
+    pub strategy: StrategyConfig,
 }
@@ -1,4 +1,5 @@
-use crate::errors::QrawlError;
+use super::types::{FetchError, WallKind};
+use crate::selectors::{H1_SELECTOR, SEARCH_SELECTOR, TITLE_SELECTOR};
 
 const MIN_BODY_LEN: usize = 500;
 
@@ -19,6 +20,184 @@ const SUSPICIOUS_PATTERNS: [&str; 9] = [
     "perimeterx",
 ];
 
+/// Automated bot-check markers: challenge scripts and interstitial copy.
+const BOT_CHECK_MARKERS: &[&str] = &[
+    "cf-browser-verification",
+    "challenge-platform",
+    "cf_chl_opt",
+    "checking your browser before accessing",
+    "blocked by cloudflare",
+    "perimeterx",
+    "captcha-delivery.com",
+    "_incapsula_resource",
+];
+
+/// Captcha widget markers.
+const CAPTCHA_MARKERS: &[&str] = &[
+    "g-recaptcha",
+    "h-captcha",
+    "hcaptcha.com/1/api.js",
+    "px-captcha",
+    "cf-captcha-container",
+    "please complete the captcha",
+    "solve this captcha",
+];
+
+/// Consent-management-platform markers (OneTrust, Quantcast, Didomi,
+/// TrustArc, Sourcepoint).
+const CONSENT_VENDOR_MARKERS: &[&str] = &[
+    "onetrust-consent-sdk",
+    "optanon",
+    "cookielaw.org",
+    "qc-cmp2",
+    "quantcast.mgr.consensu.org",
+    "didomi-host",
+    "truste_overlay",
+    "sp_message_container",
+];
+
+/// Consent calls to action.
+const CONSENT_CTAS: &[&str] = &[
+    "accept cookies",
+    "accept all cookies",
+    "accept all",
+    "cookie preferences",
+    "we value your privacy",
+];
+
+/// Paywall vendor markers (Piano / Tinypass) and calls to action.
+const PAYWALL_MARKERS: &[&str] = &[
+    "tinypass.com",
+    "piano.io",
+    "subscribe to continue",
+    "subscribe to read",
+    "subscribers only",
+    "to continue reading",
+    "already a subscriber",
+];
+
+/// Visible-word count below which a page is treated as an interstitial.
+const THIN_CONTENT_WORDS: usize = 150;
+
+/// Tags whose text isn't visible page content.
+const INVISIBLE_TAGS: &[&str] = &["script", "style", "noscript", "template"];
+
+/// Title / `<h1>` phrases of a "not found" page.
+const NOT_FOUND_TITLES: &[&str] = &[
+    "404",
+    "not found",
+    "page not found",
+    "page cannot be found",
+    "page can't be found",
+    "page doesn't exist",
+    "page does not exist",
+    "no longer available",
+];
+
+/// Final-URL path segments of a "not found" page (after an error redirect).
+const NOT_FOUND_PATHS: &[&str] = &["404", "not-found", "notfound", "page-not-found", "error"];
+
+/// "Go elsewhere" calls to action on a "not found" page.
+const NOT_FOUND_CTAS: &[&str] = &[
+    "back to home",
+    "return to home",
+    "go to home",
+    "go to our home",
+    "back to the home",
+    "return to the home",
+    "go to the home",
+    "try searching",
+    "try a search",
+    "search our site",
+    "search for what you",
+];
+
+/// Score at which [`is_soft_404`] classifies a page as not found. A matching
+/// title or heading scores 2; a not-found URL, thin content and a home/search
+/// call to action score 1 each.
+const SOFT_404_SCORE: usize = 3;
+
+pub(super) fn detect_wall(body: &str) -> Option<WallKind> {
+    let lower = body.to_ascii_lowercase();
+    let has_any = |markers: &[&str]| markers.iter().any(|m| lower.contains(m));
+
+    // Real pages embed challenge scripts, reCAPTCHA forms and bot-management
+    // tags too; only a page with little else on it is the wall itself.
+    if visible_word_count(body) >= THIN_CONTENT_WORDS {
+        return None;
+    }
+    if has_any(BOT_CHECK_MARKERS) {
+        return Some(WallKind::BotCheck);
+    }
+    if has_any(CAPTCHA_MARKERS) {
+        return Some(WallKind::Captcha);
+    }
+    if has_any(CONSENT_VENDOR_MARKERS) || has_any(CONSENT_CTAS) {
+        return Some(WallKind::ConsentWall);
+    }
+    if has_any(PAYWALL_MARKERS) {
+        return Some(WallKind::Paywall);
+    }
+    None
+}
+
+pub(super) fn is_soft_404(body: &str, final_url: &str) -> bool {
+    let doc = scraper::Html::parse_document(body);
+    let is_not_found = |text: &str| {
+        let text = text.to_lowercase();
+        NOT_FOUND_TITLES.iter().any(|phrase| text.contains(phrase))
+    };
+    let heading = doc
+        .select(&TITLE_SELECTOR)
+        .chain(doc.select(&H1_SELECTOR))
+        .any(|el| is_not_found(&el.text().collect::<String>()));
+
+    let url_signal = url::Url::parse(final_url).ok().is_some_and(|url| {
+        url.path_segments().is_some_and(|mut segments| {
+            segments.any(|segment| {
+                let stem = segment.split('.').next().unwrap_or(segment);
+                NOT_FOUND_PATHS.contains(&stem.to_ascii_lowercase().as_str())
+            })
+        })
+    });
+    let thin = visible_word_count_in(&doc) < THIN_CONTENT_WORDS;
+    let lower = body.to_lowercase();
+    // Most sites put a search box in their header, so one only reads as the
+    // page's call to action when there's little else on the page.
+    let cta = NOT_FOUND_CTAS.iter().any(|phrase| lower.contains(phrase))
+        || (thin && doc.select(&SEARCH_SELECTOR).next().is_some());
+
+    let score =
+        2 * usize::from(heading) + [url_signal, thin, cta].into_iter().filter(|s| *s).count();
+    score >= SOFT_404_SCORE
+}
+
+fn visible_word_count(body: &str) -> usize {
+    visible_word_count_in(&scraper::Html::parse_document(body))
+}
+
+fn visible_word_count_in(doc: &scraper::Html) -> usize {
+    doc.root_element()
+        .descendants()
+        .filter_map(|node| node.value().as_text().map(|text| (node, text)))
+        .filter(|(node, _)| {
+            !node.ancestors().any(|a| {
+                a.value()
+                    .as_element()
+                    .is_some_and(|el| el.name() == "head" || INVISIBLE_TAGS.contains(&el.name()))
+            })
+        })
+        .map(|(_, text)| text.split_whitespace().count())
+        .sum()
+}
+
+/// `scheme://host[:port]` of `url` — the same-site `Referer` that satisfies
+/// naive hotlink protection. `None` for unparseable or opaque-origin URLs.
+pub(super) fn url_origin(url: &str) -> Option<String> {
+    let origin = url::Url::parse(url).ok()?.origin();
+    origin.is_tuple().then(|| origin.ascii_serialization())
+}
+
 fn ensure_lower<'a>(body: &'a str, cache: &'a mut Option<String>) -> &'a str {
     if let Some(ref lower) = cache {
         lower.as_str()
@@ -69,20 +248,20 @@ fn is_suspicious_cached<'a>(body: &'a str, cache: &'a mut Option<String>) -> Opt
 pub(super) fn validate_response(
     status_code: reqwest::StatusCode,
     body: &str,
-) -> Result<(), QrawlError> {
+) -> Result<(), FetchError> {
     if !status_code.is_success() {
-        return Err(QrawlError::new(format!(
-            "HTTP status {}",
-            status_code.as_u16()
-        )));
+        return Err(FetchError::Status(status_code.as_u16()));
     }
 
     let mut body_lower_cache = None;
     if let Some(reason) = is_invalid_cached(body, &mut body_lower_cache) {
-        return Err(QrawlError::new(format!("invalid content - {}", reason)));
+        return Err(FetchError::Other(format!("invalid content - {}", reason)));
     }
     if let Some(pattern) = is_suspicious_cached(body, &mut body_lower_cache) {
-        return Err(QrawlError::new(format!("suspicious content - {}", pattern)));
+        return Err(FetchError::Other(format!(
+            "suspicious content - {}",
+            pattern
+        )));
     }
 
     Ok(())
@@ -1,13 +1,208 @@
 //! Map Tools
 
 mod tests;
-mod utils;
+pub mod types;
+pub(crate) mod utils;
+
+pub use types::{MapChildrenOptions, ParserBackend};
 
 use crate::selectors::LINK_SELECTOR;
 use crate::types::Html;
 
+/// Headings of recommendation blocks on roundup pages ("Related Recipes",
+/// "More From Delish", …), for [`MapChildrenOptions::boilerplate_headings`].
+pub const DEFAULT_BOILERPLATE_HEADINGS: &[&str] =
+    &["related", "you might also like", "more from", "trending"];
+
 /// Map URLs from HTML.
 pub async fn map_page(html: &Html, url: &str) -> Vec<String> {
+    let html = html.to_string();
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        let base = match url::Url::parse(&url) {
+            Ok(u) => u,
+            Err(_) => return Vec::new(),
+        };
+
+        let doc = scraper::Html::parse_document(&html);
+
+        doc.select(&LINK_SELECTOR)
+            .filter_map(|link| utils::resolve_page_link(&link, &base))
+            .map(|url| url.to_string())
+            .collect()
+    })
+    .await
+    .expect("map_page: spawn_blocking failed")
+}
+
+/// Map outbound URLs from HTML: only links leaving the page's host.
+///
+/// Like [`map_page`], keeping links whose canonical host (lowercase, IDNA,
+/// `www.` stripped) differs from the base URL's — so `www.example.com` counts
+/// as the same site as `example.com`, but `blog.example.com` is external.
+pub async fn map_page_external(html: &Html, url: &str) -> Vec<String> {
+    let html = html.to_string();
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        let base = match url::Url::parse(&url) {
+            Ok(u) => u,
+            Err(_) => return Vec::new(),
+        };
+
+        let doc = scraper::Html::parse_document(&html);
+
+        doc.select(&LINK_SELECTOR)
+            .filter_map(|link| utils::resolve_page_link(&link, &base))
+            .filter(|link| !utils::is_same_site(link, &base))
+            .map(|url| url.to_string())
+            .collect()
+    })
+    .await
+    .expect("map_page_external: spawn_blocking failed")
+}
+
+/// Map raw href values from HTML, exactly as authored.
+///
+/// Like [`map_page`]'s trimming (whitespace and stray quotes), but with no
+/// base resolution and no scheme filter — `/page1`, `//cdn/x` and `#top` come
+/// back unchanged, for diffing link authoring between crawls. Empty hrefs are
+/// dropped; document order, duplicates kept.
+pub async fn map_page_raw(html: &Html) -> Vec<String> {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = scraper::Html::parse_document(&html);
+
+        doc.select(&LINK_SELECTOR)
+            .filter_map(|link| link.value().attr("href"))
+            .map(utils::trim_href)
+            .filter(|href| !href.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+    .await
+    .expect("map_page_raw: spawn_blocking failed")
+}
+
+/// Map URLs from HTML with a chosen [`ParserBackend`].
+///
+/// `Html5ever` is exactly [`map_page`]. `Streaming` skips the DOM build for
+/// link-only work and resolves hrefs the same way.
+pub async fn map_page_with_backend(html: &Html, url: &str, backend: ParserBackend) -> Vec<String> {
+    if backend == ParserBackend::Html5ever {
+        return map_page(html, url).await;
+    }
+    let html = html.to_string();
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        let base = match url::Url::parse(&url) {
+            Ok(u) => u,
+            Err(_) => return Vec::new(),
+        };
+
+        utils::scan_anchor_hrefs(&html)
+            .iter()
+            .filter_map(|href| utils::resolve_page_href(href, &base))
+            .map(|url| url.to_string())
+            .collect()
+    })
+    .await
+    .expect("map_page_with_backend: spawn_blocking failed")
+}
+
+/// Map URLs from HTML, keeping only links whose scheme is in `allowed`.
+///
+/// Like [`map_page`] (which is http + https), but with a caller-provided,
+/// case-insensitive scheme allowlist — e.g. `&["https"]` for https-only, or
+/// `&["http", "https", "ftp"]`. Relative hrefs take the page's scheme.
+pub async fn map_page_with_schemes(html: &Html, url: &str, allowed: &[&str]) -> Vec<String> {
+    let html = html.to_string();
+    let url = url.to_string();
+    let allowed: Vec<String> = allowed.iter().map(|s| s.to_ascii_lowercase()).collect();
+    tokio::task::spawn_blocking(move || {
+        let base = match url::Url::parse(&url) {
+            Ok(u) => u,
+            Err(_) => return Vec::new(),
+        };
+
+        let doc = scraper::Html::parse_document(&html);
+
+        doc.select(&LINK_SELECTOR)
+            .filter_map(|link| utils::resolve_href_any_scheme(link.value().attr("href")?, &base))
+            .filter(|url| allowed.iter().any(|scheme| scheme == url.scheme()))
+            .map(|url| url.to_string())
+            .collect()
+    })
+    .await
+    .expect("map_page_with_schemes: spawn_blocking failed")
+}
+
+/// Map URLs from HTML, also reading link-bearing attributes on any element.
+///
+/// Like [`map_page`], plus values of `attrs` (e.g. `data-href`, `data-url`,
+/// `data-permalink`) on clickable non-anchor elements, resolved like hrefs.
+/// Results are in document order; an element's `href` (for `<a>`) comes before
+/// its listed attributes.
+pub async fn map_page_including_data(html: &Html, url: &str, attrs: &[&str]) -> Vec<String> {
+    let html = html.to_string();
+    let url = url.to_string();
+    let attrs: Vec<String> = attrs.iter().map(|a| a.to_string()).collect();
+    tokio::task::spawn_blocking(move || {
+        let base = match url::Url::parse(&url) {
+            Ok(u) => u,
+            Err(_) => return Vec::new(),
+        };
+
+        let doc = scraper::Html::parse_document(&html);
+
+        doc.root_element()
+            .descendants()
+            .filter_map(scraper::ElementRef::wrap)
+            .flat_map(|el| {
+                let href = (el.value().name() == "a")
+                    .then(|| el.value().attr("href"))
+                    .flatten();
+                let data = attrs.iter().filter_map(move |a| el.value().attr(a));
+                href.into_iter().chain(data).collect::<Vec<_>>()
+            })
+            .filter_map(|value| utils::resolve_page_href(value, &base))
+            .map(|url| url.to_string())
+            .collect()
+    })
+    .await
+    .expect("map_page_including_data: spawn_blocking failed")
+}
+
+/// Detect API endpoints a page loads more list items from (infinite scroll,
+/// "Load more" buttons), for when sibling detection finds only the first
+/// batch.
+///
+/// Candidates are read from inline scripts — pagination keys (`nextPage`,
+/// `next`, `loadMoreUrl`, …) in `__NEXT_DATA__`, and quoted URLs with an API
+/// path (`/api/`, `/wp-json/`, `/graphql`, …) or a paging parameter (`page=`,
+/// `offset=`, `cursor=`, …) — and from `data-*` attributes, either named like
+/// an endpoint (`data-next-url`, `data-load-more`, `data-api`) or holding
+/// such a URL. JSON-LD, external scripts and static assets are ignored.
+/// Results are resolved against `url`, deduplicated, in document order.
+pub async fn detect_list_api(html: &Html, url: &str) -> Vec<String> {
+    let html = html.to_string();
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        let Ok(base) = url::Url::parse(&url) else {
+            return Vec::new();
+        };
+        utils::list_api_candidates(&scraper::Html::parse_document(&html), &base)
+    })
+    .await
+    .expect("detect_list_api: spawn_blocking failed")
+}
+
+/// Map URLs from HTML with their anchor text, as `(url, text)` pairs.
+///
+/// Same links as [`map_page`]. Text keeps a single space wherever the source
+/// had whitespace between nodes, adds one at block-level boundaries
+/// (`<br>`, `<div>`, `<p>`, …) and is then normalized — so
+/// `<a>Spicy<span> </span>Margarita</a>` yields `"Spicy Margarita"`.
+pub async fn map_page_with_text(html: &Html, url: &str) -> Vec<(String, String)> {
     let html = html.to_string();
     let url = url.to_string();
     tokio::task::spawn_blocking(move || {
@@ -20,51 +215,72 @@ pub async fn map_page(html: &Html, url: &str) -> Vec<String> {
 
         doc.select(&LINK_SELECTOR)
             .filter_map(|link| {
-                let href = link
-                    .value()
-                    .attr("href")?
-                    .trim()
-                    .trim_matches('"')
-                    .trim_matches('\'')
-                    .trim();
-
-                // Handle protocol-relative URLs (//example.com/path)
-                let url = if href.starts_with("//") {
-                    let full_href = format!("{}:{}", base.scheme(), href);
-                    url::Url::parse(&full_href).ok()?
-                } else {
-                    url::Url::parse(href)
-                        .ok()
-                        .or_else(|| base.join(href).ok())?
-                };
-
-                // Only accept HTTP and HTTPS schemes
-                if matches!(url.scheme(), "http" | "https") {
-                    Some(url.to_string())
-                } else {
-                    None
-                }
+                let url = utils::resolve_page_link(&link, &base)?;
+                Some((url.to_string(), utils::anchor_text(&link)))
             })
             .collect()
     })
     .await
-    .expect("map_page: spawn_blocking failed")
+    .expect("map_page_with_text: spawn_blocking failed")
+}
+
+/// Score the best repeating sibling group in an HTML fragment, for tuning
+/// and debugging [`map_children`]'s group choice: between two candidate
+/// groups, the higher score is the one sibling detection picks.
+///
+/// Groups outside `<nav>`/`<header>`/`<footer>`/`<aside>` outrank ones inside,
+/// then groups inside `<main>`, then richer coverage (pattern length ×
+/// sibling count), more siblings and longer patterns. 0 when nothing repeats.
+pub fn score_sibling_group(group_html: &str) -> f64 {
+    utils::best_sibling_group_score(group_html)
 }
 
 /// Map child URLs from HTML.
 pub async fn map_children(html: &Html, url: &str) -> Vec<String> {
     let html = html.to_string();
     let url = url.to_string();
-    tokio::task::spawn_blocking(move || {
+    crate::types::spawn_blocking_in_ctx(move || {
         let doc = scraper::Html::parse_document(&html);
-        let siblings = utils::map_siblings_from_doc(&doc, &url);
-        let itemlist = utils::map_itemlist_from_doc(&doc, &url);
-        let mut result = crate::merge!(siblings, itemlist);
-        if result.is_empty() {
-            result = vec![url];
-        }
-        result
+        utils::map_children_from_doc(&doc, &url, &MapChildrenOptions::default())
     })
     .await
     .expect("map_children: spawn_blocking failed")
 }
+
+/// Map child URLs from HTML like [`map_children`], also returning the HTML
+/// the links were picked from: the detected sibling group's fragments, one
+/// per line. Empty when no sibling group was found (the URLs then come from
+/// the JSON-LD `ItemList`, or are the page itself).
+///
+/// For inspecting why a page yields the children it does.
+pub async fn map_children_debug(html: &Html, url: &str) -> (Vec<String>, String) {
+    let html = html.to_string();
+    let url = url.to_string();
+    crate::types::spawn_blocking_in_ctx(move || {
+        let doc = scraper::Html::parse_document(&html);
+        let (urls, siblings) =
+            utils::map_children_traced_from_doc(&doc, &url, &MapChildrenOptions::default());
+        (urls, siblings.join("\n"))
+    })
+    .await
+    .expect("map_children_debug: spawn_blocking failed")
+}
+
+/// Map child URLs from HTML with [`MapChildrenOptions`].
+///
+/// Default options are exactly [`map_children`].
+pub async fn map_children_with(
+    html: &Html,
+    url: &str,
+    options: &MapChildrenOptions,
+) -> Vec<String> {
+    let html = html.to_string();
+    let url = url.to_string();
+    let options = options.clone();
+    crate::types::spawn_blocking_in_ctx(move || {
+        let doc = scraper::Html::parse_document(&html);
+        utils::map_children_from_doc(&doc, &url, &options)
+    })
+    .await
+    .expect("map_children_with: spawn_blocking failed")
+}
@@ -82,6 +82,44 @@ async fn test_map_invalid_base() {
     assert_eq!(urls.len(), 0);
 }
 
+#[tokio::test]
+async fn test_map_page_with_schemes_https_only() {
+    let html = r#"<html><body>
+        <a href="https://example.com/secure">Secure</a>
+        <a href="http://example.com/plain">Plain</a>
+        <a href="/relative">Relative</a>
+    </body></html>"#;
+    let urls = map_page_with_schemes(&html.into(), "https://example.com", &["HTTPS"]).await;
+    assert_eq!(
+        urls,
+        vec!["https://example.com/secure", "https://example.com/relative"]
+    );
+}
+
+#[tokio::test]
+async fn test_map_page_with_schemes_custom_scheme() {
+    let html = r#"<html><body>
+        <a href="ftp://files.example.com/menu.pdf">Menu</a>
+        <a href="myapp://recipe/42">Open in app</a>
+        <a href="mailto:chef@example.com">Email</a>
+        <a href="https://example.com/page">Page</a>
+    </body></html>"#;
+    let urls = map_page_with_schemes(
+        &html.into(),
+        "https://example.com",
+        &["https", "ftp", "myapp"],
+    )
+    .await;
+    assert_eq!(
+        urls,
+        vec![
+            "ftp://files.example.com/menu.pdf",
+            "myapp://recipe/42",
+            "https://example.com/page",
+        ]
+    );
+}
+
 // ========== map_children tests ==========
 
 #[test]
@@ -101,6 +139,35 @@ fn test_map_body_siblings() {
     assert!(siblings[2].contains("Recipe 3"));
 }
 
+#[test]
+fn test_map_body_siblings_drops_nested_subset_group() {
+    // The inner sub-list's 3-element pattern out-scores the outer list on
+    // coverage (3 × 3 vs 6 × 1), but all its links are inside the outer list.
+    let html = r#"
+            <html><body>
+                <ul>
+                    <li><a href="/c/1">Cat 1</a><span>12 recipes</span></li>
+                    <li><a href="/c/2">Cat 2</a><span>8 recipes</span></li>
+                    <li><a href="/c/3">Cat 3</a><span>5 recipes</span></li>
+                    <li><a href="/c/4">Cat 4</a><span>9 recipes</span></li>
+                    <li><a href="/c/5">Cat 5</a><span>3 recipes</span></li>
+                    <li><a href="/c/6">Cat 6</a><span>3 recipes</span>
+                        <div>
+                            <h4><a href="/r/a">A</a></h4><p>Desc</p><span>5 min</span>
+                            <h4><a href="/r/b">B</a></h4><p>Desc</p><span>5 min</span>
+                            <h4><a href="/r/c">C</a></h4><p>Desc</p><span>5 min</span>
+                        </div>
+                    </li>
+                </ul>
+            </body></html>
+        "#;
+
+    let siblings = map_body_siblings(html);
+    assert_eq!(siblings.len(), 6);
+    assert!(siblings[0].contains("Cat 1"));
+    assert!(siblings[5].contains("/r/c"));
+}
+
 #[test]
 fn test_map_sibling_link() {
     let siblings = vec![
@@ -202,6 +269,49 @@ fn test_map_itemlist_link_full_urls() {
     assert_eq!(urls[1], "https://example.com/recipe/2");
 }
 
+#[test]
+fn test_map_itemlist_link_sorts_by_position() {
+    let html = r##"
+            <script type="application/ld+json">
+            {
+                "@type": "ItemList",
+                "itemListElement": [
+                    {"@type": "ListItem", "position": 3, "url": "https://example.com/recipe/3"},
+                    {"@type": "ListItem", "position": "1", "url": "https://example.com/recipe/1"},
+                    {"@type": "ListItem", "position": 2, "url": "https://example.com/recipe/2"}
+                ]
+            }
+            </script>
+            <script type="application/ld+json">
+            {
+                "@type": "ItemList",
+                "itemListElement": [
+                    {"@type": "ListItem", "position": 2, "url": "https://example.com/b"},
+                    {"@type": "ListItem", "position": 2, "url": "https://example.com/a"},
+                    {"@type": "ListItem", "url": "https://example.com/c"}
+                ]
+            }
+            </script>
+        "##;
+
+    let doc = Html::parse_document(html);
+    let itemlist = map_jsonld_itemlist_from_doc(&doc);
+    let urls = map_itemlist_link(&itemlist, &doc, "https://example.com");
+
+    // Second list has duplicate/missing positions, so it keeps document order.
+    assert_eq!(
+        urls,
+        vec![
+            "https://example.com/recipe/1",
+            "https://example.com/recipe/2",
+            "https://example.com/recipe/3",
+            "https://example.com/b",
+            "https://example.com/a",
+            "https://example.com/c",
+        ]
+    );
+}
+
 #[test]
 fn test_map_itemlist_link_anchors() {
     let html = r##"
@@ -351,3 +461,600 @@ async fn test_map_children_from_real_website_2() {
         urls.len()
     );
 }
+
+#[tokio::test]
+async fn test_map_page_with_text_spaces_between_nodes() {
+    let html = r#"
+            <html><body>
+                <a href="/spicy">Spicy<span> </span>Margarita</a>
+                <a href="/paloma"><div>Classic</div><div>Paloma</div></a>
+                <a href="/mule"><span>Moscow</span><span>Mule</span></a>
+                <a href="mailto:bar@example.com">Email</a>
+            </body></html>
+        "#;
+
+    let links = map_page_with_text(&html.into(), "https://example.com").await;
+    assert_eq!(
+        links,
+        vec![
+            (
+                "https://example.com/spicy".to_string(),
+                "Spicy Margarita".to_string()
+            ),
+            (
+                "https://example.com/paloma".to_string(),
+                "Classic Paloma".to_string()
+            ),
+            // Inline siblings with no source whitespace render as one word.
+            (
+                "https://example.com/mule".to_string(),
+                "MoscowMule".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_map_page_including_data_attrs() {
+    let html = r#"
+            <html><body>
+                <a href="/anchor">Anchor</a>
+                <div class="card" data-href="/margarita">Margarita</div>
+                <li data-permalink="https://other.com/paloma">Paloma</li>
+                <span data-href="javascript:void(0)">Nope</span>
+            </body></html>
+        "#;
+
+    let urls = map_page_including_data(
+        &html.into(),
+        "https://example.com",
+        &["data-href", "data-permalink"],
+    )
+    .await;
+    assert_eq!(
+        urls,
+        vec![
+            "https://example.com/anchor",
+            "https://example.com/margarita",
+            "https://other.com/paloma",
+        ]
+    );
+
+    // map_page stays anchor-only.
+    let anchors = map_page(&html.into(), "https://example.com").await;
+    assert_eq!(anchors, vec!["https://example.com/anchor"]);
+}
+
+#[tokio::test]
+async fn test_chain_map_children_honors_context_domain_filters() {
+    use crate::types::{Context, DomainPattern};
+
+    let html = r#"
+            <html><body><main><ul>
+                <li><h2><a href="https://www.delish.com/a">A</a></h2><p>One</p></li>
+                <li><h2><a href="https://example.com/b">B</a></h2><p>Two</p></li>
+                <li><h2><a href="https://food.blogspot.com/c">C</a></h2><p>Three</p></li>
+                <li><h2><a href="https://example.com/d">D</a></h2><p>Four</p></li>
+            </ul></main></body></html>
+        "#;
+    let items = vec![("https://example.com/roundup".to_string(), html.to_string())];
+
+    let ctx = std::sync::Arc::new(
+        Context::auto()
+            .with_block_domains(vec!["delish.com".to_string()])
+            .with_block_domain_patterns(vec![DomainPattern::glob("*.blogspot.*").unwrap()]),
+    );
+    let children: Vec<String> = crate::chain!(@process items, ctx, map_children)
+        .into_iter()
+        .map(|(url, _)| url)
+        .collect();
+
+    assert_eq!(
+        children,
+        vec!["https://example.com/b", "https://example.com/d"]
+    );
+}
+
+#[tokio::test]
+async fn test_map_page_backends_agree() {
+    let html = r#"<!DOCTYPE html>
+        <html><head>
+            <style>a[href="/styled"] { color: red }</style>
+            <script>document.write('<a href="/scripted">x</a>');</script>
+        </head><body>
+            <!-- <a href="/commented">hidden</a> -->
+            <nav><A HREF="/about">About</A> <a class=nav href=/contact>Contact</a></nav>
+            <p><a href='//cdn.example.com/guide.pdf' title="a > b">Guide</a></p>
+            <a href="/search?q=1&amp;page=2">Search</a>
+            <a id="x" href=" https://other.com/path " href="/second">Other</a>
+            <a>No href</a>
+            <a href="mailto:hi@example.com">Mail</a>
+            <a href="/café">Café</a>
+        </body></html>
+    "#;
+    let url = "https://example.com/blog/";
+
+    let dom = map_page_with_backend(&html.into(), url, ParserBackend::Html5ever).await;
+    let streaming = map_page_with_backend(&html.into(), url, ParserBackend::Streaming).await;
+
+    assert_eq!(dom, map_page(&html.into(), url).await);
+    assert_eq!(streaming, dom);
+    assert_eq!(
+        dom,
+        vec![
+            "https://example.com/about",
+            "https://example.com/contact",
+            "https://cdn.example.com/guide.pdf",
+            "https://example.com/search?q=1&page=2",
+            "https://other.com/path",
+            "https://example.com/caf%C3%A9",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_map_page_raw_keeps_hrefs_as_authored() {
+    let html = r##"
+        <html><body>
+            <a href="/page1">Page 1</a>
+            <a href=" //cdn/x ">CDN</a>
+            <a href="'../up'">Up</a>
+            <a href="mailto:hi@example.com">Mail</a>
+            <a href="#top">Top</a>
+            <a href="  ">Blank</a>
+        </body></html>
+    "##;
+
+    assert_eq!(
+        map_page_raw(&html.into()).await,
+        vec![
+            "/page1",
+            "//cdn/x",
+            "../up",
+            "mailto:hi@example.com",
+            "#top"
+        ]
+    );
+}
+
+#[test]
+fn test_merge_dedup_overlapping_urls() {
+    let siblings = vec![
+        "https://example.com/a".to_string(),
+        "https://example.com/b".to_string(),
+        "https://example.com/a".to_string(),
+    ];
+    let itemlist = vec![
+        "https://www.example.com/b/".to_string(),
+        "https://example.com/c".to_string(),
+        "https://example.com/a#reviews".to_string(),
+    ];
+
+    let merged = crate::merge_dedup!(siblings.clone(), itemlist.clone());
+    assert_eq!(
+        merged,
+        vec![
+            "https://example.com/a",
+            "https://example.com/b",
+            "https://example.com/c",
+        ]
+    );
+    assert_eq!(crate::merge_dedup!(merged.clone(), merged.clone()), merged);
+    assert_eq!(crate::merge!(siblings, itemlist).len(), 6);
+}
+
+#[tokio::test]
+async fn test_map_page_external_only_outbound() {
+    let html = r#"
+        <html><body>
+            <a href="/recipes/paloma">Internal</a>
+            <a href="https://www.example.com/about">www variant</a>
+            <a href="https://EXAMPLE.com/contact">Case variant</a>
+            <a href="https://partner.org/deal">External</a>
+            <a href="mailto:hi@partner.org">Mail</a>
+        </body></html>
+    "#;
+
+    assert_eq!(
+        map_page_external(&html.into(), "https://example.com/blog").await,
+        vec!["https://partner.org/deal"]
+    );
+    assert_eq!(
+        map_page(&html.into(), "https://example.com/blog")
+            .await
+            .len(),
+        4
+    );
+}
+
+#[tokio::test]
+async fn test_map_children_prefer_itemlist() {
+    let html = r#"
+        <html>
+        <head>
+            <script type="application/ld+json">
+            {
+                "@type": "ItemList",
+                "itemListElement": [
+                    {"@type": "ListItem", "position": 1, "url": "https://example.com/paloma"},
+                    {"@type": "ListItem", "position": 2, "url": "https://example.com/margarita"}
+                ]
+            }
+            </script>
+        </head>
+        <body>
+            <article>
+                <div><h3>Fish Tacos</h3><p><a href="/tacos">View</a></p></div>
+                <div><h3>Salsa Verde</h3><p><a href="/salsa">View</a></p></div>
+                <div><h3>Churros</h3><p><a href="/churros">View</a></p></div>
+            </article>
+        </body>
+        </html>
+    "#;
+    let url = "https://example.com/cocktails";
+
+    let merged = map_children(&html.into(), url).await;
+    assert!(merged.contains(&"https://example.com/salsa".to_string()));
+    assert!(merged.contains(&"https://example.com/paloma".to_string()));
+
+    let options = MapChildrenOptions {
+        prefer_itemlist: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        map_children_with(&html.into(), url, &options).await,
+        vec![
+            "https://example.com/paloma",
+            "https://example.com/margarita"
+        ]
+    );
+    assert_eq!(
+        map_children_with(&html.into(), url, &MapChildrenOptions::default()).await,
+        merged
+    );
+
+    let no_itemlist = html.replace("ItemList", "WebPage");
+    let fallback = map_children_with(&no_itemlist.clone().into(), url, &options).await;
+    assert_eq!(fallback.len(), 3);
+    assert_eq!(fallback, map_children(&no_itemlist.into(), url).await);
+}
+
+#[tokio::test]
+async fn test_map_children_match_slugs_picks_slug_matching_link() {
+    let section = |title: &str, heading_href: &str, recipe_href: &str| {
+        format!(
+            r#"<div><h3><a href="{heading_href}">{title}</a></h3><p><a href="{recipe_href}">Get the recipe</a></p></div>"#
+        )
+    };
+    let html = format!(
+        "<html><body><article>{}{}{}</article></body></html>",
+        section(
+            "Dragon's Blood Punch",
+            "/shop/punch-bowl",
+            "/recipes/dragons-blood-punch-1234"
+        ),
+        section("Witches' Brew", "/shop/cauldron", "/recipes/witches-brew"),
+        // No link clearly matches: DOM-order choice is kept.
+        section("Graveyard Dirt Cups", "/recipes/a", "/recipes/b"),
+    );
+    let url = "https://example.com/halloween";
+
+    assert_eq!(
+        map_children(&html.clone().into(), url).await,
+        vec![
+            "https://example.com/shop/punch-bowl",
+            "https://example.com/shop/cauldron",
+            "https://example.com/recipes/a",
+        ]
+    );
+    let options = MapChildrenOptions {
+        match_slugs: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        map_children_with(&html.into(), url, &options).await,
+        vec![
+            "https://example.com/recipes/dragons-blood-punch-1234",
+            "https://example.com/recipes/witches-brew",
+            "https://example.com/recipes/a",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_map_children_sibling_unwrap_depth_finds_wrapped_cards() {
+    let card = |slug: &str| {
+        format!(
+            r#"<div class="card-wrap"><div class="card-inner"><article><h3><a href="/recipes/{slug}">{slug}</a></h3><p>A cocktail.</p><a href="/recipes/{slug}">Get the recipe</a></article></div></div>"#
+        )
+    };
+    let html = format!(
+        r#"<html><body><main><div class="grid">{}{}{}{}</div></main></body></html>"#,
+        card("paloma"),
+        card("margarita"),
+        card("negroni"),
+        card("daiquiri"),
+    );
+    let url = "https://example.com/roundup";
+
+    // The wrappers each have one child, so nothing repeats at depth 0.
+    assert_eq!(map_children(&html.clone().into(), url).await, vec![url]);
+    let depth = |sibling_unwrap_depth| MapChildrenOptions {
+        sibling_unwrap_depth,
+        ..Default::default()
+    };
+    assert_eq!(
+        map_children_with(&html.clone().into(), url, &depth(1)).await,
+        vec![url]
+    );
+    assert_eq!(
+        map_children_with(&html.into(), url, &depth(2)).await,
+        vec![
+            "https://example.com/recipes/paloma",
+            "https://example.com/recipes/margarita",
+            "https://example.com/recipes/negroni",
+            "https://example.com/recipes/daiquiri",
+        ]
+    );
+}
+
+#[test]
+fn test_score_sibling_group_ranks_content_above_nav() {
+    let cards = |n: usize| {
+        (0..n)
+            .map(|i| {
+                format!(
+                    r#"<div><h3><a href="/recipes/{i}">Recipe {i}</a></h3><p>Text</p><a href="/recipes/{i}#rate">Rate</a></div>"#
+                )
+            })
+            .collect::<String>()
+    };
+    let nav = r#"<nav><ul>
+        <li><a href="/">Home</a><span>|</span></li>
+        <li><a href="/about">About</a><span>|</span></li>
+        <li><a href="/shop">Shop</a><span>|</span></li>
+    </ul></nav>"#;
+
+    let small_nav = score_sibling_group(nav);
+    let few_cards = score_sibling_group(&format!("<section>{}</section>", cards(3)));
+    let many_cards = score_sibling_group(&format!("<section>{}</section>", cards(8)));
+    let main_cards = score_sibling_group(&format!("<main>{}</main>", cards(3)));
+
+    assert!(small_nav > 0.0);
+    assert!(few_cards > small_nav, "{few_cards} vs {small_nav}");
+    assert!(many_cards > few_cards, "{many_cards} vs {few_cards}");
+    assert!(main_cards > many_cards, "{main_cards} vs {many_cards}");
+    assert_eq!(score_sibling_group("<p>No repeats</p>"), 0.0);
+}
+
+#[tokio::test]
+async fn test_map_children_drops_boilerplate_heading_groups() {
+    let cards = |slugs: &[&str]| {
+        slugs
+            .iter()
+            .map(|slug| {
+                format!(
+                    r#"<div class="card"><h3><a href="/{slug}">{slug}</a></h3><p>A cocktail.</p></div>"#
+                )
+            })
+            .collect::<String>()
+    };
+    let html = format!(
+        r#"<html><body><main>
+            <h1>Best Tequila Cocktails</h1>
+            <div class="list">{}</div>
+            <section><h2>Related <em>Recipes</em></h2><div class="list">{}</div></section>
+        </main></body></html>"#,
+        cards(&["paloma", "margarita", "batanga"]),
+        cards(&["mojito", "negroni", "spritz", "daiquiri", "gimlet"]),
+    );
+    let html: crate::types::Html = html.into();
+    let url = "https://example.com/tequila";
+    let main_list = vec![
+        "https://example.com/paloma",
+        "https://example.com/margarita",
+        "https://example.com/batanga",
+    ];
+
+    // The larger "Related" block wins by default.
+    assert_eq!(
+        map_children(&html, url).await[0],
+        "https://example.com/mojito"
+    );
+
+    let options = MapChildrenOptions {
+        boilerplate_headings: DEFAULT_BOILERPLATE_HEADINGS
+            .iter()
+            .map(|phrase| phrase.to_string())
+            .collect(),
+        ..Default::default()
+    };
+    assert_eq!(map_children_with(&html, url, &options).await, main_list);
+
+    // Phrases match whole words at the start of the heading only.
+    let options = MapChildrenOptions {
+        boilerplate_headings: vec!["relat".to_string(), "recipes".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        map_children_with(&html, url, &options).await[0],
+        "https://example.com/mojito"
+    );
+}
+
+#[test]
+fn test_collect_text_joins_inline_and_breaks_blocks() {
+    let text_of = |html: &str, policy: &TextJoinPolicy| {
+        let fragment = Html::parse_fragment(html);
+        let link = fragment
+            .select(&scraper::Selector::parse("a").unwrap())
+            .next()
+            .unwrap();
+        collect_text(&link, policy)
+    };
+
+    let lines = TextJoinPolicy {
+        block_separator: "\n",
+    };
+    let br = r#"<a href="/spicy">Spicy<br>Margarita</a>"#;
+    let bold = r#"<a href="/spicy">Spicy<b>Margarita</b></a>"#;
+    assert_eq!(text_of(br, &lines), "Spicy\nMargarita");
+    assert_eq!(text_of(br, &TextJoinPolicy::SPACED), "Spicy Margarita");
+    assert_eq!(text_of(bold, &lines), "SpicyMargarita");
+    assert_eq!(
+        text_of(
+            r#"<a href="/x"><p>One</p><p>Two<script>x()</script></p></a>"#,
+            &lines
+        ),
+        "\nOne\n\nTwo\n"
+    );
+
+    let fragment = Html::parse_fragment(br);
+    let link = fragment
+        .select(&scraper::Selector::parse("a").unwrap())
+        .next()
+        .unwrap();
+    assert_eq!(anchor_text(&link), "Spicy Margarita");
+}
+
+#[tokio::test]
+async fn test_detect_list_api_finds_next_data_pagination() {
+    let html = r#"<html><head>
+        <script src="/_next/static/chunks/main.js"></script>
+        <script type="application/ld+json">{"url": "https://example.com/roundup?page=1"}</script>
+    </head><body>
+        <div class="grid">
+            <img data-src="/images/paloma.jpg?width=400">
+            <button data-load-more-url="/collections/tequila/more">Load more</button>
+            <div data-config='{"theme": "/themes/dark.css"}'></div>
+        </div>
+        <script id="__NEXT_DATA__" type="application/json">
+            {"props": {"pageProps": {"pagination": {
+                "page": 1,
+                "nextPage": "/roundups/tequila-cocktails/2",
+                "next": null
+            }, "assetPrefix": "/_next/static/css/app.css"}}}
+        </script>
+        <script>
+            window.feed = {endpoint: "https:\/\/example.com\/wp-json\/wp\/v2\/posts?per_page=10"};
+            var legacy = '/collections/tequila?page=2';
+        </script>
+    </body></html>"#;
+
+    assert_eq!(
+        detect_list_api(
+            &html.into(),
+            "https://example.com/roundups/tequila-cocktails"
+        )
+        .await,
+        vec![
+            "https://example.com/collections/tequila/more",
+            "https://example.com/roundups/tequila-cocktails/2",
+            "https://example.com/wp-json/wp/v2/posts?per_page=10",
+            "https://example.com/collections/tequila?page=2",
+        ]
+    );
+    assert!(
+        detect_list_api(&"<p>Static page</p>".into(), "https://example.com/")
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_map_children_debug_returns_sibling_html() {
+    let html: crate::types::Html = r#"<html><body><main><ul>
+        <li><a href="/paloma">Paloma</a><p>A cocktail.</p></li>
+        <li><a href="/margarita">Margarita</a><p>A cocktail.</p></li>
+        <li><a href="/batanga">Batanga</a><p>A cocktail.</p></li>
+    </ul></main></body></html>"#
+        .into();
+    let url = "https://example.com/tequila";
+
+    let (urls, siblings) = map_children_debug(&html, url).await;
+    assert_eq!(urls, map_children(&html, url).await);
+    assert_eq!(urls.len(), 3);
+    assert!(!siblings.is_empty());
+    assert_eq!(siblings.lines().count(), 3);
+    assert!(siblings.starts_with(r#"<li><a href="/paloma">Paloma</a><p>A cocktail.</p></li>"#));
+
+    // Nothing repeats: no sibling HTML, the page itself as the only child.
+    let single: crate::types::Html = "<p>Just one <a href=\"/x\">link</a></p>".into();
+    assert_eq!(
+        map_children_debug(&single, url).await,
+        (vec![url.to_string()], String::new())
+    );
+}
+
+#[test]
+fn test_map_children_merges_same_link_siblings() {
+    let card = |slug: &str| {
+        format!(
+            r#"<div><a href="/{slug}"><img src="/{slug}.jpg" alt=""></a><span>Photo</span></div><div><a href="https://example.com/{slug}">{slug}</a><span>By Ana</span><p>A cocktail.</p></div>"#
+        )
+    };
+    let html = format!(
+        r#"<html><body><main><div class="grid">{}{}{}</div></main></body></html>"#,
+        card("paloma"),
+        card("margarita"),
+        card("batanga"),
+    );
+    let doc = Html::parse_document(&html);
+    let url = "https://example.com/tequila";
+    let children = vec![
+        "https://example.com/paloma",
+        "https://example.com/margarita",
+        "https://example.com/batanga",
+    ];
+
+    let (urls, siblings) = map_children_traced_from_doc(&doc, url, &MapChildrenOptions::default());
+    assert_eq!(urls, children);
+    assert_eq!(siblings.len(), 3);
+    assert!(siblings[0].contains("paloma.jpg") && siblings[0].contains("By Ana"));
+
+    let keep = MapChildrenOptions {
+        keep_same_link_siblings: true,
+        ..Default::default()
+    };
+    let (urls, siblings) = map_children_traced_from_doc(&doc, url, &keep);
+    assert_eq!(urls, children);
+    assert_eq!(siblings.len(), 6);
+}
+
+#[tokio::test]
+async fn test_map_children_skips_blocked_domain_links_in_siblings() {
+    use crate::types::{Context, DomainPattern, CTX};
+    use std::sync::Arc;
+
+    let cards = ["paloma", "margarita", "batanga"]
+        .iter()
+        .map(|slug| {
+            format!(
+                r#"<div class="card"><a href="https://hips.hearstapps.com/{slug}.jpg">Photo</a><a href="/{slug}">{slug}</a><p>A cocktail.</p></div>"#
+            )
+        })
+        .collect::<String>();
+    let html: crate::types::Html =
+        format!(r#"<html><body><main><div class="list">{cards}</div></main></body></html>"#).into();
+    let url = "https://example.com/tequila";
+
+    // Outside a pipeline the first link in each card is the CDN image.
+    assert_eq!(
+        map_children(&html, url).await[0],
+        "https://hips.hearstapps.com/paloma.jpg"
+    );
+
+    let ctx = Context::auto()
+        .with_block_domain_patterns(vec![DomainPattern::glob("*.hearstapps.com").unwrap()]);
+    let children = CTX
+        .scope(Arc::new(ctx), async { map_children(&html, url).await })
+        .await;
+    assert_eq!(
+        children,
+        vec![
+            "https://example.com/paloma",
+            "https://example.com/margarita",
+            "https://example.com/batanga",
+        ]
+    );
+}
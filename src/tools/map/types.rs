@@ -0,0 +1,42 @@
+/// HTML parser behind [`map_page_with_backend`](super::map_page_with_backend).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParserBackend {
+    /// Full html5ever DOM build (`scraper`) — spec-correct, the default.
+    #[default]
+    Html5ever,
+    /// Single-pass tag scanner that only looks at `<a href>`. Skips comments,
+    /// `<script>` and `<style>`, but does no tree building, so markup that
+    /// relies on parser recovery (e.g. `<a>` inside `<template>`, stray `<`
+    /// in attribute values) can differ from `Html5ever`.
+    Streaming,
+}
+
+/// Options for [`map_children_with`](super::map_children_with). Defaults
+/// reproduce plain `map_children`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MapChildrenOptions {
+    /// Use JSON-LD `ItemList` links alone when there are any, falling back to
+    /// DOM sibling links only when the itemlist is empty. Off by default:
+    /// both sources are merged.
+    pub prefer_itemlist: bool,
+    /// When a sibling section has several candidate links, pick the one whose
+    /// URL slug best matches the section heading ("Dragon's Blood Punch" →
+    /// `/dragons-blood-punch-123`), falling back to the DOM-order choice when
+    /// no link clearly wins. Off by default.
+    pub match_slugs: bool,
+    /// Single-child wrapper levels to look through when comparing sibling
+    /// structure, for sites that wrap each card in extra `<div>`s so the
+    /// wrappers all look alike (`div > div > article`). 0 (the default)
+    /// compares the siblings' own children.
+    pub sibling_unwrap_depth: usize,
+    /// Skip sibling groups whose nearest preceding heading starts with one of
+    /// these phrases (case-insensitive) — trailing "Related Recipes" / "You
+    /// Might Also Like" blocks that would otherwise pass for the list. Start
+    /// from [`DEFAULT_BOILERPLATE_HEADINGS`](super::DEFAULT_BOILERPLATE_HEADINGS) and extend as needed; empty (the
+    /// default) keeps every group.
+    pub boilerplate_headings: Vec<String>,
+    /// Keep consecutive siblings that link to the same URL as separate
+    /// fragments. Off by default: they're merged into one, so a card split
+    /// into an image sibling and a title sibling counts as one child.
+    pub keep_same_link_siblings: bool,
+}
@@ -1,7 +1,12 @@
+use super::types::MapChildrenOptions;
 use crate::selectors::{JSONLD_SELECTOR, LINK_SELECTOR};
 use crate::tools::normalize::utils::normalize_domain;
+use crate::types::ctx_allows_domain;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use scraper::{ElementRef, Html, Selector};
 use serde_json::Value;
+use std::collections::BTreeSet;
 use url::Url;
 
 /// Minimum number of siblings required to form a valid group.
@@ -22,12 +27,22 @@ const MAX_PATTERN_RATIO: usize = 2;
 /// HTML tag name for main content elements.
 const MAIN_TAG: &str = "main";
 
+/// Share of a heading's words a link slug must contain for `match_slugs` to
+/// pick it.
+const MIN_SLUG_SIMILARITY: f64 = 0.5;
+
 /// HTML tags to exclude from pattern detection (non-content elements).
 const JUNK_TAGS: &[&str] = &["script", "style", "iframe", "noscript"];
 
 /// HTML tags that indicate navigation/non-main-content (should be deprioritized).
 const NAV_TAGS: &[&str] = &["nav", "footer", "aside", "header"];
 
+/// Elements whose boundaries separate words even without source whitespace.
+const BLOCK_TAGS: &[&str] = &[
+    "article", "br", "dd", "div", "dl", "dt", "figure", "footer", "h1", "h2", "h3", "h4", "h5",
+    "h6", "header", "hr", "img", "li", "ol", "p", "section", "td", "th", "tr", "ul",
+];
+
 /// Structure pattern for sibling detection.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct StructurePattern {
@@ -48,6 +63,8 @@ struct SiblingGroup {
     pattern_len: usize,
     /// The actual HTML fragments of the siblings.
     siblings: Vec<String>,
+    /// Every (cleaned) href inside the siblings, for subset deduplication.
+    links: BTreeSet<String>,
 }
 
 impl SiblingGroup {
@@ -60,6 +77,59 @@ impl SiblingGroup {
     fn quantity(&self) -> usize {
         self.siblings.len()
     }
+
+    /// Selection rank, compared lexicographically: see `map_body_siblings`'
+    /// scoring hierarchy.
+    fn rank(&self) -> (bool, bool, usize, usize, usize) {
+        (
+            !self.in_navigation, // Exclude navigation/footer first
+            self.in_main,        // Prefer <main> content
+            self.coverage(),     // Prefer richer patterns (pattern_len × quantity)
+            self.quantity(),     // Prefer more siblings
+            self.pattern_len,    // Prefer longer patterns
+        )
+    }
+
+    /// [`rank`](Self::rank) flattened to one number: each key a base-1000
+    /// digit, capped at 999, so scores order like ranks for groups under
+    /// 1000 siblings.
+    fn score(&self) -> f64 {
+        let (not_nav, in_main, coverage, quantity, pattern_len) = self.rank();
+        [
+            usize::from(not_nav),
+            usize::from(in_main),
+            coverage,
+            quantity,
+            pattern_len,
+        ]
+        .into_iter()
+        .fold(0.0, |score, key| score * 1000.0 + key.min(999) as f64)
+    }
+
+    /// Whether this group's links are all inside a larger group's — e.g. a
+    /// nested sub-list detected alongside the list containing it. The other
+    /// group must also have more siblings (so a handful of page-wrapper
+    /// sections never swallow the real list) and be no more navigational.
+    fn is_subsumed_by(&self, other: &SiblingGroup) -> bool {
+        !self.links.is_empty()
+            && other.quantity() > self.quantity()
+            && (self.in_navigation || !other.in_navigation)
+            && self.links.len() < other.links.len()
+            && self.links.is_subset(&other.links)
+    }
+}
+
+/// Collect the cleaned hrefs of all links under `elements`.
+fn collect_group_links<'a>(
+    elements: impl IntoIterator<Item = &'a ElementRef<'a>>,
+) -> BTreeSet<String> {
+    elements
+        .into_iter()
+        .flat_map(|el| el.select(&LINK_SELECTOR))
+        .filter_map(|a| a.value().attr("href"))
+        .map(clean_href)
+        .filter(|href| !href.is_empty())
+        .collect()
 }
 
 /// Map child URLs from HTML siblings.
@@ -72,12 +142,6 @@ pub(super) fn map_siblings(html: &str, url: &str) -> Vec<String> {
     map_sibling_link(&siblings, url)
 }
 
-/// Map child URLs from HTML siblings using a pre-parsed document.
-pub(super) fn map_siblings_from_doc(doc: &Html, url: &str) -> Vec<String> {
-    let siblings = map_body_siblings_from_doc(doc);
-    map_sibling_link(&siblings, url)
-}
-
 /// Map child URLs from JSON-LD ItemList.
 ///
 /// Extracts ItemList from JSON-LD and resolves URLs (including anchor references).
@@ -94,6 +158,69 @@ pub(super) fn map_itemlist_from_doc(doc: &Html, url: &str) -> Vec<String> {
     map_itemlist_link(&itemlist, doc, url)
 }
 
+/// Map child URLs from siblings and ItemList using a pre-parsed document.
+///
+/// Falls back to the page URL itself when neither source yields links.
+pub(super) fn map_children_from_doc(
+    doc: &Html,
+    url: &str,
+    options: &MapChildrenOptions,
+) -> Vec<String> {
+    map_children_traced_from_doc(doc, url, options).0
+}
+
+/// [`map_children_from_doc`], also returning the sibling fragments the links
+/// were picked from — empty when no group was detected, or when
+/// `prefer_itemlist` skipped detection.
+pub(super) fn map_children_traced_from_doc(
+    doc: &Html,
+    url: &str,
+    options: &MapChildrenOptions,
+) -> (Vec<String>, Vec<String>) {
+    let itemlist = map_itemlist_from_doc(doc, url);
+    let (mut result, siblings) = if options.prefer_itemlist && !itemlist.is_empty() {
+        (crate::merge_dedup!(itemlist), Vec::new())
+    } else {
+        let siblings = detected_siblings(doc, url, options);
+        let links = map_sibling_link_with(&siblings, url, options);
+        (crate::merge_dedup!(links, itemlist), siblings)
+    };
+    if result.is_empty() {
+        result = vec![url.to_string()];
+    }
+    (result, siblings)
+}
+
+/// The detected sibling fragments for `url`, with runs of same-link siblings
+/// merged unless `options.keep_same_link_siblings` (see
+/// [`merge_same_link_siblings`]).
+fn detected_siblings(doc: &Html, url: &str, options: &MapChildrenOptions) -> Vec<String> {
+    let siblings = map_body_siblings_from_doc(doc, options);
+    match parse_base_url(url) {
+        Some(base) if !options.keep_same_link_siblings => {
+            merge_same_link_siblings(siblings, &base, options.match_slugs)
+        }
+        _ => siblings,
+    }
+}
+
+/// Join each run of consecutive siblings whose primary link (the one
+/// `map_sibling_link_with` would pick) is the same URL into one fragment.
+/// Siblings without a link are never merged.
+fn merge_same_link_siblings(siblings: Vec<String>, base: &Url, match_slugs: bool) -> Vec<String> {
+    let mut merged: Vec<(Option<String>, String)> = Vec::new();
+    for html in siblings {
+        let link = select_primary_link_in_document(&Html::parse_fragment(&html), base, match_slugs);
+        match merged.last_mut() {
+            Some((Some(previous), fragment)) if link.as_ref() == Some(previous) => {
+                fragment.push_str(&html)
+            }
+            _ => merged.push((link, html)),
+        }
+    }
+    merged.into_iter().map(|(_, html)| html).collect()
+}
+
 /// Map body content to sibling HTML fragments.
 ///
 /// Detects repeating sibling patterns in HTML structure by scanning
@@ -103,8 +230,10 @@ pub(super) fn map_itemlist_from_doc(doc: &Html, url: &str) -> Vec<String> {
 ///
 /// 1. **Scan**: Recursively traverse DOM to find all groups of repeating siblings
 /// 2. **Filter**: Apply domain filters to each group (removes blocked URLs)
-/// 3. **Score**: Rank groups using hierarchical criteria (see below)
-/// 4. **Select**: Return the highest-scoring group
+/// 3. **Dedupe**: Drop groups whose links are a strict subset of another
+///    group's (a nested sub-list inside the list that contains it)
+/// 4. **Score**: Rank groups using hierarchical criteria (see below)
+/// 5. **Select**: Return the highest-scoring group
 ///
 /// # Scoring Hierarchy (highest to lowest priority)
 ///
@@ -116,35 +245,60 @@ pub(super) fn map_itemlist_from_doc(doc: &Html, url: &str) -> Vec<String> {
 ///
 /// # Domain Filtering
 ///
-/// Domain filtering happens during detection to affect group selection.
-/// Groups with only blocked domains are excluded before scoring.
+/// Each sibling's link is picked among those passing the pipeline context's
+/// domain filters ([`Context::allows_domain`](crate::types::Context::allows_domain)),
+/// so a blocked share or CDN link never stands in for the child.
 ///
 #[cfg(test)]
 pub(super) fn map_body_siblings(html: &str) -> Vec<String> {
     let doc = Html::parse_document(html);
-    map_body_siblings_from_doc(&doc)
+    map_body_siblings_from_doc(&doc, &MapChildrenOptions::default())
 }
 
 /// Same as `map_body_siblings` but reuses an already-parsed document.
-pub(super) fn map_body_siblings_from_doc(doc: &Html) -> Vec<String> {
+///
+/// Before a sibling's structure is compared, up to
+/// `options.sibling_unwrap_depth` single-child wrapper elements are looked
+/// through (see [`unwrap_single_child`]), so deeply wrapped cards still match.
+/// Groups under an `options.boilerplate_headings` heading are skipped (see
+/// [`is_boilerplate_group`]).
+pub(super) fn map_body_siblings_from_doc(doc: &Html, options: &MapChildrenOptions) -> Vec<String> {
+    best_sibling_group(doc, options)
+        .map(|group| group.siblings)
+        .unwrap_or_default()
+}
+
+/// Score of the best sibling group in `html` (see [`SiblingGroup::score`]);
+/// 0 when nothing repeats.
+pub(super) fn best_sibling_group_score(html: &str) -> f64 {
+    best_sibling_group(&Html::parse_fragment(html), &MapChildrenOptions::default())
+        .map_or(0.0, |group| group.score())
+}
+
+/// The highest-ranked sibling group in `doc`, after subset deduplication.
+fn best_sibling_group(doc: &Html, options: &MapChildrenOptions) -> Option<SiblingGroup> {
     let root = doc.root_element();
 
     // Scan entire tree and find ALL sibling groups at ALL levels
     let mut all_sibling_groups: Vec<SiblingGroup> = Vec::new();
-    map_sibling_groups_recursive(&root, &mut all_sibling_groups);
+    map_sibling_groups_recursive(&root, options, &mut all_sibling_groups);
 
-    // Select best group using scoring hierarchy
-    let selected = all_sibling_groups.into_iter().max_by_key(|group| {
-        (
-            !group.in_navigation, // Exclude navigation/footer first
-            group.in_main,        // Prefer <main> content
-            group.coverage(),     // Prefer richer patterns (pattern_len × quantity)
-            group.quantity(),     // Prefer more siblings
-            group.pattern_len,    // Prefer longer patterns
-        )
-    });
+    // Drop groups subsumed by a larger overlapping group
+    let subsumed: Vec<bool> = all_sibling_groups
+        .iter()
+        .map(|group| {
+            all_sibling_groups
+                .iter()
+                .any(|other| group.is_subsumed_by(other))
+        })
+        .collect();
+    let mut subsumed = subsumed.into_iter();
+    all_sibling_groups.retain(|_| !subsumed.next().unwrap_or(false));
 
-    selected.map(|group| group.siblings).unwrap_or_default()
+    // Select best group using scoring hierarchy
+    all_sibling_groups
+        .into_iter()
+        .max_by_key(SiblingGroup::rank)
 }
 
 /// Clean href by stripping escape sequences, quotes, and whitespace.
@@ -163,6 +317,219 @@ fn clean_href(href: &str) -> String {
         .to_string()
 }
 
+/// Resolve an anchor's href against the page URL for `map_page`.
+pub(super) fn resolve_page_link(link: &ElementRef, base: &Url) -> Option<Url> {
+    resolve_page_href(link.value().attr("href")?, base)
+}
+
+/// Resolve a raw link value like `map_page` does: strips surrounding quotes,
+/// handles protocol-relative hrefs, keeps http(s) only.
+pub(super) fn resolve_page_href(href: &str, base: &Url) -> Option<Url> {
+    resolve_href_any_scheme(href, base).filter(is_valid_scheme)
+}
+
+/// [`resolve_page_href`] without the scheme check.
+pub(super) fn resolve_href_any_scheme(href: &str, base: &Url) -> Option<Url> {
+    let href = trim_href(href);
+
+    // Handle protocol-relative URLs (//example.com/path)
+    if href.starts_with("//") {
+        let full_href = format!("{}:{}", base.scheme(), href);
+        Url::parse(&full_href).ok()
+    } else {
+        Url::parse(href).ok().or_else(|| base.join(href).ok())
+    }
+}
+
+/// Raw-text elements whose content is never markup.
+const RAW_TEXT_TAGS: &[&str] = &["script", "style"];
+
+/// `href` values of `<a>` tags in document order, found by a single pass over
+/// the source instead of a DOM build ([`ParserBackend::Streaming`]). Entities
+/// are decoded; the first `href` on a tag wins, as in html5ever.
+///
+/// [`ParserBackend::Streaming`]: super::types::ParserBackend::Streaming
+pub(super) fn scan_anchor_hrefs(html: &str) -> Vec<String> {
+    let bytes = html.as_bytes();
+    let mut hrefs = Vec::new();
+    let mut i = 0;
+
+    while let Some(offset) = html[i..].find('<') {
+        i += offset;
+        let rest = &html[i..];
+        if rest.starts_with("<!--") {
+            i = rest.find("-->").map_or(html.len(), |end| i + end + 3);
+            continue;
+        }
+        if let Some(tag) = RAW_TEXT_TAGS
+            .iter()
+            .find(|tag| starts_tag(bytes, i + 1, tag))
+        {
+            let close = format!("</{tag}");
+            i = find_ascii_ci(html, i + 1 + tag.len(), &close).unwrap_or(html.len());
+            continue;
+        }
+        if starts_tag(bytes, i + 1, "a") {
+            let (href, end) = scan_tag_href(html, i + 2);
+            if let Some(href) = href {
+                hrefs.push(html_escape::decode_html_entities(href).into_owned());
+            }
+            i = end;
+            continue;
+        }
+        i += 1;
+    }
+    hrefs
+}
+
+/// Whether `bytes[at..]` starts with tag name `name` (ASCII case-insensitive)
+/// followed by a name terminator.
+fn starts_tag(bytes: &[u8], at: usize, name: &str) -> bool {
+    let end = at + name.len();
+    end <= bytes.len()
+        && bytes[at..end].eq_ignore_ascii_case(name.as_bytes())
+        && bytes
+            .get(end)
+            .map_or(true, |&b| b.is_ascii_whitespace() || b == b'>' || b == b'/')
+}
+
+/// Byte offset of the next ASCII case-insensitive `needle` at or after `from`.
+fn find_ascii_ci(haystack: &str, from: usize, needle: &str) -> Option<usize> {
+    haystack.as_bytes()[from..]
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+        .map(|pos| from + pos)
+}
+
+/// Scan a start tag's attributes from `i` (just past the tag name) to its
+/// closing `>`, returning the first `href` value and the offset past the tag.
+fn scan_tag_href(html: &str, mut i: usize) -> (Option<&str>, usize) {
+    let bytes = html.as_bytes();
+    let mut href = None;
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return (href, i);
+        }
+        if bytes[i] == b'>' {
+            return (href, i + 1);
+        }
+
+        let name_start = i;
+        while i < bytes.len()
+            && !bytes[i].is_ascii_whitespace()
+            && !matches!(bytes[i], b'=' | b'>' | b'/')
+        {
+            i += 1;
+        }
+        let name = &html[name_start..i];
+
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if bytes.get(i) != Some(&b'=') {
+            continue;
+        }
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        let value = match bytes.get(i) {
+            Some(&quote @ (b'"' | b'\'')) => {
+                let start = i + 1;
+                let end = html[start..]
+                    .find(quote as char)
+                    .map_or(html.len(), |len| start + len);
+                i = (end + 1).min(html.len());
+                &html[start..end]
+            }
+            _ => {
+                let start = i;
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                    i += 1;
+                }
+                &html[start..i]
+            }
+        };
+        if href.is_none() && name.eq_ignore_ascii_case("href") {
+            href = Some(value);
+        }
+    }
+}
+
+/// Trim whitespace and stray surrounding quotes from a raw href, as authored
+/// otherwise.
+pub(super) fn trim_href(href: &str) -> &str {
+    href.trim().trim_matches('"').trim_matches('\'').trim()
+}
+
+/// How [`collect_text`] separates the text of block-level elements.
+///
+/// Inline elements (`<b>`, `<i>`, `<span>`, …) never add a separator, so
+/// `Spicy<b>Margarita</b>` stays one word; only source whitespace splits them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TextJoinPolicy {
+    /// Pushed before and after each block element (`<p>`, `<div>`, `<br>`, …).
+    pub block_separator: &'static str,
+}
+
+impl TextJoinPolicy {
+    /// Blocks become spaces: single-line display text.
+    pub const SPACED: Self = Self {
+        block_separator: " ",
+    };
+}
+
+/// Raw text of `element`: text nodes concatenated as-is, with
+/// `policy.block_separator` at each block-level boundary. Scripts/styles are
+/// skipped; nothing is normalized.
+pub(crate) fn collect_text(element: &ElementRef, policy: &TextJoinPolicy) -> String {
+    let mut out = String::new();
+    push_text(*element, policy, &mut out);
+    out
+}
+
+fn push_text(element: ElementRef, policy: &TextJoinPolicy, out: &mut String) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            out.push_str(text);
+        } else if let Some(el) = ElementRef::wrap(child) {
+            let tag = el.value().name();
+            if JUNK_TAGS.contains(&tag) {
+                continue;
+            }
+            let is_block = BLOCK_TAGS.contains(&tag);
+            if is_block {
+                out.push_str(policy.block_separator);
+            }
+            // Void blocks (`<br>`, `<hr>`, `<img>`) are a single boundary.
+            if el.has_children() {
+                push_text(el, policy, out);
+                if is_block {
+                    out.push_str(policy.block_separator);
+                }
+            }
+        }
+    }
+}
+
+/// Display text of an anchor: [`collect_text`] with blocks as spaces, then
+/// normalized (entities, unicode, whitespace).
+pub(super) fn anchor_text(link: &ElementRef) -> String {
+    crate::tools::normalize::normalize_text(&collect_text(link, &TextJoinPolicy::SPACED))
+}
+
+/// Whether `url` is on `base`'s host, compared canonically (`www.` ignored).
+pub(super) fn is_same_site(url: &Url, base: &Url) -> bool {
+    match (url.host_str(), base.host_str()) {
+        (Some(host), Some(base_host)) => normalize_domain(host) == normalize_domain(base_host),
+        _ => false,
+    }
+}
+
 /// Check if URL scheme is acceptable (http/https).
 fn is_valid_scheme(url: &Url) -> bool {
     matches!(url.scheme(), "http" | "https")
@@ -177,6 +544,49 @@ fn parse_base_url(url: &str) -> Option<Url> {
         .ok()
 }
 
+/// Whether the nearest heading before a group's first sibling (see
+/// [`nearest_heading`]) starts with one of `options.boilerplate_headings`,
+/// compared case- and whitespace-insensitively on word boundaries: "related"
+/// matches "Related Recipes" but not "Relatedness".
+fn is_boilerplate_group(first_sibling: &ElementRef, options: &MapChildrenOptions) -> bool {
+    if options.boilerplate_headings.is_empty() {
+        return false;
+    }
+    let Some(heading) = nearest_heading(first_sibling) else {
+        return false;
+    };
+    options.boilerplate_headings.iter().any(|phrase| {
+        let phrase = normalize_text(phrase);
+        !phrase.is_empty()
+            && heading
+                .strip_prefix(&phrase)
+                .is_some_and(|rest| !rest.starts_with(char::is_alphanumeric))
+    })
+}
+
+/// Normalized text of the closest non-empty `<h1>`–`<h6>` before `element`
+/// in document order, looking through its preceding siblings (and their
+/// descendants), then its ancestors' — the heading a list sits under.
+fn nearest_heading(element: &ElementRef) -> Option<String> {
+    let is_heading =
+        |el: &ElementRef| matches!(el.value().name(), "h1" | "h2" | "h3" | "h4" | "h5" | "h6");
+    let mut current = *element;
+    loop {
+        for prev in current.prev_siblings().filter_map(ElementRef::wrap) {
+            let heading = std::iter::once(prev)
+                .chain(prev.descendants().filter_map(ElementRef::wrap))
+                .filter(is_heading)
+                .map(|h| normalize_text(&collect_text(&h, &TextJoinPolicy::SPACED)))
+                .filter(|text| !text.is_empty())
+                .last();
+            if heading.is_some() {
+                return heading;
+            }
+        }
+        current = current.parent().and_then(ElementRef::wrap)?;
+    }
+}
+
 /// Check if element is inside a specific HTML tag.
 fn is_inside_tag(element: &ElementRef, tag_name: &str) -> bool {
     let mut ancestor = element.parent();
@@ -201,6 +611,31 @@ fn map_structure_pattern(element: &ElementRef) -> StructurePattern {
     StructurePattern { tags }
 }
 
+/// Look through up to `depth` wrapper elements — ones with a single
+/// (non-junk) child element and no text of their own — returning the
+/// innermost one reached.
+fn unwrap_single_child(element: ElementRef<'_>, depth: usize) -> ElementRef<'_> {
+    let mut current = element;
+    for _ in 0..depth {
+        let mut children = current
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| !JUNK_TAGS.contains(&child.value().name()));
+        let (Some(only), None) = (children.next(), children.next()) else {
+            break;
+        };
+        let has_text = current
+            .children()
+            .filter_map(|node| node.value().as_text())
+            .any(|text| !text.trim().is_empty());
+        if has_text {
+            break;
+        }
+        current = only;
+    }
+    current
+}
+
 /// Recursively scan for sibling groups in DOM tree.
 ///
 /// Finds repeating patterns at each level by:
@@ -208,9 +643,12 @@ fn map_structure_pattern(element: &ElementRef) -> StructurePattern {
 /// 2. Detecting multi-element patterns (e.g., repeated <h3><p><a> sequences)
 /// 3. Recursing into children to scan deeper levels
 ///
-/// Each discovered group is added to `all_groups` for later scoring.
+/// Each sibling's structure is read through up to
+/// `options.sibling_unwrap_depth` wrappers. Each discovered group that isn't
+/// boilerplate is added to `all_groups` for later scoring.
 fn map_sibling_groups_recursive<'a>(
     element: &'a ElementRef<'a>,
+    options: &MapChildrenOptions,
     all_groups: &mut Vec<SiblingGroup>,
 ) {
     // Get children at this level (filter junk)
@@ -228,7 +666,8 @@ fn map_sibling_groups_recursive<'a>(
         let mut pattern_groups: Vec<(Vec<String>, Vec<usize>)> = Vec::new();
 
         for (idx, child) in children.iter().enumerate() {
-            let pattern = map_structure_pattern(child);
+            let pattern =
+                map_structure_pattern(&unwrap_single_child(*child, options.sibling_unwrap_depth));
 
             // Find existing group with compatible pattern (shares common prefix)
             let mut matched = false;
@@ -258,25 +697,28 @@ fn map_sibling_groups_recursive<'a>(
             if indices.len() >= MIN_SIBLING_GROUP_SIZE && !tags.is_empty() {
                 let siblings: Vec<String> = indices.iter().map(|&i| children[i].html()).collect();
 
-                if siblings.len() >= MIN_SIBLING_GROUP_SIZE {
-                    let first_child = &children[indices[0]];
+                let first_child = &children[indices[0]];
+                if siblings.len() >= MIN_SIBLING_GROUP_SIZE
+                    && !is_boilerplate_group(first_child, options)
+                {
                     all_groups.push(SiblingGroup {
                         in_main: is_inside_tag(first_child, MAIN_TAG),
                         in_navigation: NAV_TAGS.iter().any(|tag| is_inside_tag(first_child, tag)),
                         pattern_len: SINGLE_ELEMENT_PATTERN_LEN,
                         siblings,
+                        links: collect_group_links(indices.iter().map(|&i| &children[i])),
                     });
                 }
             }
         }
 
         // 2. Detect multi-element patterns
-        map_multi_element_patterns(&children, all_groups);
+        map_multi_element_patterns(&children, options, all_groups);
     }
 
     // Recurse into ALL children to scan deeper levels
     for child in children {
-        map_sibling_groups_recursive(&child, all_groups);
+        map_sibling_groups_recursive(&child, options, all_groups);
     }
 }
 
@@ -286,7 +728,11 @@ fn map_sibling_groups_recursive<'a>(
 /// Tries pattern lengths from MIN_PATTERN_LEN up to n/MAX_PATTERN_RATIO.
 ///
 /// Handles overlapping patterns by selecting non-overlapping instances.
-fn map_multi_element_patterns(children: &[ElementRef], all_groups: &mut Vec<SiblingGroup>) {
+fn map_multi_element_patterns(
+    children: &[ElementRef],
+    options: &MapChildrenOptions,
+    all_groups: &mut Vec<SiblingGroup>,
+) {
     use std::collections::HashMap;
 
     let n = children.len();
@@ -303,7 +749,12 @@ fn map_multi_element_patterns(children: &[ElementRef], all_groups: &mut Vec<Sibl
         let mut idx = 0;
         while idx + pattern_len <= n {
             let pattern: Vec<StructurePattern> = (0..pattern_len)
-                .map(|offset| map_structure_pattern(&children[idx + offset]))
+                .map(|offset| {
+                    map_structure_pattern(&unwrap_single_child(
+                        children[idx + offset],
+                        options.sibling_unwrap_depth,
+                    ))
+                })
                 .collect();
 
             multi_pattern_groups.entry(pattern).or_default().push(idx);
@@ -347,8 +798,10 @@ fn map_multi_element_patterns(children: &[ElementRef], all_groups: &mut Vec<Sibl
                         })
                         .collect();
 
-                    if siblings.len() >= MIN_SIBLING_GROUP_SIZE {
-                        let first_child = &children[non_overlapping[0]];
+                    let first_child = &children[non_overlapping[0]];
+                    if siblings.len() >= MIN_SIBLING_GROUP_SIZE
+                        && !is_boilerplate_group(first_child, options)
+                    {
                         all_groups.push(SiblingGroup {
                             in_main: is_inside_tag(first_child, MAIN_TAG),
                             in_navigation: NAV_TAGS
@@ -356,6 +809,9 @@ fn map_multi_element_patterns(children: &[ElementRef], all_groups: &mut Vec<Sibl
                                 .any(|tag| is_inside_tag(first_child, tag)),
                             pattern_len,
                             siblings,
+                            links: collect_group_links(non_overlapping.iter().flat_map(
+                                |&start_idx| &children[start_idx..start_idx + pattern_len],
+                            )),
                         });
                     }
                 }
@@ -374,7 +830,17 @@ fn map_multi_element_patterns(children: &[ElementRef], all_groups: &mut Vec<Sibl
 /// - Fragments are small (individual sibling elements, not full pages)
 /// - Parsing overhead is minimal compared to network I/O
 /// - Alternative (keeping ElementRefs) would require major API refactor
+#[cfg(test)]
 pub(super) fn map_sibling_link(siblings: &[String], url: &str) -> Vec<String> {
+    map_sibling_link_with(siblings, url, &MapChildrenOptions::default())
+}
+
+/// Like `map_sibling_link`, honoring `options.match_slugs`.
+fn map_sibling_link_with(
+    siblings: &[String],
+    url: &str,
+    options: &MapChildrenOptions,
+) -> Vec<String> {
     let Some(base) = parse_base_url(url) else {
         return Vec::new();
     };
@@ -383,7 +849,40 @@ pub(super) fn map_sibling_link(siblings: &[String], url: &str) -> Vec<String> {
         .iter()
         .filter_map(|html| {
             let doc = Html::parse_fragment(html);
-            select_primary_link_in_document(&doc, &base)
+            select_primary_link_in_document(&doc, &base, options.match_slugs)
+        })
+        .collect()
+}
+
+/// Sibling child links with a title each, as `(title, url)` pairs: the link
+/// [`map_children_from_doc`]'s sibling pass would pick, titled by the sibling's first
+/// `<h1>`–`<h4>`, else that link's anchor text. Siblings with no link, or
+/// neither title source, are skipped.
+pub(crate) fn map_titled_siblings_from_doc(doc: &Html, url: &str) -> Vec<(String, String)> {
+    let Some(base) = parse_base_url(url) else {
+        return Vec::new();
+    };
+
+    detected_siblings(doc, url, &MapChildrenOptions::default())
+        .iter()
+        .filter_map(|html| {
+            let fragment = Html::parse_fragment(html);
+            let link = select_primary_link_in_document(&fragment, &base, false)?;
+            let heading = fragment
+                .root_element()
+                .descendants()
+                .filter_map(ElementRef::wrap)
+                .filter(|el| matches!(el.value().name(), "h1" | "h2" | "h3" | "h4"))
+                .map(|el| anchor_text(&el))
+                .find(|text| !text.is_empty());
+            let title = heading.or_else(|| {
+                fragment
+                    .select(&LINK_SELECTOR)
+                    .filter(|a| resolve_page_link(a, &base).is_some_and(|u| u.as_str() == link))
+                    .map(|a| anchor_text(&a))
+                    .find(|text| has_meaningful_text(text) && !is_utility_text(text))
+            })?;
+            Some((title, link))
         })
         .collect()
 }
@@ -448,8 +947,8 @@ pub(super) fn map_itemlist_link(itemlist: &[Value], doc: &Html, url: &str) -> Ve
             let elements = item.get("itemListElement")?.as_array()?;
 
             Some(
-                elements
-                    .iter()
+                sort_by_position(elements)
+                    .into_iter()
                     .filter_map(|elem| {
                         let url_str = elem.get("url")?.as_str()?;
 
@@ -500,6 +999,29 @@ pub(super) fn map_itemlist_link(itemlist: &[Value], doc: &Html, url: &str) -> Ve
         .collect()
 }
 
+/// ItemList elements in `position` order. Falls back to document order unless
+/// every element has a distinct numeric `position` (a number or numeric
+/// string) — a partial or duplicated ordering isn't trustworthy.
+fn sort_by_position(elements: &[Value]) -> Vec<&Value> {
+    let positions: Option<Vec<f64>> = elements
+        .iter()
+        .map(|elem| match elem.get("position")? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        })
+        .collect();
+    let mut indexed: Vec<(f64, &Value)> = match positions {
+        Some(positions) => positions.into_iter().zip(elements).collect(),
+        None => return elements.iter().collect(),
+    };
+    indexed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    if indexed.windows(2).any(|w| w[0].0 == w[1].0) {
+        return elements.iter().collect();
+    }
+    indexed.into_iter().map(|(_, elem)| elem).collect()
+}
+
 /// Map anchor ID to real URL by finding element and extracting link.
 ///
 /// # Performance Note
@@ -509,7 +1031,7 @@ fn map_anchor_to_link(anchor_id: &str, doc: &Html, base: &Url) -> Option<String>
     // Dynamic selector - necessary because anchor_id is runtime data
     let selector = Selector::parse(&format!("[id='{}']", anchor_id)).ok()?;
     let element = doc.select(&selector).next()?;
-    select_primary_link_in_element(&element, base)
+    select_primary_link_in_element(&element, base, false)
 }
 
 fn has_meaningful_text(text: &str) -> bool {
@@ -576,7 +1098,7 @@ fn collect_heading_texts(element: &ElementRef) -> Vec<String> {
         .descendants()
         .filter_map(ElementRef::wrap)
         .filter(|el| matches!(el.value().name(), "h1" | "h2" | "h3" | "h4"))
-        .map(|el| normalize_text(&el.text().collect::<String>()))
+        .map(|el| normalize_text(&collect_text(&el, &TextJoinPolicy::SPACED)))
         .filter(|text| !text.is_empty())
         .collect()
 }
@@ -589,8 +1111,65 @@ fn link_matches_heading(link_text_norm: &str, headings: &[String]) -> bool {
     })
 }
 
-fn select_primary_link_in_element(element: &ElementRef, base: &Url) -> Option<String> {
+/// Lowercase alphanumeric tokens of `text`, apostrophes dropped so
+/// "Dragon's" matches a `dragons` slug.
+fn slug_tokens(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .replace(['\'', '\u{2019}'], "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Share of a heading's tokens found in `url`'s last path segment (extension
+/// dropped), best over `headings`.
+fn slug_similarity(url: &str, headings: &[String]) -> f64 {
+    let Some(slug) = Url::parse(url).ok().and_then(|u| {
+        u.path_segments()?
+            .rfind(|segment| !segment.is_empty())
+            .map(|segment| segment.split('.').next().unwrap_or(segment).to_string())
+    }) else {
+        return 0.0;
+    };
+    let slug = slug_tokens(&slug);
+    headings
+        .iter()
+        .map(|heading| {
+            let words = slug_tokens(heading);
+            let hits = words.iter().filter(|w| slug.contains(w)).count();
+            match words.len() {
+                0 => 0.0,
+                n => hits as f64 / n as f64,
+            }
+        })
+        .fold(0.0, f64::max)
+}
+
+/// The candidate whose URL slug best matches a heading, when it clearly
+/// beats every other candidate and covers at least
+/// [`MIN_SLUG_SIMILARITY`] of the heading's words.
+fn best_slug_match(candidates: &[String], headings: &[String]) -> Option<String> {
+    let mut scored: Vec<(f64, &String)> = candidates
+        .iter()
+        .map(|url| (slug_similarity(url, headings), url))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let (best, url) = *scored.first()?;
+    let runner_up = scored
+        .iter()
+        .find(|(_, other)| *other != url)
+        .map_or(0.0, |(score, _)| *score);
+    (best >= MIN_SLUG_SIMILARITY && best > runner_up).then(|| url.clone())
+}
+
+fn select_primary_link_in_element(
+    element: &ElementRef,
+    base: &Url,
+    match_slugs: bool,
+) -> Option<String> {
     let headings = collect_heading_texts(element);
+    let mut candidates: Vec<String> = Vec::new();
     let mut primary_text: Option<String> = None;
     let mut fallback: Option<String> = None;
     let mut heading_links: Vec<(String, String)> = Vec::new(); // (url, text) for heading links
@@ -616,7 +1195,7 @@ fn select_primary_link_in_element(element: &ElementRef, base: &Url) -> Option<St
             }
         };
 
-        if !is_valid_scheme(&url) {
+        if !is_valid_scheme(&url) || !ctx_allows_domain(url.as_str()) {
             continue;
         }
 
@@ -624,7 +1203,7 @@ fn select_primary_link_in_element(element: &ElementRef, base: &Url) -> Option<St
             fallback = Some(url.to_string());
         }
 
-        let text_raw = link.text().collect::<String>();
+        let text_raw = collect_text(&link, &TextJoinPolicy::SPACED);
         let text_norm = normalize_text(&text_raw);
         let is_heading =
             is_heading_link(&link, &text_raw) || link_matches_heading(&text_norm, &headings);
@@ -637,6 +1216,15 @@ fn select_primary_link_in_element(element: &ElementRef, base: &Url) -> Option<St
         if primary_text.is_none() && is_meaningful {
             primary_text = Some(url.to_string());
         }
+        if !is_utility_text(&text_raw) && !candidates.contains(&url.to_string()) {
+            candidates.push(url.to_string());
+        }
+    }
+
+    if match_slugs && candidates.len() > 1 {
+        if let Some(url) = best_slug_match(&candidates, &headings) {
+            return Some(url);
+        }
     }
 
     // Select heading link using deterministic priority matching
@@ -680,13 +1268,141 @@ fn select_primary_link_in_element(element: &ElementRef, base: &Url) -> Option<St
     heading_link.or(primary_text).or(fallback)
 }
 
-fn select_primary_link_in_document(doc: &Html, base: &Url) -> Option<String> {
+fn select_primary_link_in_document(doc: &Html, base: &Url, match_slugs: bool) -> Option<String> {
     for node in doc.tree.nodes() {
         if let Some(element) = ElementRef::wrap(node) {
-            if let Some(link) = select_primary_link_in_element(&element, base) {
+            if let Some(link) = select_primary_link_in_element(&element, base, match_slugs) {
                 return Some(link);
             }
         }
     }
     None
 }
+
+/// Object keys in script state (`__NEXT_DATA__`, …) that name the next page
+/// of a paginated list, compared lowercase.
+const NEXT_PAGE_KEYS: &[&str] = &[
+    "next",
+    "nextpage",
+    "nextpageurl",
+    "next_page",
+    "next_page_url",
+    "nexturl",
+    "loadmoreurl",
+];
+
+/// `data-*` attribute name fragments that mark a paging endpoint.
+const LIST_API_ATTR_HINTS: &[&str] = &["next", "load-more", "endpoint", "api"];
+
+/// Path fragments of list/feed APIs.
+const LIST_API_PATHS: &[&str] = &["/api/", "/wp-json/", "/graphql", "/ajax/", "/feed"];
+
+/// Static-asset extensions that are never list endpoints.
+const ASSET_EXTENSIONS: &[&str] = &[
+    ".js", ".css", ".map", ".png", ".jpg", ".jpeg", ".gif", ".webp", ".svg", ".woff2",
+];
+
+/// Quoted absolute URLs and root-relative paths in script source, including
+/// JSON-escaped ones (`"\/api\/recipes"`).
+static QUOTED_URL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"["']((?:https?:|\\?/)[^"'\s<>]+)["']"#).expect("valid regex"));
+
+/// Whether a URL or path looks like a list/feed API: an API path, or a
+/// pagination query parameter (`page`, `offset`, `cursor`, …). Assets never
+/// match.
+fn is_list_api_url(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    let path = lower.split(['?', '#']).next().unwrap_or_default();
+    if ASSET_EXTENSIONS.iter().any(|ext| path.ends_with(ext)) {
+        return false;
+    }
+    let query = lower.split_once('?').map_or("", |(_, query)| query);
+    LIST_API_PATHS
+        .iter()
+        .any(|fragment| path.contains(fragment))
+        || query.split('&').any(|pair| {
+            matches!(
+                pair.split('=').next(),
+                Some("page" | "offset" | "cursor" | "after" | "start")
+            )
+        })
+}
+
+/// Whether `value` is written as a URL or root-relative path.
+fn is_url_like(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    lower.starts_with('/') || lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// Push every string under a [`NEXT_PAGE_KEYS`] key of `value` that looks
+/// like a URL.
+fn push_next_page_urls(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(s)
+                        if NEXT_PAGE_KEYS.contains(&key.to_ascii_lowercase().as_str())
+                            && is_url_like(s) =>
+                    {
+                        out.push(s.clone())
+                    }
+                    _ => push_next_page_urls(value, out),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| push_next_page_urls(item, out)),
+        _ => {}
+    }
+}
+
+/// Candidate list/feed API endpoints in `doc` (see `detect_list_api`),
+/// resolved against `base`, deduplicated, in document order.
+pub(super) fn list_api_candidates(doc: &Html, base: &Url) -> Vec<String> {
+    let mut raw: Vec<String> = Vec::new();
+    for el in doc
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+    {
+        let element = el.value();
+        if element.name() == "script" {
+            let is_jsonld = element
+                .attr("type")
+                .is_some_and(|ty| ty.eq_ignore_ascii_case("application/ld+json"));
+            if element.attr("src").is_some() || is_jsonld {
+                continue;
+            }
+            let source = el.text().collect::<String>();
+            if element.attr("id") == Some("__NEXT_DATA__") {
+                if let Ok(data) = serde_json::from_str::<Value>(&source) {
+                    push_next_page_urls(&data, &mut raw);
+                }
+            }
+            raw.extend(
+                QUOTED_URL_REGEX
+                    .captures_iter(&source)
+                    .map(|cap| cap[1].replace("\\/", "/"))
+                    .filter(|url| is_list_api_url(url)),
+            );
+            continue;
+        }
+        for (name, value) in element.attrs() {
+            let Some(hint) = name.strip_prefix("data-") else {
+                continue;
+            };
+            let value = value.trim();
+            let hinted = LIST_API_ATTR_HINTS.iter().any(|h| hint.contains(h));
+            if is_url_like(value) && (hinted || is_list_api_url(value)) {
+                raw.push(value.to_string());
+            }
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    raw.iter()
+        .filter_map(|value| resolve_page_href(value, base))
+        .map(|url| url.to_string())
+        .filter(|url| seen.insert(url.clone()))
+        .collect()
+}
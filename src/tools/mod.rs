@@ -2,6 +2,8 @@
 
 pub mod batch;
 pub mod classify;
+pub mod crawl;
+pub mod dedup;
 pub mod extract;
 pub mod fetch;
 pub mod map;
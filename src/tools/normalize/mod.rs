@@ -1,9 +1,16 @@
 //! Normalize Tools
 
 mod tests;
+pub mod types;
 pub mod utils;
 
-pub use utils::{normalize_social, normalize_url};
+pub use types::{NormalizePhoneOptions, NormalizeTextOptions, NormalizeUrlOptions};
+pub use utils::{
+    escape_attr, escape_html, normalize_social, normalize_url, normalize_url_with,
+    parse_iso_duration,
+};
+
+use std::collections::HashMap;
 
 use crate::types::Html;
 
@@ -15,14 +22,66 @@ use crate::types::Html;
 /// - Remove control characters
 /// - Normalize whitespace
 pub fn normalize_text(text: &str) -> String {
+    normalize_text_with(text, &NormalizeTextOptions::default())
+}
+
+/// Normalize text with options
+///
+/// - Everything [`normalize_text`] does
+/// - With `ascii_fold`: fold smart quotes, dashes and ellipses to ASCII
+/// - With `remove_phrases`: drop whole lines that are just one of the phrases
+///   (compared after per-line whitespace normalization, case-insensitively)
+pub fn normalize_text_with(text: &str, options: &NormalizeTextOptions) -> String {
     let mut result = utils::decode_html_entities(text);
     result = utils::normalize_unicode(&result);
     result = utils::remove_zero_width_chars(&result);
     result = utils::remove_control_chars(&result);
+    if options.ascii_fold {
+        result = utils::fold_punctuation_to_ascii(&result);
+    }
+    if !options.remove_phrases.is_empty() {
+        result = utils::remove_phrase_lines(&result, &options.remove_phrases);
+    }
     result = utils::normalize_whitespace(&result);
     result
 }
 
+/// Truncate text to at most `max_chars` characters for previews
+///
+/// - Text that fits is returned unchanged (no ellipsis)
+/// - Otherwise cut at the last word boundary within `max_chars`, drop trailing
+///   whitespace and separators (`,;:-`), and append `…` (not counted in `max_chars`)
+/// - A single word longer than `max_chars` is hard-cut, never inside a
+///   grapheme (combining marks, ZWJ sequences and variation selectors stay whole)
+/// - Counts Unicode scalar values, not bytes — safe on multibyte text
+pub fn truncate_at_word_boundary(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut = utils::word_boundary_cut(text, max_chars);
+    let head = text[..cut].trim_end_matches(|c: char| c.is_whitespace() || ",;:-".contains(c));
+    format!("{head}\u{2026}")
+}
+
+/// Normalize a page title for display by stripping a trailing site suffix
+///
+/// - Separators: ` | `, ` - `, ` – `, ` — ` (spaced, so `Spider-Man` is safe)
+/// - With `site_name` (e.g. `og:site_name`): strip `<sep> <site_name>` when the
+///   title ends with it (case-insensitive); otherwise leave the title alone
+/// - Without: strip the segment after the last separator when it looks like a
+///   site name — at most 4 words and shorter than what precedes it
+/// - Text is normalized first (see [`normalize_text`])
+///
+/// `"Southern Red Velvet Cake | Food Network"` → `"Southern Red Velvet Cake"`
+pub fn normalize_title(title: &str, site_name: Option<&str>) -> String {
+    let title = normalize_text(title);
+    let stripped = match site_name.map(normalize_text).filter(|s| !s.is_empty()) {
+        Some(site) => utils::strip_site_suffix(&title, &site),
+        None => utils::strip_trailing_segment(&title),
+    };
+    stripped.unwrap_or(&title).to_string()
+}
+
 /// Normalize HTML
 ///
 /// - Normalize escaped newlines
@@ -65,11 +124,23 @@ pub fn normalize_phones(phones: &[String]) -> Vec<String> {
     crate::dedupe!(phones, utils::normalize_phone)
 }
 
+/// Normalize phone numbers with options
+///
+/// - Everything [`normalize_phones`] does
+/// - With `keep_extension`: keep the extension as a `;ext=123` suffix
+///   (`555-123-4567 x123` → `5551234567;ext=123`) instead of stripping it
+pub fn normalize_phones_with(phones: &[String], options: &NormalizePhoneOptions) -> Vec<String> {
+    crate::dedupe!(phones, |phone: &String| utils::normalize_phone_with(
+        phone, options
+    ))
+}
+
 /// Normalize URLs
 ///
 /// - Add https:// if protocol is missing
 /// - Normalize protocol to https
 /// - Normalize domain (lowercase, IDNA, strip www)
+/// - Strip default ports (`:80` for http, `:443` for https); keep others
 /// - Normalize path (strip all trailing slashes)
 /// - Sort query parameters
 /// - Remove fragment
@@ -78,6 +149,41 @@ pub fn normalize_urls(urls: &[String]) -> Vec<String> {
     crate::dedupe!(urls, utils::normalize_url)
 }
 
+/// Normalize URLs with options
+///
+/// - Everything [`normalize_urls`] does
+/// - With `keep_fragment`: keep fragments, so `#slide-49` and `#slide-50`
+///   stay distinct
+pub fn normalize_urls_with(urls: &[String], options: &NormalizeUrlOptions) -> Vec<String> {
+    crate::dedupe!(urls, |url: &String| utils::normalize_url_with(url, options))
+}
+
+/// Normalize URLs, folding alias hosts into their canonical host
+///
+/// - Everything [`normalize_urls`] does
+/// - Rewrite hosts found in `aliases` (alias → canonical host) before
+///   deduplicating, so mirrors like `m.example.com` and `amp.example.com`
+///   collapse into `example.com`. Hosts are compared after domain
+///   normalization (lowercase, IDNA, `www.` stripped); subdomains of an alias
+///   are not aliased.
+pub fn normalize_urls_with_aliases(
+    urls: &[String],
+    aliases: &HashMap<String, String>,
+) -> Vec<String> {
+    let aliases: HashMap<String, String> = aliases
+        .iter()
+        .map(|(alias, canonical)| {
+            (
+                utils::normalize_domain(alias.trim()),
+                utils::normalize_domain(canonical.trim()),
+            )
+        })
+        .collect();
+    crate::dedupe!(urls, |url: &String| utils::normalize_url_with_aliases(
+        url, &aliases
+    ))
+}
+
 /// Normalize social URLs
 ///
 /// - Deduplicate
@@ -88,3 +194,38 @@ pub fn normalize_urls(urls: &[String]) -> Vec<String> {
 pub fn normalize_social_urls(socials: &[String]) -> Vec<String> {
     crate::dedupe!(socials.iter().cloned())
 }
+
+/// Normalize roundup children (`(title, url)` pairs)
+///
+/// - Collapse entries with the same canonical URL, even when titles differ
+/// - With `fuzzy_titles`: also collapse entries on the same host whose titles
+///   are near-identical (token sets, case and punctuation ignored)
+/// - Keep the first occurrence of each
+pub fn normalize_children(
+    children: &[(String, String)],
+    fuzzy_titles: bool,
+) -> Vec<(String, String)> {
+    let mut seen_urls = std::collections::HashSet::new();
+    let mut kept: Vec<(String, String)> = Vec::new();
+    let mut kept_titles: Vec<(String, std::collections::BTreeSet<String>)> = Vec::new();
+
+    for (title, url) in children {
+        if !seen_urls.insert(utils::normalize_url(url)) {
+            continue;
+        }
+        let host = utils::url_host(url);
+        let tokens = utils::title_tokens(title);
+        if fuzzy_titles
+            && !tokens.is_empty()
+            && kept_titles
+                .iter()
+                .any(|(h, t)| *h == host && utils::is_near_duplicate_title(t, &tokens))
+        {
+            continue;
+        }
+        kept_titles.push((host, tokens));
+        kept.push((title.clone(), url.clone()));
+    }
+
+    kept
+}
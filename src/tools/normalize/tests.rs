@@ -270,6 +270,15 @@ async fn test_normalize_html_removes_junk_attributes() {
     );
 }
 
+#[tokio::test]
+async fn test_normalize_html_preserves_heading_ids() {
+    let input = r#"<div id="ad-slot" class="ad">Ad</div><h2 class="title" id="ingredients">Ingredients</h2><H3 ID='method' data-x="1">Method</H3><p id="intro">Text</p>"#;
+    assert_eq!(
+        normalize_html(&input.into()).await.as_str(),
+        r#"<div>Ad</div><h2 id="ingredients">Ingredients</h2><H3 ID='method'>Method</H3><p>Text</p>"#
+    );
+}
+
 #[tokio::test]
 async fn test_normalize_html_preserves_jsonld() {
     let input = r#"<script>alert('bad')</script><script type="application/ld+json">{"@context":"schema.org"}</script><p>Text</p>"#;
@@ -369,6 +378,35 @@ fn test_normalize_urls_trailing_slash() {
     assert_eq!(output.len(), 1);
 }
 
+#[test]
+fn test_normalize_urls_default_ports() {
+    let input = vec![
+        "https://example.com:443/recipes".to_string(),
+        "http://example.com:80/recipes".to_string(),
+        "HTTPS://Example.COM:443/recipes/".to_string(),
+        "https://example.com/recipes".to_string(),
+    ];
+    let output = normalize_urls(&input);
+    assert_eq!(output, vec!["https://example.com/recipes".to_string()]);
+}
+
+#[test]
+fn test_normalize_urls_keeps_non_default_ports() {
+    let input = vec![
+        "https://example.com:8080/recipes".to_string(),
+        "http://example.com:8080/recipes".to_string(),
+        "https://example.com/recipes".to_string(),
+    ];
+    let output = normalize_urls(&input);
+    assert_eq!(
+        output,
+        vec![
+            "https://example.com:8080/recipes".to_string(),
+            "https://example.com/recipes".to_string(),
+        ]
+    );
+}
+
 #[test]
 fn test_normalize_urls_query_param_order() {
     let input = vec![
@@ -390,6 +428,65 @@ fn test_normalize_urls_fragment_removal() {
     assert_eq!(output.len(), 1);
 }
 
+#[test]
+fn test_normalize_urls_with_keep_fragment() {
+    let input = vec![
+        "https://www.delish.com/cooking/g1234/best-dips/#slide-49".to_string(),
+        "https://www.delish.com/cooking/g1234/best-dips/#slide-50".to_string(),
+        "https://delish.com/cooking/g1234/best-dips#slide-50".to_string(),
+    ];
+
+    assert_eq!(
+        normalize_urls(&input),
+        vec!["https://delish.com/cooking/g1234/best-dips"]
+    );
+
+    let kept = normalize_urls_with(
+        &input,
+        &NormalizeUrlOptions {
+            keep_fragment: true,
+        },
+    );
+    assert_eq!(
+        kept,
+        vec![
+            "https://delish.com/cooking/g1234/best-dips#slide-49",
+            "https://delish.com/cooking/g1234/best-dips#slide-50",
+        ]
+    );
+    assert_eq!(
+        normalize_urls_with(&input, &NormalizeUrlOptions::default()),
+        normalize_urls(&input)
+    );
+}
+
+#[test]
+fn test_normalize_urls_with_aliases() {
+    let aliases = std::collections::HashMap::from([
+        ("m.example.com".to_string(), "example.com".to_string()),
+        ("AMP.Example.com".to_string(), "www.example.com".to_string()),
+    ]);
+    let input = vec![
+        "https://m.example.com/x".to_string(),
+        "https://example.com/x".to_string(),
+        "http://amp.example.com/x/?utm_source=feed".to_string(),
+        "https://www.m.example.com/y".to_string(),
+        "https://cdn.m.example.com/x".to_string(),
+    ];
+    assert_eq!(
+        normalize_urls_with_aliases(&input, &aliases),
+        vec![
+            "https://example.com/x",
+            "https://example.com/y",
+            "https://cdn.m.example.com/x",
+        ]
+    );
+    assert_eq!(
+        normalize_urls_with_aliases(&input, &Default::default()),
+        normalize_urls(&input)
+    );
+}
+
 #[test]
 fn test_normalize_urls_combined() {
     let input = vec![
@@ -506,6 +603,13 @@ fn test_normalize_emails_empty_list() {
     assert_eq!(output.len(), 0);
 }
 
+#[test]
+fn test_normalize_emails_stray_angle_bracket_before_address() {
+    // A `>` ahead of the `<…>` address used to slice backwards and panic.
+    let input = vec!["Chef > Crème — <Chef@Example.com>".to_string()];
+    assert_eq!(normalize_emails(&input), vec!["chef@example.com"]);
+}
+
 // Tests for normalize_phones()
 
 #[test]
@@ -556,6 +660,56 @@ fn test_normalize_phones_with_extensions() {
     assert_eq!(output[0], "5551234567");
 }
 
+#[test]
+fn test_normalize_phones_keep_extension() {
+    let keep = NormalizePhoneOptions {
+        keep_extension: true,
+    };
+    for phone in [
+        "555-123-4567 ext. 123",
+        "555-123-4567 ext 123",
+        "555-123-4567 extension 123",
+        "555-123-4567 x123",
+        "555-123-4567x123",
+        "555-123-4567 #123",
+        "555-123-4567#123",
+    ] {
+        assert_eq!(
+            normalize_phones_with(&[phone.to_string()], &keep),
+            vec!["5551234567;ext=123"],
+            "{phone}"
+        );
+    }
+    assert_eq!(
+        normalize_phones_with(&["+1 (555) 123-4567 EXT. 9".to_string()], &keep),
+        vec!["+15551234567;ext=9"]
+    );
+
+    let input = vec![
+        "555-123-4567".to_string(),
+        "555-123-4567 ext. 123".to_string(),
+        "(555) 123-4567 x123".to_string(),
+    ];
+    assert_eq!(
+        normalize_phones_with(&input, &keep),
+        vec!["5551234567", "5551234567;ext=123"]
+    );
+    assert_eq!(
+        normalize_phones_with(&input, &NormalizePhoneOptions::default()),
+        normalize_phones(&input)
+    );
+}
+
+#[test]
+fn test_normalize_phones_multibyte_before_extension() {
+    // Unicode lowercasing grows `İ` from 2 to 3 bytes, so an extension offset
+    // found in the lowercased text used to land inside the em-dash.
+    let input = vec!["İİİİİ 555-123-4567 ext—12".to_string()];
+    assert_eq!(normalize_phones(&input), vec!["5551234567"]);
+    let input = vec!["☎️ 555–987–6543 x 7".to_string()];
+    assert_eq!(normalize_phones(&input), vec!["5559876543"]);
+}
+
 #[test]
 fn test_normalize_phones_international_vs_local() {
     // International and local versions should be treated as different
@@ -585,6 +739,41 @@ fn test_decode_html_entities() {
     assert_eq!(decode_html_entities("&nbsp;"), "\u{00A0}");
 }
 
+#[test]
+fn test_escape_html() {
+    assert_eq!(
+        escape_html("<b>Fish & Chips</b>"),
+        "&lt;b&gt;Fish &amp; Chips&lt;/b&gt;"
+    );
+    assert_eq!(escape_html(r#"say "hi" it's"#), r#"say "hi" it's"#);
+    assert_eq!(escape_html("&amp;"), "&amp;amp;");
+}
+
+#[test]
+fn test_escape_attr() {
+    let escaped = escape_attr(r#"x" onload="alert('1')"#);
+    assert!(!escaped.contains('"'));
+    assert!(!escaped.contains('\''));
+    assert!(escape_attr("a<b>&c").starts_with("a&lt;b&gt;&amp;c"));
+}
+
+#[test]
+fn test_escape_round_trips_through_decode() {
+    let inputs = [
+        "",
+        "plain text",
+        "<script>alert('x')</script>",
+        r#"Tom & Jerry's "Show""#,
+        "already &amp; encoded &lt;tag&gt; &#39;q&#39;",
+        "https://example.com/?a=1&b=2",
+        "caf\u{e9} \u{2014} \u{1F600}",
+    ];
+    for input in inputs {
+        assert_eq!(decode_html_entities(&escape_html(input)), input);
+        assert_eq!(decode_html_entities(&escape_attr(input)), input);
+    }
+}
+
 #[test]
 fn test_normalize_domain() {
     // Basic lowercase
@@ -858,3 +1047,260 @@ fn test_normalize_social_matches_classify_forms() {
         "https://youtube.com/watch?t=30&v=dQw4w9WgXcQ"
     );
 }
+
+// Tests for normalize_children()
+
+fn child(title: &str, url: &str) -> (String, String) {
+    (title.to_string(), url.to_string())
+}
+
+#[test]
+fn test_normalize_children_collapses_same_canonical_url() {
+    let input = vec![
+        child(
+            "Nightmare on Bourbon Street",
+            "https://www.halfbakedharvest.com/nightmare-on-bourbon-street/",
+        ),
+        child("Spiced Apple Cider", "https://example.com/cider"),
+        child(
+            "Bourbon Street Cocktail",
+            "https://halfbakedharvest.com/nightmare-on-bourbon-street?utm_source=x",
+        ),
+    ];
+    let output = normalize_children(&input, false);
+    assert_eq!(output, vec![input[0].clone(), input[1].clone()]);
+}
+
+#[test]
+fn test_normalize_children_fuzzy_collapses_near_duplicate_titles() {
+    let input = vec![
+        child(
+            "Nightmare on Bourbon Street",
+            "https://www.halfbakedharvest.com/nightmare-on-bourbon-street/",
+        ),
+        child(
+            "Nightmare On Bourbon Street!",
+            "https://www.halfbakedharvest.com/nightmare-bourbon-cocktail/",
+        ),
+        // Same title, different host — a distinct recipe.
+        child(
+            "Nightmare on Bourbon Street",
+            "https://example.com/nightmare-on-bourbon-street",
+        ),
+    ];
+
+    assert_eq!(normalize_children(&input, false).len(), 3);
+    let output = normalize_children(&input, true);
+    assert_eq!(output, vec![input[0].clone(), input[2].clone()]);
+}
+
+// Tests for normalize_text_with()
+
+#[test]
+fn test_normalize_text_with_ascii_fold() {
+    let input = "\u{201C}Don\u{2019}t stir\u{201D} \u{2014} shake 10\u{2013}15 seconds\u{2026}";
+    let folded = normalize_text_with(
+        input,
+        &NormalizeTextOptions {
+            ascii_fold: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(folded, "\"Don't stir\" - shake 10-15 seconds...");
+
+    // Off by default: normalize_text leaves typography alone.
+    assert_eq!(normalize_text(input), input);
+}
+
+#[test]
+fn test_normalize_text_with_remove_phrases() {
+    let input = "Whisk the eggs until pale.\n  ADVERTISEMENT  \nFold in the flour.\n\
+                 Advertisement revenue keeps this site free.\nJump to Recipe";
+    let options = NormalizeTextOptions {
+        remove_phrases: vec!["Advertisement".to_string(), "jump  to recipe".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        normalize_text_with(input, &options),
+        "Whisk the eggs until pale. Fold in the flour. \
+         Advertisement revenue keeps this site free."
+    );
+
+    // Empty by default: nothing removed.
+    assert_eq!(
+        normalize_text_with(input, &NormalizeTextOptions::default()),
+        normalize_text(input)
+    );
+    assert!(normalize_text(input).contains("ADVERTISEMENT"));
+}
+
+// Tests for normalize_title()
+
+#[test]
+fn test_normalize_title_pipe_with_site_name() {
+    assert_eq!(
+        normalize_title(
+            "Southern Red Velvet Cake | Food Network",
+            Some("Food Network")
+        ),
+        "Southern Red Velvet Cake"
+    );
+    // Site name is matched case-insensitively; a different suffix stays.
+    assert_eq!(
+        normalize_title("Paloma | SERIOUS EATS", Some("Serious Eats")),
+        "Paloma"
+    );
+    assert_eq!(
+        normalize_title("Paloma | Cocktails", Some("Serious Eats")),
+        "Paloma | Cocktails"
+    );
+}
+
+#[test]
+fn test_normalize_title_dash_heuristic() {
+    assert_eq!(
+        normalize_title("Spider-Man Pancakes - Delish", None),
+        "Spider-Man Pancakes"
+    );
+    assert_eq!(
+        normalize_title(
+            "How to Make Birria Tacos \u{2013} Step by Step - Tasty",
+            None
+        ),
+        "How to Make Birria Tacos \u{2013} Step by Step"
+    );
+}
+
+#[test]
+fn test_normalize_title_em_dash() {
+    assert_eq!(
+        normalize_title("Classic Lasagna \u{2014} Bon App\u{e9}tit", None),
+        "Classic Lasagna"
+    );
+    assert_eq!(
+        normalize_title(
+            "Classic Lasagna \u{2014} Bon App\u{e9}tit",
+            Some("Bon App\u{e9}tit")
+        ),
+        "Classic Lasagna"
+    );
+}
+
+#[test]
+fn test_normalize_title_without_suffix_unchanged() {
+    assert_eq!(
+        normalize_title("Southern Red Velvet Cake", None),
+        "Southern Red Velvet Cake"
+    );
+    assert_eq!(
+        normalize_title("Southern Red Velvet Cake", Some("Food Network")),
+        "Southern Red Velvet Cake"
+    );
+    // A long trailing segment is part of the title, not a site name.
+    assert_eq!(
+        normalize_title("Pie - The Best Flaky Butter Crust You Will Ever Make", None),
+        "Pie - The Best Flaky Butter Crust You Will Ever Make"
+    );
+}
+
+// Tests for truncate_at_word_boundary()
+
+#[test]
+fn test_truncate_exact_fit_has_no_ellipsis() {
+    assert_eq!(
+        truncate_at_word_boundary("Spicy Margarita", 15),
+        "Spicy Margarita"
+    );
+    assert_eq!(truncate_at_word_boundary("", 0), "");
+}
+
+#[test]
+fn test_truncate_backs_off_to_word_boundary() {
+    let text = "The best spicy margarita, shaken with jalapeño";
+    assert_eq!(
+        truncate_at_word_boundary(text, 18),
+        "The best spicy\u{2026}"
+    );
+    // Boundary exactly at the limit keeps the whole word; separators are dropped.
+    assert_eq!(
+        truncate_at_word_boundary(text, 24),
+        "The best spicy margarita\u{2026}"
+    );
+    assert_eq!(
+        truncate_at_word_boundary(text, 25),
+        "The best spicy margarita\u{2026}"
+    );
+}
+
+#[test]
+fn test_truncate_is_multibyte_safe() {
+    // Accented words and emoji are counted per char, not per byte.
+    let text = "Crème brûlée façile 🍮🍮 dessert";
+    assert_eq!(truncate_at_word_boundary(text, 14), "Crème brûlée\u{2026}");
+    assert_eq!(
+        truncate_at_word_boundary(text, 22),
+        "Crème brûlée façile 🍮🍮\u{2026}"
+    );
+
+    // A single overlong word is hard-cut, but never between a base char and
+    // its combining accent.
+    let decomposed = "cre\u{0300}me";
+    assert_eq!(truncate_at_word_boundary(decomposed, 3), "cr\u{2026}");
+}
+
+#[test]
+fn test_truncate_em_dash_and_emoji_at_boundary() {
+    let text = "Paloma\u{2014}grapefruit\u{2014}tequila 🍹🍹";
+    for max_chars in 0..=text.chars().count() + 1 {
+        let out = truncate_at_word_boundary(text, max_chars);
+        assert!(
+            out.trim_end_matches('\u{2026}').chars().count() <= max_chars,
+            "{max_chars}: {out:?}"
+        );
+    }
+    assert_eq!(truncate_at_word_boundary(text, 7), "Paloma\u{2014}\u{2026}");
+    // A ZWJ emoji sequence is never split.
+    assert_eq!(truncate_at_word_boundary("👩\u{200D}🍳cook", 1), "\u{2026}");
+}
+
+#[test]
+fn test_parse_iso_duration() {
+    use std::time::Duration;
+
+    assert_eq!(
+        parse_iso_duration("PT1H30M"),
+        Some(Duration::from_secs(90 * 60))
+    );
+    assert_eq!(
+        parse_iso_duration("PT45M"),
+        Some(Duration::from_secs(45 * 60))
+    );
+    assert_eq!(parse_iso_duration(" pt20s "), Some(Duration::from_secs(20)));
+    assert_eq!(
+        parse_iso_duration("PT1.5H"),
+        Some(Duration::from_secs(90 * 60))
+    );
+    assert_eq!(
+        parse_iso_duration("P1DT2H"),
+        Some(Duration::from_secs(26 * 3600))
+    );
+    assert_eq!(
+        parse_iso_duration("P0DT0H10M"),
+        Some(Duration::from_secs(600))
+    );
+
+    for garbage in [
+        "30 minutes",
+        "",
+        "P",
+        "PT",
+        "P1DT",
+        "PT30M1H",
+        "PT-5M",
+        "P1M",
+        "PTxM",
+        "1H30M",
+    ] {
+        assert_eq!(parse_iso_duration(garbage), None, "{garbage}");
+    }
+}
@@ -0,0 +1,35 @@
+/// Options for [`normalize_text_with`](super::normalize_text_with).
+///
+/// `Default` matches [`normalize_text`](super::normalize_text).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NormalizeTextOptions {
+    /// Fold typographic punctuation to ASCII: curly quotes/apostrophes →
+    /// straight, en/em dashes → `-`, ellipsis `…` → `...`.
+    pub ascii_fold: bool,
+    /// Boilerplate lines to drop ("Advertisement", "Jump to Recipe", …):
+    /// a line is removed when its whitespace-normalized text equals one of
+    /// these, case-insensitively. Partial matches inside prose are kept.
+    pub remove_phrases: Vec<String>,
+}
+
+/// Options for [`normalize_urls_with`](super::normalize_urls_with).
+///
+/// `Default` matches [`normalize_urls`](super::normalize_urls).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizeUrlOptions {
+    /// Keep the `#fragment` instead of stripping it — for fragment-routed
+    /// pages (slideshows like `#slide-50`) where it names the target. Kept
+    /// fragments make URLs distinct for deduplication.
+    pub keep_fragment: bool,
+}
+
+/// Options for [`normalize_phones_with`](super::normalize_phones_with).
+///
+/// `Default` matches [`normalize_phones`](super::normalize_phones).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizePhoneOptions {
+    /// Keep a trailing extension ("ext. 123", "x123", "#123") as a canonical
+    /// `;ext=123` suffix instead of stripping it. Numbers that differ only by
+    /// extension then stay distinct for deduplication.
+    pub keep_extension: bool,
+}
@@ -1,10 +1,12 @@
 use once_cell::sync::Lazy;
 /// Helper functions for text normalization
 use regex::Regex;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::Duration;
 use unicode_normalization::UnicodeNormalization;
 use url::Url;
 
+use super::types::{NormalizePhoneOptions, NormalizeUrlOptions};
 use crate::types::SocialPlatform;
 
 // Lazy static regex for whitespace normalization
@@ -34,6 +36,13 @@ static SVG_REGEX: Lazy<Regex> =
 static COMMENT_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?s)<!--.*?-->").expect("valid regex"));
 
+/// A trailing phone extension: "ext. 123", "extension 123", "x123", "#123".
+static PHONE_EXTENSION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\s*(?:ext(?:ension)?\.?|x|#)\s*(\d+)\s*$").expect("valid regex"));
+
+static HEADING_TAG_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<h[1-6](?:\s[^>]*)?>").expect("valid regex"));
+
 static JUNK_ATTR_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
         r#"(?ix)
@@ -97,6 +106,23 @@ pub(super) fn decode_html_entities(text: &str) -> String {
     html_escape::decode_html_entities(text).to_string()
 }
 
+/// Escape text for embedding as HTML element content (inverse of
+/// [`decode_html_entities`]).
+///
+/// Escapes `&`, `<` and `>`; quotes are left as-is. Existing entities are
+/// escaped too, so `&amp;` becomes `&amp;amp;` and decodes back unchanged.
+pub fn escape_html(text: &str) -> String {
+    html_escape::encode_text(text).into_owned()
+}
+
+/// Escape text for embedding in a quoted HTML attribute value (single or
+/// double quotes).
+///
+/// Escapes `&`, `<`, `>`, `"` and `'`.
+pub fn escape_attr(text: &str) -> String {
+    html_escape::encode_quoted_attribute(text).into_owned()
+}
+
 /// Normalize Unicode to NFC (Canonical Composition).
 ///
 /// This ensures consistent representation of characters.
@@ -137,6 +163,72 @@ pub(super) fn remove_control_chars(text: &str) -> String {
         .collect()
 }
 
+/// Fold typographic punctuation to its ASCII equivalent.
+///
+/// - Curly/low/prime quotes → `'` / `"`
+/// - Hyphen, figure/en/em dashes, horizontal bar, minus sign → `-`
+/// - Ellipsis `…` → `...`
+pub(super) fn fold_punctuation_to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => out.push('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => out.push('"'),
+            '\u{2010}'..='\u{2015}' | '\u{2212}' => out.push('-'),
+            '\u{2026}' => out.push_str("..."),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Byte index to cut `text` at for `truncate_at_word_boundary`: the last word
+/// boundary within the first `max_chars` chars — whitespace, or a clause
+/// separator (`,;:.!?`) followed by whitespace — else a hard cut at
+/// `max_chars` backed off so it doesn't split a grapheme.
+pub(super) fn word_boundary_cut(text: &str, max_chars: usize) -> usize {
+    let mut hard_cut = text.len();
+    let mut last_space = None;
+    let mut seen_word = false;
+    let mut chars = text.char_indices().enumerate().peekable();
+    while let Some((i, (idx, c))) = chars.next() {
+        if i > max_chars {
+            break;
+        }
+        if i == max_chars {
+            hard_cut = idx;
+        }
+        let ends_clause = ",;:.!?".contains(c)
+            && chars
+                .peek()
+                .map_or(true, |(_, (_, next))| next.is_whitespace());
+        if seen_word && (c.is_whitespace() || ends_clause) {
+            last_space = Some(idx);
+        }
+        seen_word |= !c.is_whitespace();
+    }
+    if let Some(idx) = last_space {
+        return idx;
+    }
+    // Back off while the cut would separate a base char from what joins it.
+    while hard_cut > 0 {
+        let next = text[hard_cut..].chars().next();
+        let prev = text[..hard_cut].chars().next_back();
+        if next.is_some_and(joins_previous) || prev == Some('\u{200D}') {
+            hard_cut -= prev.map_or(1, char::len_utf8);
+        } else {
+            break;
+        }
+    }
+    hard_cut
+}
+
+/// Chars that render as part of the preceding grapheme.
+fn joins_previous(c: char) -> bool {
+    unicode_normalization::char::canonical_combining_class(c) != 0
+        || matches!(c, '\u{200D}' | '\u{FE00}'..='\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}')
+}
+
 /// Normalize whitespace by collapsing multiple spaces/newlines and trimming.
 ///
 /// - Multiple spaces → single space
@@ -146,6 +238,51 @@ pub(super) fn normalize_whitespace(text: &str) -> String {
     WHITESPACE_REGEX.replace_all(text, " ").trim().to_string()
 }
 
+/// Drop lines whose whitespace-normalized text equals one of `phrases`
+/// (case-insensitive). Runs before whitespace collapsing, while line breaks
+/// still exist.
+pub(super) fn remove_phrase_lines(text: &str, phrases: &[String]) -> String {
+    let phrases: Vec<String> = phrases
+        .iter()
+        .map(|p| normalize_whitespace(p).to_lowercase())
+        .filter(|p| !p.is_empty())
+        .collect();
+    text.lines()
+        .filter(|line| !phrases.contains(&normalize_whitespace(line).to_lowercase()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Title/site-name separators, spaced.
+const TITLE_SEPARATORS: &[&str] = &[" | ", " - ", " \u{2013} ", " \u{2014} "];
+
+/// Most words a trailing title segment may have to be taken for a site name.
+const MAX_SITE_SUFFIX_WORDS: usize = 4;
+
+/// `title` without a trailing `<separator><site>` (case-insensitive).
+pub(super) fn strip_site_suffix<'a>(title: &'a str, site: &str) -> Option<&'a str> {
+    let site = site.to_lowercase();
+    TITLE_SEPARATORS.iter().find_map(|sep| {
+        let (head, tail) = title.rsplit_once(sep)?;
+        let head = head.trim_end();
+        (tail.trim().to_lowercase() == site && !head.is_empty()).then_some(head)
+    })
+}
+
+/// `title` without the segment after its last separator, when that segment is
+/// short enough to be a site name and shorter than the rest.
+pub(super) fn strip_trailing_segment(title: &str) -> Option<&str> {
+    let (head, tail) = TITLE_SEPARATORS
+        .iter()
+        .filter_map(|sep| title.rsplit_once(sep))
+        .max_by_key(|(head, _)| head.len())?;
+    let (head, tail) = (head.trim_end(), tail.trim());
+    let site_like = !tail.is_empty()
+        && tail.split_whitespace().count() <= MAX_SITE_SUFFIX_WORDS
+        && head.chars().count() > tail.chars().count();
+    site_like.then_some(head)
+}
+
 /// Normalize escaped newlines (\\n) to actual newlines (\n).
 pub(super) fn normalize_escaped_newlines(text: &str) -> String {
     NEWLINE_REGEX.replace_all(text, "\n").to_string()
@@ -192,6 +329,27 @@ pub fn normalize_domain(host: &str) -> String {
 /// - `https://example.com/page#section` → `https://example.com/page`
 /// - `https://example.com?utm_source=x&id=7` → `https://example.com?id=7`
 pub fn normalize_url(url: &str) -> String {
+    normalize_url_with(url, &NormalizeUrlOptions::default())
+}
+
+/// [`normalize_url`], then swap the host for its canonical one when it's a key
+/// of `aliases`. Both sides of `aliases` must already be [`normalize_domain`]ed.
+pub(super) fn normalize_url_with_aliases(url: &str, aliases: &HashMap<String, String>) -> String {
+    let normalized = normalize_url(url);
+    let Ok(mut parsed) = Url::parse(&normalized) else {
+        return normalized;
+    };
+    let Some(canonical) = parsed.host_str().and_then(|host| aliases.get(host)) else {
+        return normalized;
+    };
+    if parsed.set_host(Some(canonical)).is_err() {
+        return normalized;
+    }
+    normalize_url(parsed.as_str())
+}
+
+/// [`normalize_url`] with options; `keep_fragment` skips step 7.
+pub fn normalize_url_with(url: &str, options: &NormalizeUrlOptions) -> String {
     // Prepend https:// if protocol is missing (case-insensitive check)
     // Only prepend if it looks like a domain (contains a dot)
     let url_lower = url.to_ascii_lowercase();
@@ -209,7 +367,10 @@ pub fn normalize_url(url: &str) -> String {
         Err(_) => return url.to_string(), // Keep malformed URLs as-is
     };
 
-    // 1. Normalize protocol to https
+    // 1. Normalize protocol to https. `Url::parse` already drops a scheme's
+    // default port (`http://…:80`, `https://…:443`) and `set_scheme` drops one
+    // that becomes the default (`http://…:443`), so only ports like `:8080`
+    // survive.
     let _ = parsed.set_scheme("https");
 
     // 2. Canonicalize domain
@@ -247,12 +408,63 @@ pub fn normalize_url(url: &str) -> String {
     }
 
     // 5. Remove fragment
-    parsed.set_fragment(None);
+    if !options.keep_fragment {
+        parsed.set_fragment(None);
+    }
 
     // url crate adds trailing slash for empty path, strip it
     parsed.to_string().trim_end_matches('/').to_string()
 }
 
+/// Parse an ISO-8601 duration as used by schema.org (`prepTime`, `totalTime`,
+/// `duration`, …).
+///
+/// Accepts weeks and days before the `T`, hours, minutes and seconds after
+/// it, case-insensitively, with fractional values (`PT1.5H`). Years and
+/// months have no fixed length, so they're rejected, as is anything else
+/// that isn't a duration.
+///
+/// Examples:
+/// - `PT1H30M` → 90 minutes
+/// - `PT45M` → 45 minutes
+/// - `P1DT2H` → 26 hours
+/// - `30 minutes` → `None`
+pub fn parse_iso_duration(s: &str) -> Option<Duration> {
+    let s = s.trim().to_ascii_uppercase();
+    let rest = s.strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((_, "")) => return None,
+        Some((date, time)) => (date, time),
+        None => (rest, ""),
+    };
+
+    let mut secs = 0.0;
+    let mut any = false;
+    let parts: [(&str, &[(char, f64)]); 2] = [
+        (date, &[('W', 604_800.0), ('D', 86_400.0)]),
+        (time, &[('H', 3_600.0), ('M', 60.0), ('S', 1.0)]),
+    ];
+    for (mut part, units) in parts {
+        for &(unit, scale) in units {
+            let Some((value, tail)) = part.split_once(unit) else {
+                continue;
+            };
+            let value = value.replace(',', ".");
+            if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                return None;
+            }
+            secs += value.parse::<f64>().ok()? * scale;
+            any = true;
+            part = tail;
+        }
+        if !part.is_empty() {
+            return None;
+        }
+    }
+    any.then(|| Duration::try_from_secs_f64(secs).ok())
+        .flatten()
+}
+
 /// Canonicalize a URL with social-platform awareness — the form the
 /// classify/extract tools and the fetch cache key on.
 ///
@@ -326,6 +538,35 @@ fn is_social_tracker(key: &str) -> bool {
     )
 }
 
+/// Minimum token-set (Jaccard) similarity for two titles to count as the same item.
+const NEAR_DUPLICATE_TITLE_SIMILARITY: f64 = 0.8;
+
+/// Canonical host of a URL (`normalize_domain`), or empty when unparseable.
+pub(super) fn url_host(url: &str) -> String {
+    Url::parse(&normalize_url(url))
+        .ok()
+        .and_then(|u| u.host_str().map(normalize_domain))
+        .unwrap_or_default()
+}
+
+/// Lowercased alphanumeric word set of a title (`’`/`'` folded away so
+/// "Dragon's" and "Dragons" agree).
+pub(super) fn title_tokens(title: &str) -> BTreeSet<String> {
+    title
+        .to_lowercase()
+        .replace(['\'', '\u{2019}'], "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether two title token sets are near-identical (Jaccard ≥ 0.8).
+pub(super) fn is_near_duplicate_title(a: &BTreeSet<String>, b: &BTreeSet<String>) -> bool {
+    let union = a.union(b).count();
+    union > 0 && a.intersection(b).count() as f64 / union as f64 >= NEAR_DUPLICATE_TITLE_SIMILARITY
+}
+
 /// Normalize a single email address.
 ///
 /// Performs:
@@ -343,8 +584,8 @@ pub(super) fn normalize_email(email: &str) -> String {
 
     // Extract from display name format: "Name" <email@example.com> or Name <email@example.com>
     if let Some(start) = result.find('<') {
-        if let Some(end) = result.find('>') {
-            result = result[start + 1..end].to_string();
+        if let Some(len) = result[start + 1..].find('>') {
+            result = result[start + 1..start + 1 + len].to_string();
         }
     }
 
@@ -408,16 +649,30 @@ pub(super) fn normalize_email(email: &str) -> String {
 /// 3. Keep international prefix (+) if present
 /// 4. Strip all other non-digit characters except leading +
 pub(super) fn normalize_phone(phone: &str) -> String {
+    normalize_phone_with(phone, &NormalizePhoneOptions::default())
+}
+
+/// [`normalize_phone`] with options; `keep_extension` turns step 2 into a
+/// `;ext=123` suffix on the normalized number.
+pub(super) fn normalize_phone_with(phone: &str, options: &NormalizePhoneOptions) -> String {
+    if options.keep_extension {
+        if let Some(cap) = PHONE_EXTENSION_REGEX.captures(phone) {
+            let number = &phone[..cap.get(0).expect("whole match").start()];
+            return format!("{};ext={}", normalize_phone(number), &cap[1]);
+        }
+    }
+
     let mut result = phone.trim().to_string();
 
-    // Strip extension patterns
-    if let Some(pos) = result
-        .to_lowercase()
+    // Strip extension patterns. ASCII lowercasing keeps byte offsets valid
+    // for `result` (full Unicode lowercasing can change lengths: `İ` → `i̇`).
+    let lower = result.to_ascii_lowercase();
+    if let Some(pos) = lower
         .find(" ext")
-        .or_else(|| result.to_lowercase().find(" x"))
-        .or_else(|| result.to_lowercase().find(" extension"))
+        .or_else(|| lower.find(" x"))
+        .or_else(|| lower.find(" extension"))
     {
-        result = result[..pos].to_string();
+        result.truncate(pos);
     }
 
     // Keep international prefix, strip all other non-digits
@@ -454,9 +709,30 @@ pub(super) fn strip_junk(html: &str) -> String {
     normalized_html = IFRAME_REGEX.replace_all(&normalized_html, "").to_string();
     normalized_html = SVG_REGEX.replace_all(&normalized_html, "").to_string();
     normalized_html = COMMENT_REGEX.replace_all(&normalized_html, "").to_string();
-    normalized_html = JUNK_ATTR_REGEX
-        .replace_all(&normalized_html, "")
-        .to_string();
+    strip_junk_attrs(&normalized_html)
+}
 
-    normalized_html
+/// Strip [`JUNK_ATTR_REGEX`] attributes, except `id` on `<h1>`–`<h6>` start
+/// tags: heading ids are in-page anchor targets (`#ingredients`) that deep
+/// links into cleaned content rely on.
+fn strip_junk_attrs(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut last = 0;
+    for heading in HEADING_TAG_REGEX.find_iter(html) {
+        out.push_str(&JUNK_ATTR_REGEX.replace_all(&html[last..heading.start()], ""));
+        out.push_str(
+            &JUNK_ATTR_REGEX.replace_all(heading.as_str(), |attr: &regex::Captures| {
+                let full = &attr[0];
+                let name = full.split('=').next().unwrap_or_default().trim();
+                if name.eq_ignore_ascii_case("id") {
+                    full.to_string()
+                } else {
+                    String::new()
+                }
+            }),
+        );
+        last = heading.end();
+    }
+    out.push_str(&JUNK_ATTR_REGEX.replace_all(&html[last..], ""));
+    out
 }
@@ -0,0 +1,99 @@
+//! Parse Cache
+//!
+//! Per-pipeline LRU of scrape results, keyed by the page HTML.
+//!
+//! `scraper::Html` isn't `Send`, so the parsed DOM can't be shared between
+//! the blocking threads the tools parse on. The cache holds what the tools
+//! return instead — body, metadata and the unified schema view — filled from
+//! a single parse on the first miss.
+
+use std::sync::{Arc, Mutex};
+
+use crate::types::{Jsonld, Metadata};
+
+/// `(body, metadata, schema)`, as returned by `scrape_all`.
+pub(super) type Scraped = (String, Metadata, Jsonld);
+
+/// A cached page: the HTML's hash (checked first, to skip most string
+/// compares), the HTML itself (so a hash collision never aliases two pages)
+/// and its scrape.
+struct Entry {
+    hash: u64,
+    html: String,
+    scraped: Arc<Scraped>,
+}
+
+/// Bounded cache of scrape results, created by
+/// [`Context::with_parse_cache`](crate::types::Context::with_parse_cache) and
+/// shared by that context's clones — so one pipeline's capacity never evicts
+/// another's pages.
+pub struct ParseCache {
+    capacity: usize,
+    /// Least recently used first.
+    entries: Mutex<Vec<Entry>>,
+    /// Pages parsed to fill the cache, for tests asserting hits skip the parse.
+    #[cfg(test)]
+    pub(super) parses: std::sync::atomic::AtomicUsize,
+}
+
+impl std::fmt::Debug for ParseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let len = self.entries.lock().map_or(0, |entries| entries.len());
+        f.debug_struct("ParseCache")
+            .field("capacity", &self.capacity)
+            .field("len", &len)
+            .finish()
+    }
+}
+
+impl ParseCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(Vec::new()),
+            #[cfg(test)]
+            parses: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    /// The cached scrape of `html`, parsing it on a miss. Entries beyond the
+    /// capacity are evicted, least recently used first.
+    fn scraped(&self, html: &str) -> Arc<Scraped> {
+        let hash = crate::hash::fnv1a(html.as_bytes());
+        let is_page = |entry: &Entry| entry.hash == hash && entry.html == html;
+        {
+            let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(pos) = entries.iter().position(is_page) {
+                let entry = entries.remove(pos);
+                let scraped = entry.scraped.clone();
+                entries.push(entry);
+                return scraped;
+            }
+        }
+
+        // Parse without holding the lock; a concurrent miss on the same page
+        // just parses it twice.
+        #[cfg(test)]
+        self.parses
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let scraped = Arc::new(super::utils::scrape_from_doc(
+            &scraper::Html::parse_document(html),
+        ));
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|entry| !is_page(entry));
+        entries.push(Entry {
+            hash,
+            html: html.to_string(),
+            scraped: scraped.clone(),
+        });
+        let excess = entries.len().saturating_sub(self.capacity);
+        entries.drain(..excess);
+        scraped
+    }
+}
+
+/// The scrape of `html` from `cache`; `None` when the pipeline has no cache.
+pub(super) fn scraped(html: &str, cache: Option<&ParseCache>) -> Option<Arc<Scraped>> {
+    cache.map(|cache| cache.scraped(html))
+}
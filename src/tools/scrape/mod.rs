@@ -1,15 +1,26 @@
 //! Scrape Tools
 
-use crate::types::{Html, Jsonld, Metadata};
+use crate::types::{get_parse_cache, Html, Jsonld, Metadata};
 
+mod cache;
 mod tests;
+pub mod types;
 mod utils;
 
+pub use cache::ParseCache;
+pub use types::ImageInfo;
+
 /// Scrape body content from HTML.
+///
+/// Like [`scrape_metadata`], [`scrape_jsonld`], [`scrape_schema_type`] and
+/// [`scrape_all`], served from the parse cache when the pipeline enables it
+/// ([`Context::with_parse_cache`](crate::types::Context::with_parse_cache)).
 pub async fn scrape_body(html: &Html) -> String {
     let html = html.to_string();
-    tokio::task::spawn_blocking(move || {
-        utils::scrape_body_from_doc(&scraper::Html::parse_document(&html))
+    let cache = get_parse_cache();
+    tokio::task::spawn_blocking(move || match cache::scraped(&html, cache.as_deref()) {
+        Some(scraped) => scraped.0.clone(),
+        None => utils::scrape_body_from_doc(&scraper::Html::parse_document(&html)),
     })
     .await
     .expect("scrape_body: spawn_blocking failed")
@@ -29,18 +40,87 @@ pub async fn scrape_body(html: &Html) -> String {
 /// (page-level RDFa `<meta>` is left to [`scrape_metadata`]).
 pub async fn scrape_jsonld(html: &Html) -> Jsonld {
     let html = html.to_string();
-    tokio::task::spawn_blocking(move || {
-        utils::scrape_jsonld_from_doc(&scraper::Html::parse_document(&html))
+    let cache = get_parse_cache();
+    tokio::task::spawn_blocking(move || match cache::scraped(&html, cache.as_deref()) {
+        Some(scraped) => scraped.2.clone(),
+        None => utils::scrape_jsonld_from_doc(&scraper::Html::parse_document(&html)),
     })
     .await
     .expect("scrape_jsonld: spawn_blocking failed")
 }
 
+/// Scrape every schema.org entity of one `@type` (e.g. `"Recipe"`), whatever
+/// its encoding — JSON-LD (including `@graph`), Microdata, RDFa or
+/// Microformats2 — via the unified [`scrape_jsonld`] view.
+///
+/// Matches the short type name case-insensitively against any entry of a
+/// multi-typed `@type`; IRIs are accepted (`https://schema.org/Recipe`).
+pub async fn scrape_schema_type(html: &Html, ty: &str) -> Jsonld {
+    let html = html.to_string();
+    let ty = ty.to_string();
+    let cache = get_parse_cache();
+    tokio::task::spawn_blocking(move || {
+        let items = match cache::scraped(&html, cache.as_deref()) {
+            Some(scraped) => scraped.2.clone(),
+            None => utils::scrape_jsonld_from_doc(&scraper::Html::parse_document(&html)),
+        };
+        utils::filter_schema_type(items, &ty)
+    })
+    .await
+    .expect("scrape_schema_type: spawn_blocking failed")
+}
+
+/// Scrape JSON state embedded in inline `<script>`s by JS frameworks, as
+/// `(source id, value)` pairs in document order.
+///
+/// - `<script type="application/json" id="…">` — Next.js `__NEXT_DATA__`,
+///   Nuxt 3 `__NUXT_DATA__`, … — keyed by its `id`
+/// - `window.X = {…}` / `window["X"] = […]` assignments (`__NUXT__`,
+///   `__INITIAL_STATE__`, `__APOLLO_STATE__`, …) — keyed by `X`; the literal
+///   is cut out by bracket matching, so trailing code is ignored
+///
+/// Values that aren't valid JSON (JS object literals, Nuxt 2's IIFE) are
+/// skipped. JSON-LD is left to [`scrape_jsonld`].
+pub async fn scrape_embedded_json(html: &Html) -> Vec<(String, serde_json::Value)> {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || {
+        utils::scrape_embedded_json_from_doc(&scraper::Html::parse_document(&html))
+    })
+    .await
+    .expect("scrape_embedded_json: spawn_blocking failed")
+}
+
+/// Scrape every `<img>` with its alt text and best-guess natural dimensions.
+///
+/// Dimensions come from `width`/`height` attributes (`px` suffixes accepted,
+/// percentages ignored) and `srcset` `w` descriptors; missing ones stay `None`.
+/// Images without any source are skipped. URLs are kept raw (no base-URL
+/// resolution).
+pub async fn scrape_images(html: &Html) -> Vec<ImageInfo> {
+    let html = html.to_string();
+    tokio::task::spawn_blocking(move || {
+        utils::scrape_images_from_doc(&scraper::Html::parse_document(&html))
+    })
+    .await
+    .expect("scrape_images: spawn_blocking failed")
+}
+
+/// Pick the highest-resolution candidate URL from a `srcset`.
+///
+/// The largest `w` descriptor wins; failing that, the largest `x` density
+/// (candidates without a descriptor count as `1x`); with no descriptors at
+/// all, the last candidate. `None` for an empty `srcset`.
+pub fn best_srcset_url(srcset: &str) -> Option<String> {
+    utils::best_srcset_candidate(&utils::parse_srcset(srcset))
+}
+
 /// Scrape metadata tags from HTML.
 pub async fn scrape_metadata(html: &Html) -> Metadata {
     let html = html.to_string();
-    tokio::task::spawn_blocking(move || {
-        utils::scrape_metadata_from_doc(&scraper::Html::parse_document(&html))
+    let cache = get_parse_cache();
+    tokio::task::spawn_blocking(move || match cache::scraped(&html, cache.as_deref()) {
+        Some(scraped) => scraped.1.clone(),
+        None => utils::scrape_metadata_from_doc(&scraper::Html::parse_document(&html)),
     })
     .await
     .expect("scrape_metadata: spawn_blocking failed")
@@ -53,8 +133,10 @@ pub async fn scrape_metadata(html: &Html) -> Metadata {
 /// times).
 pub async fn scrape_all(html: &Html) -> (String, Metadata, Jsonld) {
     let html = html.to_string();
-    tokio::task::spawn_blocking(move || {
-        utils::scrape_from_doc(&scraper::Html::parse_document(&html))
+    let cache = get_parse_cache();
+    tokio::task::spawn_blocking(move || match cache::scraped(&html, cache.as_deref()) {
+        Some(scraped) => (*scraped).clone(),
+        None => utils::scrape_from_doc(&scraper::Html::parse_document(&html)),
     })
     .await
     .expect("scrape_all: spawn_blocking failed")
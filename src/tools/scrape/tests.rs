@@ -763,3 +763,275 @@ async fn scrape_jsonld_merges_native_and_mf2() {
         "h-card Person unified in: {schema:#?}"
     );
 }
+
+#[tokio::test]
+async fn scrape_schema_type_collects_across_encodings() {
+    let html = r#"
+        <html><head>
+          <script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+              {"@type": "WebPage", "name": "Drinks"},
+              {"@type": ["Recipe", "NewsArticle"], "name": "Spicy Margarita"}
+            ]}
+          </script>
+        </head><body>
+          <div itemscope itemtype="https://schema.org/Recipe">
+            <h2 itemprop="name">Paloma</h2>
+            <span itemprop="recipeYield">1 drink</span>
+          </div>
+        </body></html>
+    "#;
+
+    let recipes = scrape_schema_type(&html.into(), "Recipe").await;
+    let names: Vec<&str> = recipes.iter().filter_map(|r| r["name"].as_str()).collect();
+    assert_eq!(names, vec!["Spicy Margarita", "Paloma"]);
+
+    let iri = scrape_schema_type(&html.into(), "https://schema.org/recipe").await;
+    assert_eq!(iri, recipes);
+}
+
+#[tokio::test]
+async fn scrape_images_dimensions_and_aspect_ratio() {
+    let html = r#"
+        <html><body>
+          <img src="/hero.jpg" alt="Spicy margarita" width="1200" height="800px">
+          <img src="data:image/gif;base64,R0lG" data-src="/lazy.jpg"
+               srcset="/lazy-320.jpg 320w, /lazy-960.jpg 960w" width="320" height="180">
+          <img src="/fluid.png" width="100%">
+          <img alt="no source">
+        </body></html>
+    "#;
+
+    let images = scrape_images(&html.into()).await;
+    assert_eq!(images.len(), 3);
+
+    assert_eq!(images[0].src, "/hero.jpg");
+    assert_eq!(images[0].alt.as_deref(), Some("Spicy margarita"));
+    assert_eq!((images[0].width, images[0].height), (Some(1200), Some(800)));
+    assert_eq!(images[0].aspect_ratio, Some(1.5));
+
+    // Natural size from the largest srcset width, height scaled by the attributes' ratio.
+    assert_eq!(images[1].src, "/lazy.jpg");
+    assert_eq!((images[1].width, images[1].height), (Some(960), Some(540)));
+    assert!((images[1].aspect_ratio.unwrap() - 16.0 / 9.0).abs() < 1e-9);
+
+    assert_eq!(
+        (images[2].width, images[2].height, images[2].aspect_ratio),
+        (None, None, None)
+    );
+}
+
+#[test]
+fn parse_srcset_keeps_commas_inside_urls() {
+    use super::utils::{parse_srcset, SrcsetDescriptor};
+
+    let candidates = parse_srcset("/img/w_300,h_200/a.jpg 300w, /img/w_600,h_400/a.jpg 2x");
+    assert_eq!(
+        candidates,
+        vec![
+            (
+                "/img/w_300,h_200/a.jpg".to_string(),
+                Some(SrcsetDescriptor::Width(300))
+            ),
+            (
+                "/img/w_600,h_400/a.jpg".to_string(),
+                Some(SrcsetDescriptor::Density(2.0))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn best_srcset_url_prefers_largest_width() {
+    assert_eq!(
+        best_srcset_url("/thumb-150.jpg 150w, /thumb-1200.jpg 1200w, /thumb-600.jpg 600w"),
+        Some("/thumb-1200.jpg".to_string())
+    );
+    // Width descriptors beat densities when mixed.
+    assert_eq!(
+        best_srcset_url("/a.jpg 3x, /b.jpg 800w"),
+        Some("/b.jpg".to_string())
+    );
+}
+
+#[test]
+fn best_srcset_url_prefers_largest_density_then_last() {
+    assert_eq!(
+        best_srcset_url("/img.jpg, /img@3x.jpg 3x, /img@2x.jpg 2x"),
+        Some("/img@3x.jpg".to_string())
+    );
+    // A bare candidate is 1x, so it loses to 1.5x.
+    assert_eq!(
+        best_srcset_url("/img.jpg, /img-hd.jpg 1.5x"),
+        Some("/img-hd.jpg".to_string())
+    );
+    assert_eq!(
+        best_srcset_url("/small.jpg, /large.jpg"),
+        Some("/large.jpg".to_string())
+    );
+    assert_eq!(best_srcset_url("  "), None);
+}
+
+#[tokio::test]
+async fn scrape_embedded_json_next_data_and_window_state() {
+    let html = r#"
+        <html><head>
+            <script type="application/ld+json">{"@type": "Recipe", "name": "LD"}</script>
+            <script id="__NEXT_DATA__" type="application/json">
+                {"props": {"pageProps": {"recipe": {"title": "Birria Tacos", "tags": ["[mexican]"]}}},
+                 "page": "/recipes/[slug]"}
+            </script>
+            <script src="/app.js"></script>
+        </head><body>
+            <script>
+                window.__INITIAL_STATE__ = {"user": null, "note": "a } in a string"};
+                window["__APOLLO_STATE__"]=[1, 2];
+                if (window.flag == true) { window.__NUXT__ = (function(a){return {a:a}})(1); }
+            </script>
+        </body></html>
+    "#;
+
+    let found = scrape_embedded_json(&html.into()).await;
+    let ids: Vec<&str> = found.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec!["__NEXT_DATA__", "__INITIAL_STATE__", "__APOLLO_STATE__"]
+    );
+
+    assert_eq!(
+        found[0].1["props"]["pageProps"]["recipe"]["title"],
+        "Birria Tacos"
+    );
+    assert_eq!(found[0].1["page"], "/recipes/[slug]");
+    assert_eq!(
+        found[1].1,
+        serde_json::json!({"user": null, "note": "a } in a string"})
+    );
+    assert_eq!(found[2].1, serde_json::json!([1, 2]));
+}
+
+#[tokio::test]
+async fn scrape_embedded_json_multibyte_after_assignment() {
+    let html = "<script>window.x=ñ; window.y=\u{a0}{\"ok\": true};</script>";
+
+    let found = scrape_embedded_json(&html.into()).await;
+    assert_eq!(found, vec![("y".to_string(), serde_json::json!({"ok": true}))]);
+}
+
+#[tokio::test]
+async fn scrape_jsonld_repairs_or_skips_malformed_blocks() {
+    let html = r#"
+        <script type="application/ld+json">{"@type": "Organization", "name": "Acme"}</script>
+        <script type="application/ld+json">
+            {"@type": "Recipe", "name": "Chili", "recipeIngredient": ["beans", "beef",],}
+        </script>
+        <script type="application/ld+json">{&quot;@type&quot;: &quot;Person&quot;, &quot;name&quot;: &quot;Ann&quot;}</script>
+        <script type="application/ld+json">
+            //<![CDATA[
+            {"@type": "WebSite", "name": "Two
+            lines"}
+            //]]>
+        </script>
+        <script type="application/ld+json">{"@type": "Event", "name": </script>
+    "#;
+
+    let jsonld = scrape_jsonld(&html.into()).await;
+    let names: Vec<(&str, &str)> = jsonld
+        .iter()
+        .filter_map(|v| Some((v["@type"].as_str()?, v["name"].as_str()?)))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("Organization", "Acme"),
+            ("Recipe", "Chili"),
+            ("Person", "Ann"),
+            ("WebSite", "Two\n            lines"),
+        ]
+    );
+    assert_eq!(
+        jsonld[1]["recipeIngredient"],
+        serde_json::json!(["beans", "beef"])
+    );
+}
+
+#[tokio::test]
+async fn scrape_jsonld_expands_multiple_objects_in_one_block() {
+    let html = r#"
+        <script type="application/ld+json">
+            [{"@type": "Organization", "name": "Acme"}, {"@type": "WebSite", "name": "Acme Eats"}]
+        </script>
+        <script type="application/ld+json">{"@type": "Recipe", "name": "Chili"}</script>
+        <script type="application/ld+json">
+            {"@type": "Person", "name": "Ann"}
+            {"@type": "Person", "name": "Bo"}
+        </script>
+        <script type="application/ld+json">
+            {"@type": "Event", "name": "Launch"}, {"@type": "Place", "name": "Hall"}
+        </script>
+    "#;
+
+    let jsonld = scrape_jsonld(&html.into()).await;
+    let names: Vec<&str> = jsonld.iter().filter_map(|v| v["name"].as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["Acme", "Acme Eats", "Chili", "Ann", "Bo", "Launch", "Hall"]
+    );
+    assert!(jsonld.iter().all(|v| v.is_object()));
+}
+
+#[tokio::test]
+async fn test_parse_cache_hit_skips_reparse() {
+    use crate::types::{Context, CTX};
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    let page = |n: usize| -> Html {
+        format!(
+            r#"<html><head><title>Page {n}</title>
+            <script type="application/ld+json">{{"@type": "Recipe", "name": "Recipe {n}"}}</script>
+            </head><body><p>Body {n}</p></body></html>"#
+        )
+        .into()
+    };
+    let (first, second) = (page(1), page(2));
+    let uncached = scrape_all(&first).await;
+
+    let ctx = Arc::new(Context::fast().with_parse_cache(1));
+    let cache = ctx.parse_cache.clone().unwrap();
+    let parses = || cache.parses.load(Ordering::SeqCst);
+    let passes = async {
+        let metadata = scrape_metadata(&first).await;
+        let jsonld = scrape_jsonld(&first).await;
+        let body = scrape_body(&first).await;
+        let recipes = scrape_schema_type(&first, "Recipe").await;
+        assert_eq!((body, metadata, jsonld), uncached);
+        assert_eq!(recipes.len(), 1);
+        assert_eq!(scrape_all(&first).await, uncached);
+        // One parse for all five passes.
+        assert_eq!(parses(), 1);
+
+        // Capacity 1: a second page evicts the first.
+        scrape_body(&second).await;
+        scrape_body(&first).await;
+        assert_eq!(parses(), 3);
+    };
+    CTX.scope(ctx.clone(), passes).await;
+
+    // Another pipeline's pages don't evict this one's.
+    let other = Arc::new(Context::fast().with_parse_cache(1));
+    CTX.scope(other, async {
+        scrape_body(&second).await;
+    })
+    .await;
+    CTX.scope(ctx, async {
+        scrape_body(&first).await;
+    })
+    .await;
+    assert_eq!(parses(), 3);
+
+    // Off by default.
+    assert!(Context::fast().parse_cache.is_none());
+    assert!(Context::fast().with_parse_cache(0).parse_cache.is_none());
+    assert_eq!(scrape_all(&first).await, uncached);
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// An `<img>` on the page.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageInfo {
+    /// `src` (or lazy-load `data-src`, else the first `srcset` candidate), as written.
+    pub src: String,
+    pub alt: Option<String>,
+    /// Best-guess natural width: the largest `srcset` `w` descriptor, else the
+    /// `width` attribute.
+    pub width: Option<u32>,
+    /// Best-guess natural height: the `height` attribute, scaled to a
+    /// `srcset`-derived width when the attributes give the ratio.
+    pub height: Option<u32>,
+    /// `width / height` when both are known.
+    pub aspect_ratio: Option<f64>,
+}
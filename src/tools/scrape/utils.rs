@@ -1,6 +1,11 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::types::ImageInfo;
 use crate::selectors::{
-    BODY_SELECTOR, CLASS_SELECTOR, HTML_LANG_SELECTOR, JSONLD_SELECTOR, LI_SELECTOR, META_SELECTOR,
-    MICRODATA_SELECTOR, P_SELECTOR, RDFA_SELECTOR, TITLE_SELECTOR,
+    BODY_SELECTOR, CLASS_SELECTOR, HTML_LANG_SELECTOR, IMG_SELECTOR, INLINE_SCRIPT_SELECTOR,
+    JSONLD_SELECTOR, LI_SELECTOR, META_SELECTOR, MICRODATA_SELECTOR, P_SELECTOR, RDFA_SELECTOR,
+    TITLE_SELECTOR,
 };
 use crate::types::{Jsonld, Metadata, Microformats};
 
@@ -18,7 +23,7 @@ pub(super) fn scrape_body_from_doc(document: &scraper::Html) -> String {
 pub(super) fn scrape_jsonld_from_doc(document: &scraper::Html) -> Jsonld {
     let mut items: Jsonld = document
         .select(&JSONLD_SELECTOR)
-        .filter_map(|el| serde_json::from_str(&el.text().collect::<String>()).ok())
+        .filter_map(|el| parse_jsonld_block(&el.text().collect::<String>()))
         .flat_map(flatten_jsonld)
         .collect();
     merge_schema_entities(&mut items, scrape_microdata_from_doc(document));
@@ -30,6 +35,91 @@ pub(super) fn scrape_jsonld_from_doc(document: &scraper::Html) -> Jsonld {
     items
 }
 
+/// `window.X =` / `window["X"] =` assignment heads in inline scripts.
+static WINDOW_ASSIGNMENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"window\s*(?:\.\s*([A-Za-z_$][\w$]*)|\[\s*["']([^"']+)["']\s*\])\s*="#)
+        .expect("valid window assignment regex")
+});
+
+/// Framework state from inline scripts: `application/json` scripts keyed by
+/// `id`, and JSON-valued `window.X = …` assignments keyed by `X`.
+pub(super) fn scrape_embedded_json_from_doc(
+    document: &scraper::Html,
+) -> Vec<(String, serde_json::Value)> {
+    let mut found = Vec::new();
+    for script in document.select(&INLINE_SCRIPT_SELECTOR) {
+        let text: String = script.text().collect();
+        let ty = script.value().attr("type").unwrap_or("").trim();
+
+        if ty.eq_ignore_ascii_case("application/json") {
+            let id = script.value().id().unwrap_or("").trim();
+            if id.is_empty() {
+                continue;
+            }
+            if let Ok(value) = serde_json::from_str(text.trim()) {
+                found.push((id.to_string(), value));
+            }
+            continue;
+        }
+        if !ty.is_empty() && !ty.to_ascii_lowercase().contains("javascript") {
+            continue;
+        }
+
+        for caps in WINDOW_ASSIGNMENT_REGEX.captures_iter(&text) {
+            let Some(name) = caps.get(1).or_else(|| caps.get(2)) else {
+                continue;
+            };
+            let head = caps.get(0).expect("regex match has group 0");
+            let rest = &text[head.end()..];
+            // `==` / `===` is a comparison, not an assignment.
+            if rest.starts_with('=') {
+                continue;
+            }
+            let rest = rest.trim_start();
+            let value = json_literal_prefix(rest).and_then(|lit| serde_json::from_str(lit).ok());
+            if let Some(value) = value {
+                found.push((name.as_str().to_string(), value));
+            }
+        }
+    }
+    found
+}
+
+/// The leading `{…}` / `[…]` of `text`, matched by bracket depth (brackets
+/// inside JSON strings don't count). `None` if it doesn't start with one or
+/// never closes.
+fn json_literal_prefix(text: &str) -> Option<&str> {
+    if !text.starts_with(['{', '[']) {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[..=i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Everything from one parse: body HTML, metadata, and the unified schema view.
 pub(super) fn scrape_from_doc(document: &scraper::Html) -> (String, Metadata, Jsonld) {
     (
@@ -39,6 +129,100 @@ pub(super) fn scrape_from_doc(document: &scraper::Html) -> (String, Metadata, Js
     )
 }
 
+/// Parse one JSON-LD `<script>` body, repairing common CMS breakage when the
+/// strict parse fails: `<!-- -->` / CDATA wrappers, trailing commas, raw
+/// newlines/tabs inside strings, and an HTML-entity-escaped block
+/// (`{&quot;@type&quot;: …}`). Several top-level values in one block —
+/// concatenated (`{…}{…}`) or comma-separated (`{…}, {…}`) — come back as an
+/// array. `None` skips just this block.
+fn parse_jsonld_block(text: &str) -> Option<serde_json::Value> {
+    if let Some(value) = parse_json_sequence(text) {
+        return Some(value);
+    }
+    let unwrapped = strip_script_wrappers(text);
+    let repaired = repair_json(unwrapped);
+    parse_json_sequence(&repaired)
+        .or_else(|| {
+            parse_json_sequence(&repair_json(&html_escape::decode_html_entities(unwrapped)))
+        })
+        .or_else(|| serde_json::from_str(&format!("[{repaired}]")).ok())
+}
+
+/// One or more whitespace-separated JSON values; several become an array.
+fn parse_json_sequence(text: &str) -> Option<serde_json::Value> {
+    let mut values = serde_json::Deserializer::from_str(text)
+        .into_iter::<serde_json::Value>()
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    match values.len() {
+        0 => None,
+        1 => values.pop(),
+        _ => Some(serde_json::Value::Array(values)),
+    }
+}
+
+/// Strip the `<!-- … -->` and `<![CDATA[ … ]]>` wrappers old templates put
+/// around script bodies (optionally behind `//`).
+fn strip_script_wrappers(text: &str) -> &str {
+    let mut text = text.trim();
+    for (open, close) in [("<!--", "-->"), ("<![CDATA[", "]]>")] {
+        let head = text.trim_start_matches("//").trim_start();
+        if let Some(inner) = head.strip_prefix(open) {
+            text = inner.trim();
+            if let Some(inner) = text.strip_suffix(close) {
+                text = inner.trim_end().trim_end_matches("//").trim_end();
+            }
+        }
+    }
+    text
+}
+
+/// Drop trailing commas before `}` / `]` and escape raw control characters
+/// inside strings — string contents are otherwise left untouched.
+fn repair_json(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    let mut escaped = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            match c {
+                _ if escaped => {
+                    escaped = false;
+                    out.push(c);
+                }
+                '\\' => {
+                    escaped = true;
+                    out.push(c);
+                }
+                '"' => {
+                    in_string = false;
+                    out.push(c);
+                }
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                _ => out.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            ',' => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 fn flatten_jsonld(value: serde_json::Value) -> Vec<serde_json::Value> {
     match value {
         serde_json::Value::Array(arr) => arr.into_iter().flat_map(flatten_jsonld).collect(),
@@ -85,6 +269,167 @@ pub(super) fn scrape_metadata_from_doc(document: &scraper::Html) -> Metadata {
     tags
 }
 
+// ---------------------------------------------------------------------------
+// Images
+// ---------------------------------------------------------------------------
+
+/// A `srcset` descriptor: `640w` (width) or `2x` (pixel density).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum SrcsetDescriptor {
+    Width(u32),
+    Density(f64),
+}
+
+/// Parse a `srcset` into `(url, descriptor)` candidates, in order. Candidates
+/// without a (valid) descriptor get `None` (implicitly `1x`).
+pub(super) fn parse_srcset(srcset: &str) -> Vec<(String, Option<SrcsetDescriptor>)> {
+    // Per the HTML spec, a URL is a run of non-whitespace, so commas inside it
+    // (CDN transforms like `w_300,h_200`) are kept; only trailing commas end a
+    // candidate. Descriptors then run up to the next comma.
+    let mut candidates = Vec::new();
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+        let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (raw_url, after) = rest.split_at(url_end);
+        let url = raw_url.trim_end_matches(',');
+        let descriptor = if url.len() < raw_url.len() {
+            rest = after;
+            None
+        } else {
+            let desc_end = after.find(',').unwrap_or(after.len());
+            let (descriptors, after) = after.split_at(desc_end);
+            rest = after;
+            descriptors
+                .split_whitespace()
+                .next()
+                .and_then(parse_srcset_descriptor)
+        };
+        candidates.push((url.to_string(), descriptor));
+    }
+    candidates
+}
+
+fn parse_srcset_descriptor(descriptor: &str) -> Option<SrcsetDescriptor> {
+    if let Some(w) = descriptor.strip_suffix(['w', 'W']) {
+        w.parse().ok().map(SrcsetDescriptor::Width)
+    } else if let Some(x) = descriptor.strip_suffix(['x', 'X']) {
+        x.parse().ok().map(SrcsetDescriptor::Density)
+    } else {
+        None
+    }
+}
+
+pub(super) fn best_srcset_candidate(
+    candidates: &[(String, Option<SrcsetDescriptor>)],
+) -> Option<String> {
+    let widest = candidates
+        .iter()
+        .filter_map(|(url, d)| match d {
+            Some(SrcsetDescriptor::Width(w)) => Some((url, *w)),
+            _ => None,
+        })
+        .fold(None, |best: Option<(&String, u32)>, (url, w)| match best {
+            Some((_, best_w)) if best_w >= w => best,
+            _ => Some((url, w)),
+        });
+    if let Some((url, _)) = widest {
+        return Some(url.clone());
+    }
+
+    if candidates
+        .iter()
+        .any(|(_, d)| matches!(d, Some(SrcsetDescriptor::Density(_))))
+    {
+        let density = |d: &Option<SrcsetDescriptor>| match d {
+            Some(SrcsetDescriptor::Density(x)) => *x,
+            _ => 1.0,
+        };
+        return candidates
+            .iter()
+            .fold(
+                None,
+                |best: Option<&(String, Option<SrcsetDescriptor>)>, c| match best {
+                    Some(b) if density(&b.1) >= density(&c.1) => best,
+                    _ => Some(c),
+                },
+            )
+            .map(|(url, _)| url.clone());
+    }
+
+    candidates.last().map(|(url, _)| url.clone())
+}
+
+/// A dimension attribute as whole pixels: `"600"`, `"600px"`, `"600.5"`;
+/// percentages, zero and garbage are `None`.
+fn parse_dimension(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let value = value.strip_suffix("px").unwrap_or(value).trim();
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 1.0)
+        .map(|n| n.round() as u32)
+}
+
+pub(super) fn scrape_images_from_doc(document: &scraper::Html) -> Vec<ImageInfo> {
+    document
+        .select(&IMG_SELECTOR)
+        .filter_map(|img| {
+            let attr = |name: &str| {
+                img.value()
+                    .attr(name)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+            };
+            let srcset = attr("srcset")
+                .or_else(|| attr("data-srcset"))
+                .map(parse_srcset)
+                .unwrap_or_default();
+            let src = attr("src")
+                .filter(|s| !s.starts_with("data:"))
+                .or_else(|| attr("data-src"))
+                .map(str::to_string)
+                .or_else(|| srcset.first().map(|(url, _)| url.clone()))?;
+
+            let attr_width = attr("width").and_then(parse_dimension);
+            let attr_height = attr("height").and_then(parse_dimension);
+            let srcset_width = srcset
+                .iter()
+                .filter_map(|(_, d)| match d {
+                    Some(SrcsetDescriptor::Width(w)) => Some(*w),
+                    _ => None,
+                })
+                .max();
+
+            let width = srcset_width.or(attr_width);
+            let height = match (srcset_width, attr_width, attr_height) {
+                (Some(natural), Some(w), Some(h)) => {
+                    Some((natural as f64 * h as f64 / w as f64).round() as u32)
+                }
+                (_, _, h) => h,
+            };
+            // Attributes describe the intended ratio even when the srcset width
+            // is larger, so prefer them.
+            let aspect_ratio = match (attr_width, attr_height, width, height) {
+                (Some(w), Some(h), _, _) | (_, _, Some(w), Some(h)) => Some(w as f64 / h as f64),
+                _ => None,
+            };
+
+            Some(ImageInfo {
+                src,
+                alt: attr("alt").map(str::to_string),
+                width,
+                height,
+                aspect_ratio,
+            })
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Microdata
 //
@@ -799,6 +1144,31 @@ pub(super) fn merge_schema_entities(base: &mut Jsonld, extra: Jsonld) {
     }
 }
 
+/// Entities of the unified view whose `@type` (any entry of an array, short
+/// name, case-insensitive) is `ty`. Exact duplicates are dropped.
+pub(super) fn filter_schema_type(items: Jsonld, ty: &str) -> Jsonld {
+    let ty = ty.rsplit(['/', '#', ':']).next().unwrap_or(ty);
+    let mut out: Jsonld = Vec::new();
+    for item in items {
+        let types: Vec<&str> = match item.get("@type") {
+            Some(serde_json::Value::String(s)) => vec![s.as_str()],
+            Some(serde_json::Value::Array(arr)) => {
+                arr.iter().filter_map(serde_json::Value::as_str).collect()
+            }
+            _ => Vec::new(),
+        };
+        let matches = types.iter().any(|t| {
+            t.rsplit(['/', '#', ':'])
+                .next()
+                .is_some_and(|short| short.eq_ignore_ascii_case(ty))
+        });
+        if matches && !out.contains(&item) {
+            out.push(item);
+        }
+    }
+    out
+}
+
 /// Two entities are "the same" when they share a `@type` (short name) and a
 /// non-empty `name`. Without a name there's no safe identity — don't merge.
 fn same_schema_entity(a: &serde_json::Value, b: &serde_json::Value) -> bool {
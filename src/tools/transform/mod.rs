@@ -1,6 +1,10 @@
 //! Transform Tools
 
 mod tests;
+pub mod types;
+mod utils;
+
+pub use types::{InlineImagePolicy, MarkdownOptions};
 
 use htmd::HtmlToMarkdown;
 
@@ -8,16 +12,26 @@ use crate::types::{Html, Markdown};
 
 /// Transform HTML to Markdown suitable for LLM input.
 pub async fn transform_markdown(html: &Html) -> Markdown {
+    transform_markdown_with(html, &MarkdownOptions::default()).await
+}
+
+/// [`transform_markdown`] with options, e.g. dropping decorative inline
+/// images (icons, logos, spacers) with
+/// [`InlineImagePolicy::Drop`]. Default options are exactly
+/// [`transform_markdown`].
+pub async fn transform_markdown_with(html: &Html, options: &MarkdownOptions) -> Markdown {
     let html = html.clone();
+    let policy = options.inline_image_policy;
     // htmd parses the DOM (CPU-bound), so convert off the async runtime.
     tokio::task::spawn_blocking(move || {
-        let markdown = HtmlToMarkdown::builder()
-            .skip_tags(vec!["script", "style", "head", "noscript", "iframe", "svg"])
-            .build()
-            .convert(html.as_str())
-            .unwrap_or_default();
+        let mut builder = HtmlToMarkdown::builder()
+            .skip_tags(vec!["script", "style", "head", "noscript", "iframe", "svg"]);
+        if policy != InlineImagePolicy::Keep {
+            builder = builder.add_handler(vec!["img"], utils::image_handler(policy));
+        }
+        let markdown = builder.build().convert(html.as_str()).unwrap_or_default();
         Markdown::new(markdown)
     })
     .await
-    .expect("transform_markdown: spawn_blocking failed")
+    .expect("transform_markdown_with: spawn_blocking failed")
 }
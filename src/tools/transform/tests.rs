@@ -32,3 +32,55 @@ async fn preserves_links_and_lists() {
 async fn empty_input_yields_empty_output() {
     assert_eq!(transform_markdown(&"".into()).await.as_str(), "");
 }
+
+const IMAGE_SNIPPET: &str = r#"<p>Shake well <img src="/icons/star.png" width="16" height="16" alt="Rated"> and serve.</p>
+<figure><img src="/photos/margarita.jpg" width="800" height="600" alt="A margarita"></figure>"#;
+
+#[tokio::test]
+async fn keep_policy_renders_every_image() {
+    let md = transform_markdown_with(&IMAGE_SNIPPET.into(), &MarkdownOptions::default()).await;
+    assert!(
+        md.as_str().contains("![Rated](/icons/star.png)"),
+        "got: {md:?}"
+    );
+    assert!(
+        md.as_str()
+            .contains("![A margarita](/photos/margarita.jpg)"),
+        "got: {md:?}"
+    );
+    assert_eq!(md, transform_markdown(&IMAGE_SNIPPET.into()).await);
+}
+
+#[tokio::test]
+async fn drop_policy_removes_decorative_images_only() {
+    let options = MarkdownOptions {
+        inline_image_policy: InlineImagePolicy::Drop,
+    };
+    let md = transform_markdown_with(&IMAGE_SNIPPET.into(), &options).await;
+    assert!(!md.as_str().contains("star.png"), "got: {md:?}");
+    assert!(!md.as_str().contains("Rated"), "got: {md:?}");
+    assert!(md.as_str().contains("Shake well"), "got: {md:?}");
+    assert!(
+        md.as_str()
+            .contains("![A margarita](/photos/margarita.jpg)"),
+        "got: {md:?}"
+    );
+}
+
+#[tokio::test]
+async fn alt_text_only_policy_replaces_decorative_images_with_alt() {
+    let options = MarkdownOptions {
+        inline_image_policy: InlineImagePolicy::AltTextOnly,
+    };
+    let md = transform_markdown_with(&IMAGE_SNIPPET.into(), &options).await;
+    assert!(!md.as_str().contains("star.png"), "got: {md:?}");
+    assert!(
+        md.as_str().contains("Shake well Rated and serve."),
+        "got: {md:?}"
+    );
+    assert!(
+        md.as_str()
+            .contains("![A margarita](/photos/margarita.jpg)"),
+        "got: {md:?}"
+    );
+}
@@ -0,0 +1,21 @@
+/// What [`transform_markdown_with`](super::transform_markdown_with) does with
+/// decorative inline images (icons, logos, spacers, badges). Content images
+/// are always kept as `![alt](src)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InlineImagePolicy {
+    /// Keep every image as `![alt](src)` — the default.
+    #[default]
+    Keep,
+    /// Drop decorative images entirely.
+    Drop,
+    /// Replace decorative images with their alt text (dropped when it's empty).
+    AltTextOnly,
+}
+
+/// Options for [`transform_markdown_with`](super::transform_markdown_with).
+/// Defaults reproduce plain `transform_markdown`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownOptions {
+    /// How decorative inline images are rendered.
+    pub inline_image_policy: InlineImagePolicy,
+}
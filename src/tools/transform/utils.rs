@@ -0,0 +1,54 @@
+use htmd::Element;
+
+use super::types::InlineImagePolicy;
+use crate::tools::extract::utils::is_decorative_image;
+
+/// `<img>` handler applying `policy` to decorative images and rendering the
+/// rest exactly as htmd's own handler does: `![alt](src "title")`, with
+/// quotes in alt/title and parentheses in the source escaped, and `<…>`
+/// around a source containing spaces.
+pub(super) fn image_handler(policy: InlineImagePolicy) -> impl Fn(Element) -> Option<String> {
+    move |element: Element| {
+        let attr = |name: &str| {
+            element
+                .attrs
+                .iter()
+                .find(|a| &*a.name.local == name)
+                .map(|a| a.value.trim())
+                .filter(|v| !v.is_empty())
+        };
+        let src = attr("src")?;
+        let alt = attr("alt").map(escape_alt_title).unwrap_or_default();
+
+        if is_decorative_image(attr, src) {
+            match policy {
+                InlineImagePolicy::Keep => {}
+                InlineImagePolicy::Drop => return None,
+                InlineImagePolicy::AltTextOnly => {
+                    return attr("alt")
+                        .map(|alt| alt.split_whitespace().collect::<Vec<_>>().join(" "));
+                }
+            }
+        }
+
+        let src = src.replace('(', "\\(").replace(')', "\\)");
+        let title = attr("title")
+            .map(|t| format!(" \"{}\"", escape_alt_title(t)))
+            .unwrap_or_default();
+        Some(if src.contains(' ') {
+            format!("![{alt}](<{src}{title}>)")
+        } else {
+            format!("![{alt}]({src}{title})")
+        })
+    }
+}
+
+/// Trims each line of an alt/title value, escapes its quotes and drops the
+/// blank ones.
+fn escape_alt_title(text: &str) -> String {
+    text.lines()
+        .map(|line| line.trim().replace('"', "\\\""))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
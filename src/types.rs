@@ -1,10 +1,17 @@
 //! Shared Types
 
 use dashmap::DashMap;
+use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::errors::QrawlError;
+use crate::tools::fetch::strategies::{RetryBudget, PER_HOST_CONCURRENCY};
+use crate::tools::fetch::PoolConfig;
+use crate::tools::scrape::ParseCache;
 
 /// Fetch strategy for pipeline `fetch_*` steps.
 ///
@@ -19,7 +26,7 @@ pub enum FetchStrategy {
 /// Default fetch timeout.
 pub const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
 
-/// Default concurrency.
+/// Default concurrency, also used when a context's `concurrency` is 0.
 pub const DEFAULT_CONCURRENCY: usize = 1000;
 
 /// Context to chain tools
@@ -28,20 +35,40 @@ pub struct Context {
     pub fetch_strategy: FetchStrategy,
     pub fetch_timeout: Duration,
     pub concurrency: usize,
+    /// Max in-flight fetches per host, under the global `concurrency`.
+    pub per_host_concurrency: usize,
     pub depth: usize,
     pub limit: usize,
     /// Allow domains pre-fetch. Empty = allow all.
     pub allow_domains: Vec<String>,
     /// Block domains pre-fetch. Empty = block none.
     pub block_domains: Vec<String>,
+    /// Block domains matching a glob/regex pre-fetch. Empty = block none.
+    pub block_domain_patterns: Vec<DomainPattern>,
     /// Allow URLs pre-fetch. Empty = allow all.
     pub allow_urls: Vec<String>,
     /// Block URLs pre-fetch. Empty = block none.
     pub block_urls: Vec<String>,
+    /// Follow only discovered links whose URL matches. `None` = follow all.
+    pub url_filter: Option<Regex>,
+    /// Follow only discovered links at most this many path segments deep.
+    /// `None` = any depth.
+    pub max_path_depth: Option<usize>,
     /// Include schema.org types post-fetch. Empty = include all.
     pub include_schemas: Vec<String>,
     /// Exclude schema.org types post-fetch. Empty = exclude none.
     pub exclude_schemas: Vec<String>,
+    /// Auto-cascade retries per profile after a retryable failure. 0 = none.
+    pub max_retries: usize,
+    /// Retry budget shared by every fetch in the pipeline. `None` = unlimited.
+    pub retry_budget: Option<Arc<RetryBudget>>,
+    /// Connection-pool settings for the fetch clients.
+    pub pool: PoolConfig,
+    /// Parse cache shared by the scrape tools in this pipeline. `None` = off.
+    pub parse_cache: Option<Arc<ParseCache>>,
+    /// Cancels the pipeline's fetches and batches when triggered. `None` =
+    /// runs to completion.
+    pub cancellation: Option<CancellationToken>,
 }
 
 impl Context {
@@ -51,14 +78,23 @@ impl Context {
             fetch_strategy: FetchStrategy::Auto,
             fetch_timeout: DEFAULT_FETCH_TIMEOUT,
             concurrency: DEFAULT_CONCURRENCY,
+            per_host_concurrency: PER_HOST_CONCURRENCY,
             depth: 0,
             limit: 0,
             allow_domains: Vec::new(),
             block_domains: Vec::new(),
+            block_domain_patterns: Vec::new(),
             allow_urls: Vec::new(),
             block_urls: Vec::new(),
+            url_filter: None,
+            max_path_depth: None,
             include_schemas: Vec::new(),
             exclude_schemas: Vec::new(),
+            max_retries: 0,
+            retry_budget: None,
+            pool: PoolConfig::DEFAULT,
+            parse_cache: None,
+            cancellation: None,
         }
     }
 
@@ -79,11 +115,37 @@ impl Context {
         self
     }
 
+    /// Default concurrency for multi-URL operations (batches, template
+    /// streams). 0 means [`DEFAULT_CONCURRENCY`].
     pub fn with_concurrency(mut self, concurrency: usize) -> Self {
         self.concurrency = concurrency;
         self
     }
 
+    /// `concurrency`, with 0 resolved to [`DEFAULT_CONCURRENCY`].
+    pub fn effective_concurrency(&self) -> usize {
+        match self.concurrency {
+            0 => DEFAULT_CONCURRENCY,
+            n => n,
+        }
+    }
+
+    /// Cap in-flight fetches to any single host, so a batch of URLs on one
+    /// site isn't fetched at full `concurrency` while different hosts still
+    /// run in parallel. 0 means [`PER_HOST_CONCURRENCY`].
+    pub fn with_per_host_concurrency(mut self, per_host_concurrency: usize) -> Self {
+        self.per_host_concurrency = per_host_concurrency;
+        self
+    }
+
+    /// `per_host_concurrency`, with 0 resolved to [`PER_HOST_CONCURRENCY`].
+    pub fn effective_per_host_concurrency(&self) -> usize {
+        match self.per_host_concurrency {
+            0 => PER_HOST_CONCURRENCY,
+            n => n,
+        }
+    }
+
     pub fn with_depth(mut self, depth: usize) -> Self {
         self.depth = depth;
         self
@@ -104,6 +166,11 @@ impl Context {
         self
     }
 
+    pub fn with_block_domain_patterns(mut self, patterns: Vec<DomainPattern>) -> Self {
+        self.block_domain_patterns = patterns;
+        self
+    }
+
     pub fn with_allow_urls(mut self, urls: Vec<String>) -> Self {
         self.allow_urls = urls;
         self
@@ -114,6 +181,22 @@ impl Context {
         self
     }
 
+    /// Focus a crawl: discovered links whose URL doesn't match `pattern`
+    /// (unanchored, e.g. `/recipes/`) are neither fetched nor recursed into.
+    /// Seed URLs are always processed.
+    pub fn with_url_filter(mut self, pattern: Regex) -> Self {
+        self.url_filter = Some(pattern);
+        self
+    }
+
+    /// Keep a crawl shallow: discovered links deeper than `depth` path
+    /// segments (see [`url_path_depth`](crate::tools::crawl::url_path_depth))
+    /// are neither fetched nor recursed into. Seed URLs are always processed.
+    pub fn with_max_path_depth(mut self, depth: usize) -> Self {
+        self.max_path_depth = Some(depth);
+        self
+    }
+
     pub fn with_include_schemas(mut self, schemas: Vec<String>) -> Self {
         self.include_schemas = schemas;
         self
@@ -123,6 +206,150 @@ impl Context {
         self.exclude_schemas = schemas;
         self
     }
+
+    /// Retry retryable fetch failures (429, 503, timeouts, …) up to
+    /// `max_retries` times per profile in the Auto cascade.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Cap retries across the whole pipeline: once `budget` is spent for its
+    /// window, failures cascade or fail fast instead of retrying, so a site
+    /// that is down isn't hammered by every item of a batch.
+    pub fn with_retry_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.retry_budget = Some(budget);
+        self
+    }
+
+    /// Override the fetch clients' connection pool. High-concurrency batches
+    /// against few hosts raise `max_idle_per_host` so connections are reused
+    /// instead of churned.
+    pub fn with_pool(mut self, pool: PoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Cache scrape results for the last `capacity` distinct pages, so running
+    /// `scrape_body`, `scrape_metadata`, `scrape_jsonld` … over the same page
+    /// parses it once. The cache belongs to this context and its clones —
+    /// other pipelines keep their own — and least-recently-used pages are
+    /// evicted first; 0 turns it off (the default).
+    pub fn with_parse_cache(mut self, capacity: usize) -> Self {
+        self.parse_cache = (capacity > 0).then(|| Arc::new(ParseCache::new(capacity)));
+        self
+    }
+
+    /// Stop the pipeline early when `token` is cancelled (e.g. on client
+    /// disconnect): in-flight fetches fail with
+    /// [`FetchError::Cancelled`](crate::tools::fetch::FetchError::Cancelled)
+    /// and [`batch`](crate::tools::batch::batch) stops, returning the items
+    /// that already completed.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Whether `url`'s host passes this context's domain filters
+    /// (`allow_domains`, `block_domains`, `block_domain_patterns`) — the same
+    /// rules fetch applies, checked at parse time so pipeline stages can drop
+    /// discovered links before queueing them — and `url` matches `url_filter`
+    /// and `max_path_depth`. No filters = everything passes.
+    pub fn allows_url(&self, url: &str) -> bool {
+        if let Some(filter) = &self.url_filter {
+            if !filter.is_match(url) {
+                return false;
+            }
+        }
+        if let Some(max) = self.max_path_depth {
+            if crate::tools::crawl::url_path_depth(url) > max {
+                return false;
+            }
+        }
+        self.allows_domain(url)
+    }
+
+    /// Whether `url`'s host passes this context's domain filters alone
+    /// (`allow_domains`, `block_domains`, `block_domain_patterns`). Sibling
+    /// detection uses this to skip blocked links when picking each child.
+    pub fn allows_domain(&self, url: &str) -> bool {
+        let allow = (!self.allow_domains.is_empty()).then_some(self.allow_domains.as_slice());
+        let block = (!self.block_domains.is_empty()).then_some(self.block_domains.as_slice());
+        if !crate::tools::fetch::is_url_allowed(url, allow, block) {
+            return false;
+        }
+        if self.block_domain_patterns.is_empty() {
+            return true;
+        }
+        match url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
+        {
+            Some(host) => !self.block_domain_patterns.iter().any(|p| p.matches(&host)),
+            None => true,
+        }
+    }
+}
+
+/// A compiled host pattern, for domain filters that exact/suffix matching can't
+/// express — "every hearstapps.com image CDN", "`*.blogspot.*`".
+///
+/// Construct via [`DomainPattern::glob`] (`*` = any run of characters, `?` = one,
+/// anchored at both ends) or [`DomainPattern::regex`] (unanchored, as written).
+/// Both match case-insensitively against the host. An invalid pattern errors at
+/// construction, so a typo surfaces when the `Context` is built rather than
+/// silently matching nothing mid-crawl.
+#[derive(Debug, Clone)]
+pub struct DomainPattern {
+    pattern: String,
+    regex: Regex,
+}
+
+impl DomainPattern {
+    /// Compile a host glob such as `*.delish.com` or `*.blogspot.*`.
+    pub fn glob(pattern: &str) -> Result<Self, QrawlError> {
+        let glob = pattern.trim().to_ascii_lowercase();
+        if glob.is_empty()
+            || !glob
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '*' | '?'))
+        {
+            return Err(QrawlError::new(format!("invalid domain glob: {pattern:?}")));
+        }
+        let body: String = glob
+            .chars()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                c => regex::escape(&c.to_string()),
+            })
+            .collect();
+        Self::compile(pattern, &format!("(?i)^{body}$"))
+    }
+
+    /// Compile a host regex such as `^hips?\.hearstapps\.com$`.
+    pub fn regex(pattern: &str) -> Result<Self, QrawlError> {
+        Self::compile(pattern, &format!("(?i){pattern}"))
+    }
+
+    fn compile(pattern: &str, source: &str) -> Result<Self, QrawlError> {
+        Regex::new(source)
+            .map(|regex| Self {
+                pattern: pattern.to_string(),
+                regex,
+            })
+            .map_err(|e| QrawlError::new(format!("invalid domain pattern {pattern:?}: {e}")))
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether `host` matches.
+    pub fn matches(&self, host: &str) -> bool {
+        self.regex.is_match(host)
+    }
 }
 
 tokio::task_local! {
@@ -156,18 +383,107 @@ pub fn fetch_cache_put(url: &str, html: &str) {
     });
 }
 
+// Fetches whose response depends on more than the URL (a non-default
+// `Accept-Language`, …) key on the canonical URL plus that `variant`, joined by
+// a newline no URL contains, so they never answer — or get answered by — a
+// plain `fetch_cache_get`.
+fn fetch_cache_variant_key(url: &str, variant: &str) -> String {
+    format!(
+        "{}\n{variant}",
+        crate::tools::normalize::normalize_social(url)
+    )
+}
+
+pub(crate) fn fetch_cache_get_variant(url: &str, variant: &str) -> Option<String> {
+    let key = fetch_cache_variant_key(url, variant);
+    FETCH_CACHE
+        .try_with(|cache| cache.get(&key).map(|v| v.clone()))
+        .ok()
+        .flatten()
+}
+
+pub(crate) fn fetch_cache_put_variant(url: &str, variant: &str, html: &str) {
+    let key = fetch_cache_variant_key(url, variant);
+    let _ = FETCH_CACHE.try_with(|cache| {
+        cache.insert(key, html.to_string());
+    });
+}
+
 pub fn get_fetch_strategy() -> FetchStrategy {
     CTX.try_with(|ctx| ctx.fetch_strategy)
         .ok()
         .unwrap_or(FetchStrategy::Auto)
 }
 
+/// [`Context::effective_concurrency`] for the task-local `CTX`;
+/// [`DEFAULT_CONCURRENCY`] outside a pipeline.
+pub fn get_concurrency() -> usize {
+    CTX.try_with(|ctx| ctx.effective_concurrency())
+        .unwrap_or(DEFAULT_CONCURRENCY)
+}
+
+/// [`Context::effective_per_host_concurrency`] for the task-local `CTX`;
+/// [`PER_HOST_CONCURRENCY`] outside a pipeline.
+pub fn get_per_host_concurrency() -> usize {
+    CTX.try_with(|ctx| ctx.effective_per_host_concurrency())
+        .unwrap_or(PER_HOST_CONCURRENCY)
+}
+
 pub fn get_fetch_timeout() -> Duration {
     CTX.try_with(|ctx| ctx.fetch_timeout)
         .ok()
         .unwrap_or(DEFAULT_FETCH_TIMEOUT)
 }
 
+/// `(max_retries, retry_budget)` from the task-local `CTX`; no retries outside
+/// a pipeline.
+pub(crate) fn get_retry_settings() -> (usize, Option<Arc<RetryBudget>>) {
+    CTX.try_with(|ctx| (ctx.max_retries, ctx.retry_budget.clone()))
+        .unwrap_or((0, None))
+}
+
+/// Pool settings from the task-local `CTX`; [`PoolConfig::DEFAULT`] outside a
+/// pipeline.
+pub(crate) fn get_pool_config() -> PoolConfig {
+    CTX.try_with(|ctx| ctx.pool).unwrap_or(PoolConfig::DEFAULT)
+}
+
+/// Parse cache from the task-local `CTX`; `None` (off) outside a pipeline.
+pub(crate) fn get_parse_cache() -> Option<Arc<ParseCache>> {
+    CTX.try_with(|ctx| ctx.parse_cache.clone()).unwrap_or(None)
+}
+
+/// Cancellation token from the task-local `CTX`; `None` outside a pipeline.
+pub(crate) fn get_cancellation() -> Option<CancellationToken> {
+    CTX.try_with(|ctx| ctx.cancellation.clone()).unwrap_or(None)
+}
+
+/// [`Context::allows_url`] for the task-local `CTX`; `true` outside a pipeline.
+pub fn ctx_allows_url(url: &str) -> bool {
+    CTX.try_with(|ctx| ctx.allows_url(url)).unwrap_or(true)
+}
+
+/// [`Context::allows_domain`] for the task-local `CTX`; `true` outside a
+/// pipeline.
+pub(crate) fn ctx_allows_domain(url: &str) -> bool {
+    CTX.try_with(|ctx| ctx.allows_domain(url)).unwrap_or(true)
+}
+
+/// `tokio::task::spawn_blocking` that carries the task-local `CTX` into the
+/// blocking closure, for sync code that reads the context (sibling link choice
+/// via [`ctx_allows_domain`]).
+pub(crate) fn spawn_blocking_in_ctx<F, R>(f: F) -> tokio::task::JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let ctx = CTX.try_with(Arc::clone).ok();
+    tokio::task::spawn_blocking(move || match ctx {
+        Some(ctx) => CTX.sync_scope(ctx, f),
+        None => f(),
+    })
+}
+
 /// Raw HTML content — the substrate every page-scraping tool consumes.
 ///
 /// A newtype over `String` (not the parsed `scraper::Html`, which is `!Send` and